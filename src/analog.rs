@@ -0,0 +1,92 @@
+// Threshold band of an analog transmitter, several bands share the channel of the
+// transmitter. Limits are kept as typed, an empty limit leaves the band open on that side
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnalogBand {
+    pub low: String,
+    pub high: String,
+    pub hysteresis: String,
+}
+
+fn parse_limit(text: &str) -> Result<Option<f64>, ()> {
+    match text.trim() {
+        "" => Ok(None),
+        text => text.replace(',', ".").parse::<f64>().map(Some).map_err(|_| ()),
+    }
+}
+
+impl AnalogBand {
+    pub fn new() -> Self {
+        AnalogBand {
+            low: String::new(),
+            high: String::new(),
+            hysteresis: String::new(),
+        }
+    }
+
+    // Low and high limits with the hysteresis, None when they do not describe a band
+    pub fn get_limits(&self) -> Option<(Option<f64>, Option<f64>, f64)> {
+        let low = parse_limit(&self.low).ok()?;
+        let high = parse_limit(&self.high).ok()?;
+        let hysteresis = parse_limit(&self.hysteresis).ok()?.unwrap_or(0.);
+
+        if hysteresis < 0. || (low.is_none() && high.is_none()) {
+            return None;
+        }
+        if let (Some(low), Some(high)) = (low, high) {
+            if low >= high {
+                return None;
+            }
+        }
+
+        Some((low, high, hysteresis))
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.get_limits().is_some()
+    }
+
+    // Short form for column titles, e.g. "20..80 ±2" or "> 80"
+    pub fn get_range(&self) -> String {
+        let range = match (self.low.trim(), self.high.trim()) {
+            ("", high) => format!("< {}", high),
+            (low, "") => format!(">= {}", low),
+            (low, high) => format!("{}..{}", low, high),
+        };
+
+        match self.hysteresis.trim() {
+            "" | "0" => range,
+            hysteresis => format!("{} ±{}", range, hysteresis),
+        }
+    }
+
+    // Structured text of the comparator: the band switches on inside the limits and
+    // only switches off once the value leaves them by more than the hysteresis
+    pub fn get_logic(&self, input: &str) -> String {
+        let (low, high, hysteresis) = match self.get_limits() {
+            Some(limits) => limits,
+            None => return String::new(),
+        };
+        let compare = |low: Option<f64>, high: Option<f64>| {
+            let mut terms = vec![];
+
+            if let Some(low) = low {
+                terms.push(format!("{} >= {}", input, low));
+            }
+            if let Some(high) = high {
+                terms.push(format!("{} < {}", input, high));
+            }
+
+            terms.join(" AND ")
+        };
+
+        let on = compare(low, high);
+
+        if hysteresis <= 0. {
+            return format!("Q := {};", on);
+        }
+
+        let hold = compare(low.map(|low| low - hysteresis), high.map(|high| high + hysteresis));
+
+        format!("Q := ({}) OR (Q AND {});", on, hold)
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::HashSet;
+
+use crate::configs::{IOElement, IOElementMessage, IO_CONFIG};
+use crate::configuration::{
+    language_pack_conastants::{BULK_RANGE_LIMIT, BULK_RANGE_ORDER, IMPORT_ISSUE_ADDRESS_RANGE},
+    FrameTypes, SignalTypes
+};
+use crate::hardware::HwAddress;
+
+const BULK_LIMIT: u32 = 1000;
+
+#[derive(Debug, Clone)]
+pub enum IOBulkMessage {
+    PatternChanged(String),
+    FromChanged(String),
+    ToChanged(String),
+    FrameTypeSelected(FrameTypes),
+    SignalTypeSelected(SignalTypes),
+    FirstAddressChanged(String),
+    Cancel,
+}
+
+#[derive(Debug, Clone)]
+pub struct BulkRow {
+    pub name: String,
+    pub hw_address: Option<HwAddress>,
+    pub duplicate: bool,
+}
+
+// Family of elements such as SensorBelt1..SensorBelt12
+#[derive(Debug, Clone)]
+pub struct BulkPattern {
+    pub pattern: String,
+    pub from: String,
+    pub to: String,
+    pub frame_type: FrameTypes,
+    pub signal_type: SignalTypes,
+    pub first_address: String,
+}
+
+// The first run of '#' is replaced by the number padded to its length, without one
+// the number is appended
+fn expand_name(pattern: &str, number: u32) -> String {
+    match pattern.find('#') {
+        Some(start) => {
+            let width = pattern[start..].chars().take_while(|c| *c == '#').count();
+
+            format!(
+                "{}{:0width$}{}",
+                &pattern[..start], number, &pattern[start + width..], width = width
+            )
+        },
+        None => format!("{}{}", pattern, number)
+    }
+}
+
+impl BulkPattern {
+    pub fn new() -> Self {
+        BulkPattern {
+            pattern: String::new(),
+            from: String::from("1"),
+            to: String::from("1"),
+            frame_type: FrameTypes::State,
+            signal_type: SignalTypes::Input,
+            first_address: String::new(),
+        }
+    }
+
+    pub fn update(&mut self, message: IOBulkMessage) {
+        match message {
+            IOBulkMessage::PatternChanged(pattern) => self.pattern = pattern,
+            IOBulkMessage::FromChanged(from) => self.from = from,
+            IOBulkMessage::ToChanged(to) => self.to = to,
+            IOBulkMessage::FrameTypeSelected(frame_type) => self.frame_type = frame_type,
+            IOBulkMessage::SignalTypeSelected(signal_type) => self.signal_type = signal_type,
+            IOBulkMessage::FirstAddressChanged(address) => self.first_address = address,
+            _ => ()
+        }
+    }
+
+    // Addresses follow the free channels of the signal type, starting at the first
+    // address or at the first free channel when it is left empty.
+    // Errors are language pack keys
+    pub fn get_rows(&self) -> Result<Vec<BulkRow>, &'static str> {
+        if self.pattern.trim().is_empty() {
+            return Ok(vec![]);
+        }
+
+        let from = self.from.trim().parse::<u32>().unwrap_or(0);
+        let to = self.to.trim().parse::<u32>().unwrap_or(0);
+
+        if to < from {
+            return Err(BULK_RANGE_ORDER);
+        }
+        if to - from >= BULK_LIMIT {
+            return Err(BULK_RANGE_LIMIT);
+        }
+
+        let first_address = match self.first_address.trim() {
+            "" => None,
+            address => Some(HwAddress::parse(address).ok_or(IMPORT_ISSUE_ADDRESS_RANGE)?),
+        };
+
+        let io_config = unsafe {
+            &IO_CONFIG
+        }.as_ref().unwrap().borrow();
+        let mut used: HashSet<HwAddress> = io_config.get_all_elelments().iter()
+            .filter_map(|element| element.borrow().get_channel())
+            .map(|(hw_address, _)| hw_address)
+            .collect();
+        let names: HashSet<String> = io_config.get_all_elelments().iter()
+            .map(|element| element.borrow().get_data().0)
+            .collect();
+
+        let mut channels = io_config.get_hardware().get_channels(self.signal_type);
+        if let Some(first_address) = first_address {
+            match channels.iter().position(|address| *address == first_address) {
+                Some(start) => {
+                    channels.drain(..start);
+                },
+                // Outside of the declared modules the channels simply count up
+                None => {
+                    channels = (first_address.channel..=u16::MAX)
+                        .map(|channel| HwAddress {channel: channel, ..first_address})
+                        .collect()
+                }
+            }
+        }
+        let mut channels = channels.into_iter();
+
+        Ok((from..=to).map(|number| {
+            let name = expand_name(self.pattern.trim(), number);
+            let hw_address = channels.find(|address| !used.contains(address));
+
+            if let Some(hw_address) = hw_address {
+                used.insert(hw_address);
+            }
+
+            BulkRow {
+                duplicate: names.contains(&name),
+                name: name,
+                hw_address: hw_address,
+            }
+        }).collect())
+    }
+}
+
+impl BulkRow {
+    pub fn is_valid(&self) -> bool {
+        !self.duplicate && self.hw_address.is_some()
+    }
+
+    pub fn to_element(&self, pattern: &BulkPattern) -> IOElement {
+        let mut element = IOElement::new();
+
+        element.update(IOElementMessage::NameInputChanged(self.name.clone()));
+        element.update(IOElementMessage::FrameTypeSelected(pattern.frame_type));
+        element.update(IOElementMessage::SignalTypeSelected(pattern.signal_type));
+        if let Some(hw_address) = self.hw_address {
+            element.update(IOElementMessage::HwSelected(hw_address.to_string()));
+        }
+
+        element
+    }
+}
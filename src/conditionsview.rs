@@ -8,14 +8,16 @@ use crate::configuration:: {
     language_pack_conastants::{
         FIELD_NAME,  FIELD_ADDRESS, BUTTON_EDIT_STATES_SUBPROGRAM_STEP,
         BUTTON_EDIT_CONTROLS_SUBPROGRAM_STEP,  SUBPROGRAM_TYPE_BLOCKED,
-        SUBPROGRAM_TYPE_CRITICAL
+        SUBPROGRAM_TYPE_CRITICAL, BUTTON_MOVE_UP, BUTTON_MOVE_DOWN,
+        BUTTON_DUPLICATE
     },
-    style_config::{DEFAULT_SPACING, SUBPRORAM_DESCRIPTION_WIDTH}, GLOBAL_CONFIG,
+    style_config::{self, DEFAULT_SPACING, SUBPRORAM_DESCRIPTION_WIDTH}, GLOBAL_CONFIG,
     delete_icon, FrameTypes,
 };
 
 use crate::configs::{
-    ConditionsConfigElement, ConditionsConfigElementMessage, SUBPROGRAMS_CONFIG
+    ConditionsConfigElement, ConditionsConfigElementMessage, SUBPROGRAMS_CONFIG,
+    TransitionTarget, MoveDirection
 };
 
 #[derive(Debug)]
@@ -23,7 +25,10 @@ pub struct ConditonsElementView {
     state_edit_button: button::State,
     control_edit_button: button::State,
     delete_button: button::State,
-    address_list: pick_list::State<usize>,
+    move_up_button: button::State,
+    move_down_button: button::State,
+    duplicate_button: button::State,
+    address_list: pick_list::State<TransitionTarget>,
     description_input: text_input::State,
     conditonselement: Rc<RefCell<ConditionsConfigElement>>,
 }
@@ -34,70 +39,89 @@ impl<'a> ConditonsElementView {
             delete_button: button::State::new(),
             state_edit_button: button::State::new(),
             control_edit_button: button::State::new(),
+            move_up_button: button::State::new(),
+            move_down_button: button::State::new(),
+            duplicate_button: button::State::new(),
             description_input: text_input::State::new(),
             address_list: pick_list::State::default(),
             conditonselement: conditonselement.clone(),
         }
     }
 
-    fn generate_address_list() -> Vec<usize> {
+    fn generate_address_list() -> Vec<TransitionTarget> {
         let subprogramconfig = unsafe {
             &SUBPROGRAMS_CONFIG
         }.as_ref().unwrap();
 
-        let mut address_list: Vec<usize> = vec![];
-
-        for address in 1..subprogramconfig.borrow().get_last_address() {
-            address_list.push(address);
-        }
-
-        address_list
-
+        subprogramconfig.borrow().get_transition_targets()
     }
 
     pub fn view(&'a mut self) -> Element<'a, ConditionsConfigElementMessage> {
-        let (description, _, _, blocked, critical, address) =
+        let (description, _, _, blocked, critical, transition) =
             self.conditonselement.borrow().get_data();
+        let address_list = Self::generate_address_list();
+        let transition = transition.filter(|target| address_list.contains(target));
 
         let description_input = TextInput::new(
             &mut self.description_input,
             "", &description, ConditionsConfigElementMessage::DescriptionChanged
-        ).size(30).width(Length::Units(SUBPRORAM_DESCRIPTION_WIDTH));
+        ).size(30).width(Length::Units(SUBPRORAM_DESCRIPTION_WIDTH)).style(style_config::TextInput);
 
         let address_list = PickList::new(
             &mut self.address_list,
-            Self::generate_address_list(),
-            Some(address),
-            ConditionsConfigElementMessage::AddressSelected
-        );
+            address_list,
+            transition,
+            ConditionsConfigElementMessage::TransitionTargetSelected
+        ).style(style_config::PickList);
 
         let config = unsafe {
             &GLOBAL_CONFIG
-        }.as_ref().unwrap();
+        }.as_ref().unwrap().borrow();
 
         let edit_state_button = Button::new(
             &mut self.state_edit_button,
             Text::new(config.get_field(BUTTON_EDIT_STATES_SUBPROGRAM_STEP).to_string())
-        ).on_press(ConditionsConfigElementMessage::PickConditions(FrameTypes::State));
+        ).style(style_config::Button::Icon)
+            .on_press(ConditionsConfigElementMessage::PickConditions(FrameTypes::State));
 
         let edit_control_button = Button::new(
             &mut self.control_edit_button,
             Text::new(config.get_field(BUTTON_EDIT_CONTROLS_SUBPROGRAM_STEP).to_string())
-        ).on_press(ConditionsConfigElementMessage::PickConditions(FrameTypes::Control));
+        ).style(style_config::Button::Icon)
+            .on_press(ConditionsConfigElementMessage::PickConditions(FrameTypes::Control));
 
         let blocked_check = Checkbox::new(
             blocked,
             config.get_field(SUBPROGRAM_TYPE_BLOCKED).to_string().as_str(),
             ConditionsConfigElementMessage::BlockedPicked
-         );
+         ).style(style_config::Checkbox);
 
         let critical_check = Checkbox::new(
             critical,
             config.get_field(SUBPROGRAM_TYPE_CRITICAL).to_string().as_str(),
             ConditionsConfigElementMessage::CriticalPicked
-         );
+         ).style(style_config::Checkbox);
+
+        let move_up_button = Button::new(
+            &mut self.move_up_button,
+            Text::new(config.get_field(BUTTON_MOVE_UP).to_string())
+        ).style(style_config::Button::Icon)
+            .on_press(ConditionsConfigElementMessage::MoveCondition(MoveDirection::Up));
+
+        let move_down_button = Button::new(
+            &mut self.move_down_button,
+            Text::new(config.get_field(BUTTON_MOVE_DOWN).to_string())
+        ).style(style_config::Button::Icon)
+            .on_press(ConditionsConfigElementMessage::MoveCondition(MoveDirection::Down));
+
+        let duplicate_button = Button::new(
+            &mut self.duplicate_button,
+            Text::new(config.get_field(BUTTON_DUPLICATE).to_string())
+        ).style(style_config::Button::Icon)
+            .on_press(ConditionsConfigElementMessage::DuplicateCondition);
 
         let delete_button = Button::new(&mut self.delete_button, delete_icon())
+            .style(style_config::Button::Icon)
             .on_press(ConditionsConfigElementMessage::DeleteCondition);
 
         Row::new()
@@ -114,6 +138,9 @@ impl<'a> ConditonsElementView {
                 .push(Text::new(config.get_field(FIELD_ADDRESS).to_string()
                                                               .as_str()))
                 .push(address_list))
+            .push(move_up_button)
+            .push(move_down_button)
+            .push(duplicate_button)
             .push(delete_button)
 
             .into()
@@ -1,37 +1,99 @@
-use std::{rc::Rc, cell::RefCell};
+use std::{rc::Rc, cell::RefCell, collections::HashSet, sync::atomic::{AtomicUsize, Ordering}};
 
 use crate::configuration:: {
-    FrameTypes, SignalTypes, SubprogramTypes, Operators, IOElementStates,
+    FrameTypes, SignalTypes, SubprogramTypes, Operators, IOElementStates, ElementKind,
+    GLOBAL_CONFIG, language_pack_conastants::{SUBPROGRAM_STEP, FIELD_CONDITION},
+};
+use crate::tsv::parse_io_elements;
+use crate::csvimport::IOImportMessage;
+use crate::bulkcreate::IOBulkMessage;
+use crate::timer::{Timer, TimerMode};
+use crate::counter::{Counter, CounterMode};
+use crate::analog::AnalogBand;
+use crate::hardware::{
+    HwAddress, HwField, HwIssue, HwLayout, HwLayoutMessage, HwAssignment, HwAssignMessage
 };
 
 pub static mut IO_CONFIG: Option<Rc<RefCell<IOConfig>>> = None;
 pub static mut SUBPROGRAMS_CONFIG: Option<Rc<RefCell<SubprogramConfig>>> = None;
 pub static mut CONDTIONS_CONFIG: Option<Rc<RefCell<CondtionsConfig>>> = None;
 
+static NEXT_UID: AtomicUsize = AtomicUsize::new(1);
+
+// Identifiers that survive reordering, used to reference objects from messages
+fn generate_uid() -> usize {
+    NEXT_UID.fetch_add(1, Ordering::Relaxed)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveDirection {
+    Up,
+    Down,
+}
+
+// Swaps the element with its neighbour, moves past the list bounds are ignored
+pub fn move_element<T>(elements: &mut Vec<T>, i: usize, direction: MoveDirection) {
+    let target = match direction {
+        MoveDirection::Up => i.checked_sub(1),
+        MoveDirection::Down => Some(i + 1),
+    };
+
+    if let Some(target) = target.filter(|target| *target < elements.len()) {
+        elements.swap(i, target);
+    }
+}
+
+// Takes the element out and puts it at the target index, moves past the list bounds are ignored
+pub fn move_element_to<T>(elements: &mut Vec<T>, i: usize, target: usize) {
+    if i < elements.len() && target < elements.len() {
+        let element = elements.remove(i);
+        elements.insert(target, element);
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum IOElementMessage {
     NameInputChanged(String),
     FrameTypeSelected(FrameTypes),
     SignalTypeSelected(SignalTypes),
     HwSelected(String),
+    HwFieldChanged(HwField, String),
+    KindSelected(ElementKind),
+    TimerModeSelected(TimerMode),
+    TimerPresetChanged(String),
+    CounterModeSelected(CounterMode),
+    CounterPresetChanged(String),
+    AnalogLowChanged(String),
+    AnalogHighChanged(String),
+    AnalogHysteresisChanged(String),
     DeleteElement,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct IOElement {
+    uid: usize,
     name: String,
     frame_type: FrameTypes,
     signal_type: SignalTypes,
-    hw_address: u8,
+    hw_address: HwAddress,
+    kind: ElementKind,
+    timer: Timer,
+    counter: Counter,
+    analog: AnalogBand,
 }
 
 impl IOElement {
     pub fn new() -> Self {
         IOElement {
+            uid: generate_uid(),
             name: String::new(),
             frame_type: FrameTypes::State,
             signal_type: SignalTypes::Input,
-            hw_address: 0
+            hw_address: HwAddress::default(),
+            kind: ElementKind::Discrete,
+            timer: Timer::new(),
+            counter: Counter::new(),
+            analog: AnalogBand::new(),
         }
     }
 
@@ -47,35 +109,235 @@ impl IOElement {
                 self.signal_type = signal_type
             },
             IOElementMessage::HwSelected(hw) => {
-                let parsed = hw.parse::<u8>();
-                if parsed.is_ok() {
-                    self.hw_address = parsed.unwrap()
+                if let Some(hw_address) = HwAddress::parse(hw.as_str()) {
+                    self.hw_address = hw_address
                 }
-            }
+            },
+            IOElementMessage::HwFieldChanged(field, value) => {
+                if let Some(hw_address) = self.hw_address.with_field(field, value.as_str()) {
+                    self.hw_address = hw_address
+                }
+            },
+            IOElementMessage::KindSelected(kind) => {
+                self.kind = kind
+            },
+            IOElementMessage::TimerModeSelected(mode) => {
+                self.timer.mode = mode
+            },
+            IOElementMessage::TimerPresetChanged(preset) => {
+                let preset = match preset.trim() {
+                    "" => Some(0),
+                    preset => preset.parse::<u32>().ok(),
+                };
+
+                if let Some(preset) = preset {
+                    self.timer.preset_ms = preset
+                }
+            },
+            IOElementMessage::CounterModeSelected(mode) => {
+                self.counter.mode = mode
+            },
+            IOElementMessage::CounterPresetChanged(preset) => {
+                let preset = match preset.trim() {
+                    "" => Some(0),
+                    preset => preset.parse::<u32>().ok(),
+                };
+
+                if let Some(preset) = preset {
+                    self.counter.preset = preset
+                }
+            },
+            IOElementMessage::AnalogLowChanged(low) => {
+                self.analog.low = low
+            },
+            IOElementMessage::AnalogHighChanged(high) => {
+                self.analog.high = high
+            },
+            IOElementMessage::AnalogHysteresisChanged(hysteresis) => {
+                self.analog.hysteresis = hysteresis
+            },
             _ => {}
         }
     }
 
-    pub fn get_data(&self) -> (String, FrameTypes, SignalTypes, u8) {
+    pub fn get_uid(&self) -> usize {
+        self.uid
+    }
+
+    pub fn get_data(&self) -> (String, FrameTypes, SignalTypes, HwAddress) {
         (self.name.clone(), self.frame_type, self.signal_type, self.hw_address)
     }
+
+    pub fn get_kind(&self) -> ElementKind {
+        self.kind
+    }
+
+    pub fn get_timer(&self) -> Timer {
+        self.timer
+    }
+
+    pub fn get_counter(&self) -> Counter {
+        self.counter
+    }
+
+    pub fn get_analog(&self) -> AnalogBand {
+        self.analog.clone()
+    }
+
+    // Internal flags, timers and counters are driven from the control columns and checked
+    // in the state ones, analog bands are derived conditions and only appear in the state
+    // columns
+    pub fn has_frame_type(&self, frame_type: FrameTypes) -> bool {
+        match self.kind {
+            ElementKind::Discrete => {
+                self.frame_type == frame_type || FrameTypes::Flag == self.frame_type
+            },
+            ElementKind::Timer | ElementKind::Counter => FrameTypes::Flag != frame_type,
+            ElementKind::Analog => FrameTypes::State == frame_type,
+        }
+    }
+
+    pub fn is_flag(&self) -> bool {
+        self.kind == ElementKind::Discrete && FrameTypes::Flag == self.frame_type
+    }
+
+    // Only discrete signals occupy a bit channel of the declared modules
+    pub fn is_physical(&self) -> bool {
+        self.kind == ElementKind::Discrete && !self.is_flag()
+    }
+
+    // Channel taken by the element, analog bands share the input channel of their transmitter
+    pub fn get_channel(&self) -> Option<(HwAddress, SignalTypes)> {
+        match self.kind {
+            ElementKind::Discrete if !self.is_flag() => Some((self.hw_address, self.signal_type)),
+            ElementKind::Analog => Some((self.hw_address, SignalTypes::Input)),
+            _ => None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IOElementId(pub usize);
+
+impl std::fmt::Display for IOElementId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let io_config = unsafe {
+            &IO_CONFIG
+        }.as_ref().unwrap();
+
+        match io_config.borrow().get_element_by_uid(self.0) {
+            Some(element) => {
+                let (name, ..) = element.borrow().get_data();
+                write!(f, "{}", name)
+            },
+            None => write!(f, "-"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IOElementUsage {
+    SubprogramStep(String, usize),
+    Condition(String),
+}
+
+impl std::fmt::Display for IOElementUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+
+        match self {
+            IOElementUsage::SubprogramStep(name, step) => write!(
+                f, "{} / {} {}",
+                name, config.get_field(SUBPROGRAM_STEP).to_string(), step
+            ),
+            IOElementUsage::Condition(description) => write!(
+                f, "{}: {}",
+                config.get_field(FIELD_CONDITION).to_string(), description
+            ),
+        }
+    }
+}
+
+// Every step and conditions row holding a reference to the IO element
+pub fn get_io_element_usages(uid: usize) -> Vec<IOElementUsage> {
+    let subprogramconfig = unsafe {
+        &SUBPROGRAMS_CONFIG
+    }.as_ref().unwrap();
+
+    let conditionsconfig = unsafe {
+        &CONDTIONS_CONFIG
+    }.as_ref().unwrap();
+
+    let mut usages = subprogramconfig.borrow().get_io_element_usages(uid);
+    usages.append(&mut conditionsconfig.borrow().get_io_element_usages(uid));
+
+    usages
+}
+
+fn remove_io_element_references(uid: usize) {
+    unsafe {
+        &SUBPROGRAMS_CONFIG
+    }.as_ref().unwrap().borrow().remove_io_element_references(uid);
+
+    unsafe {
+        &CONDTIONS_CONFIG
+    }.as_ref().unwrap().borrow().remove_io_element_references(uid);
+}
+
+fn replace_io_element_references(uid: usize, element: Rc<RefCell<IOElement>>) {
+    unsafe {
+        &SUBPROGRAMS_CONFIG
+    }.as_ref().unwrap().borrow().replace_io_element_references(uid, element.clone());
+
+    unsafe {
+        &CONDTIONS_CONFIG
+    }.as_ref().unwrap().borrow().replace_io_element_references(uid, element);
 }
 
 #[derive(Debug, Clone)]
 pub enum IOConfigMessage {
     AddNewElement,
     IOElementMessage(usize, IOElementMessage),
+    DeleteElementConfirmed(usize),
+    DeleteElementReassigned(usize, IOElementId),
+    DeleteElementCanceled,
+    ReassignElementSelected(IOElementId),
+    PasteElements(String),
+    OpenImport,
+    ImportMessage(IOImportMessage),
+    ImportElements(Vec<IOElement>, bool),
+    OpenBulk,
+    BulkMessage(IOBulkMessage),
+    OpenHardware,
+    CloseHardware,
+    HardwareMessage(HwLayoutMessage),
+    OpenAssign,
+    AssignMessage(HwAssignMessage),
+    AssignHwAddresses(bool),
 }
 
 #[derive(Debug, Clone)]
 pub struct IOConfig {
     elements: Vec<Rc<RefCell<IOElement>>>,
+    hardware: HwLayout,
 }
 
 impl IOConfig {
     pub fn new() -> Self {
         IOConfig {
-            elements: vec![]
+            elements: vec![],
+            hardware: HwLayout::new(),
+        }
+    }
+
+    pub fn deep_copy(&self) -> Self {
+        IOConfig {
+            elements: self.elements.iter()
+                .map(|element| Rc::new(RefCell::new(element.borrow().clone())))
+                .collect(),
+            hardware: self.hardware.clone(),
         }
     }
 
@@ -89,7 +351,7 @@ impl IOConfig {
         let mut elements: Vec<Rc<RefCell<IOElement>>> = vec![];
 
         for element in &self.elements {
-            if element.borrow().frame_type == frame_type {
+            if element.borrow().has_frame_type(frame_type) {
                 elements.push(element.clone());
             }
         }
@@ -127,6 +389,12 @@ impl IOConfig {
         element
     }
 
+    pub fn get_element_by_uid(&self, uid: usize) -> Option<Rc<RefCell<IOElement>>> {
+        self.elements.iter()
+            .find(|element| element.borrow().get_uid() == uid)
+            .cloned()
+    }
+
     pub fn get_all_elelments(&self) -> Vec<Rc<RefCell<IOElement>>> {
         self.elements.clone()
     }
@@ -139,6 +407,84 @@ impl IOConfig {
         self.elements.last().unwrap().clone()
     }
 
+    pub fn get_hardware(&self) -> HwLayout {
+        self.hardware.clone()
+    }
+
+    pub fn get_iec_address(&self, element: &IOElement) -> Option<String> {
+        if !element.is_physical() {
+            return None;
+        }
+
+        self.hardware.get_iec_address(element.hw_address, element.signal_type)
+    }
+
+    // Channels shared with other elements and channels missing from the declared modules
+    pub fn get_hw_issues(&self, element: &IOElement) -> Vec<HwIssue> {
+        let (hw_address, signal_type) = match element.get_channel() {
+            Some(channel) => channel,
+            None => return vec![]
+        };
+        let is_analog = |element: &IOElement| ElementKind::Analog == element.kind;
+
+        let mut issues: Vec<HwIssue> = self.elements.iter()
+            .map(|other| other.borrow())
+            .filter(|other| other.uid != element.uid && !(is_analog(element) && is_analog(other)))
+            .filter(|other| other.get_channel().map(|(address, _)| address) == Some(hw_address))
+            .map(|other| HwIssue::DuplicateChannel(other.name.clone()))
+            .collect();
+
+        issues.append(&mut self.hardware.check(hw_address, signal_type));
+
+        issues
+    }
+
+    // Free channels for the elements without a valid address in list order, with keep off
+    // every element is re-packed from the first channel of its signal type
+    pub fn plan_hw_addresses(&self, keep: bool) -> Vec<HwAssignment> {
+        // Analog bands are not moved, the channels of their transmitters stay taken
+        let mut used: HashSet<HwAddress> = self.elements.iter()
+            .map(|element| element.borrow())
+            .filter(|element| ElementKind::Analog == element.kind)
+            .map(|element| element.hw_address)
+            .collect();
+        let mut assigned: Vec<&Rc<RefCell<IOElement>>> = vec![];
+
+        for element in self.elements.iter().filter(|element| element.borrow().is_physical()) {
+            let element_ref = element.borrow();
+            let is_valid = self.hardware
+                .check(element_ref.hw_address, element_ref.signal_type)
+                .is_empty();
+
+            if keep && is_valid && used.insert(element_ref.hw_address) {
+                continue;
+            }
+
+            assigned.push(element);
+        }
+
+        let mut inputs = self.hardware.get_channels(SignalTypes::Input).into_iter();
+        let mut outputs = self.hardware.get_channels(SignalTypes::Output).into_iter();
+
+        assigned.into_iter()
+            .map(|element| {
+                let element = element.borrow();
+                let channels = match element.signal_type {
+                    SignalTypes::Input => &mut inputs,
+                    SignalTypes::Output => &mut outputs,
+                };
+
+                HwAssignment {
+                    uid: element.uid,
+                    name: element.name.clone(),
+                    from: element.hw_address,
+                    to: channels.find(|address| !used.contains(address)),
+                }
+            })
+            .filter(|assignment| assignment.to != Some(assignment.from))
+            .collect()
+    }
+
     pub fn update(
         &mut self,
         message: IOConfigMessage
@@ -147,7 +493,7 @@ impl IOConfig {
             IOConfigMessage::IOElementMessage(i, message) => {
                 match message {
                     IOElementMessage::DeleteElement => {
-                        self.elements.remove(i);
+                        self.update(IOConfigMessage::DeleteElementConfirmed(i))
                     },
                     _ => {
                         if let Some(element) = self.elements.get_mut(i) {
@@ -159,7 +505,64 @@ impl IOConfig {
             },
             IOConfigMessage::AddNewElement => {
                 self.add_new_element(IOElement::new())
-            }
+            },
+            IOConfigMessage::HardwareMessage(message) => {
+                self.hardware.update(message)
+            },
+            IOConfigMessage::AssignHwAddresses(keep) => {
+                for assignment in self.plan_hw_addresses(keep) {
+                    if let (Some(element), Some(to)) =
+                        (self.get_element_by_uid(assignment.uid), assignment.to) {
+                        element.borrow_mut().hw_address = to;
+                    }
+                }
+            },
+            IOConfigMessage::PasteElements(text) => {
+                for element in parse_io_elements(text.as_str()) {
+                    self.add_new_element(element)
+                }
+            },
+            IOConfigMessage::ImportElements(elements, replace) => {
+                let replaced: Vec<Rc<RefCell<IOElement>>> = if replace {
+                    self.elements.drain(..).collect()
+                } else {
+                    vec![]
+                };
+
+                for element in elements {
+                    self.add_new_element(element)
+                }
+
+                // References move to the imported element of the same name
+                for element in replaced {
+                    let (name, ..) = element.borrow().get_data();
+                    let uid = element.borrow().get_uid();
+
+                    match self.get_elelment_by_name(name) {
+                        Some(replacement) => replace_io_element_references(uid, replacement),
+                        None => remove_io_element_references(uid)
+                    }
+                }
+            },
+            IOConfigMessage::DeleteElementConfirmed(i) => {
+                if i < self.elements.len() {
+                    let element = self.elements.remove(i);
+                    remove_io_element_references(element.borrow().get_uid());
+                }
+            },
+            IOConfigMessage::DeleteElementReassigned(i, IOElementId(uid)) => {
+                if i < self.elements.len() {
+                    let element = self.elements.remove(i);
+
+                    match self.get_element_by_uid(uid) {
+                        Some(replacement) => replace_io_element_references(
+                            element.borrow().get_uid(), replacement
+                        ),
+                        None => remove_io_element_references(element.borrow().get_uid())
+                    }
+                }
+            },
+            _ => ()
         }
     }
 
@@ -170,9 +573,11 @@ pub enum IOElementCoditionsMessage {
     DeleteElement(FrameTypes),
     IOElementMessage(usize, IOElementMessage),
     StateChanged(IOElementStates),
-    IOElementSelected(String),
+    IOElementSelected(IOElementId),
 }
 
+// The frame type is the list holding the condition, internal flags are tested in the
+// state list and set or reset in the control list
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct IOElementCoditions {
     element: Option<Rc<RefCell<IOElement>>>,
@@ -194,12 +599,12 @@ impl IOElementCoditions {
             IOElementCoditionsMessage::StateChanged(state) => {
                 self.state = state
             },
-            IOElementCoditionsMessage::IOElementSelected(name) => {
+            IOElementCoditionsMessage::IOElementSelected(IOElementId(uid)) => {
                 let io_config = unsafe {
                     &IO_CONFIG
                 }.as_ref().unwrap();
 
-                self.element = io_config.borrow().get_elelment_by_name(name)
+                self.element = io_config.borrow().get_element_by_uid(uid)
             }
             _ => {}
         }
@@ -209,6 +614,97 @@ impl IOElementCoditions {
         (self.element.clone(), self.state.clone(), self.frame_type)
     }
 
+    // IO references are resolved against the given config by uid
+    pub fn deep_copy(&self, io_config: &IOConfig) -> Self {
+        IOElementCoditions {
+            element: self.get_element_uid()
+                .and_then(|uid| io_config.get_element_by_uid(uid)),
+            state: self.state,
+            frame_type: self.frame_type
+        }
+    }
+
+    pub fn get_element_uid(&self) -> Option<usize> {
+        self.element.as_ref().map(|element| element.borrow().get_uid())
+    }
+
+}
+
+// Copies keep referencing the same IO elements
+fn duplicate_conditions(
+    conditions: &Vec<Rc<RefCell<IOElementCoditions>>>
+) -> Vec<Rc<RefCell<IOElementCoditions>>> {
+    conditions.iter()
+        .map(|condition| Rc::new(RefCell::new(condition.borrow().clone())))
+        .collect()
+}
+
+fn deep_copy_conditions(
+    conditions: &Vec<Rc<RefCell<IOElementCoditions>>>, io_config: &IOConfig
+) -> Vec<Rc<RefCell<IOElementCoditions>>> {
+    conditions.iter()
+        .map(|condition| Rc::new(RefCell::new(condition.borrow().deep_copy(io_config))))
+        .collect()
+}
+
+// Active -> Inactive -> Any -> no condition -> Active
+fn cycle_io_element_conditions(
+    conditions: &mut Vec<Rc<RefCell<IOElementCoditions>>>,
+    element: Rc<RefCell<IOElement>>,
+    frame_type: FrameTypes
+) {
+    let uid = element.borrow().get_uid();
+    let position = conditions.iter()
+        .position(|condition| condition.borrow().get_element_uid() == Some(uid));
+
+    match position {
+        None => {
+            conditions.push(Rc::new(RefCell::new(IOElementCoditions {
+                element: Some(element.clone()),
+                state: IOElementStates::Active,
+                frame_type: frame_type
+            })));
+        },
+        Some(i) => {
+            let state = conditions[i].borrow().state;
+
+            match state {
+                IOElementStates::Active => {
+                    conditions[i].borrow_mut().state = IOElementStates::Inactive
+                },
+                IOElementStates::Inactive => {
+                    conditions[i].borrow_mut().state = IOElementStates::Any
+                },
+                IOElementStates::Any => {
+                    conditions.remove(i);
+                }
+            }
+        }
+    }
+}
+
+fn conditions_use_io_element(
+    conditions: &Vec<Rc<RefCell<IOElementCoditions>>>, uid: usize
+) -> bool {
+    conditions.iter()
+        .any(|condition| condition.borrow().get_element_uid() == Some(uid))
+}
+
+fn remove_io_element_conditions(
+    conditions: &mut Vec<Rc<RefCell<IOElementCoditions>>>, uid: usize
+) {
+    conditions.retain(|condition| condition.borrow().get_element_uid() != Some(uid));
+}
+
+fn replace_io_element_conditions(
+    conditions: &Vec<Rc<RefCell<IOElementCoditions>>>, uid: usize,
+    element: Rc<RefCell<IOElement>>
+) {
+    for condition in conditions {
+        if condition.borrow().get_element_uid() == Some(uid) {
+            condition.borrow_mut().element = Some(element.clone());
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -220,10 +716,15 @@ pub enum SubprogramStepMessage {
     PickConditions(FrameTypes),
     IOElementCoditionsMessage(usize, IOElementCoditionsMessage),
     OperatorSelected(Operators),
+    CycleIOElementState(IOElementId, FrameTypes),
+    MoveStep(MoveDirection),
+    MoveStepTo(usize),
+    DuplicateStep,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SubprogramStep {
+    uid: usize,
     id: usize,
     descripton: String,
     merge_operator: Operators,
@@ -235,6 +736,7 @@ pub struct SubprogramStep {
 impl SubprogramStep {
     pub fn new() -> Self {
         SubprogramStep {
+            uid: generate_uid(),
             id: 0,
             merge_operator: Operators::AND,
             state_conditions: vec![],
@@ -244,6 +746,29 @@ impl SubprogramStep {
         }
     }
 
+    pub fn get_uid(&self) -> usize {
+        self.uid
+    }
+
+    pub fn deep_copy(&self, io_config: &IOConfig) -> Self {
+        SubprogramStep {
+            state_conditions: deep_copy_conditions(&self.state_conditions, io_config),
+            control_conditions: deep_copy_conditions(&self.control_conditions, io_config),
+            descripton: self.descripton.clone(),
+            ..*self
+        }
+    }
+
+    pub fn duplicate(&self) -> Self {
+        SubprogramStep {
+            uid: generate_uid(),
+            state_conditions: duplicate_conditions(&self.state_conditions),
+            control_conditions: duplicate_conditions(&self.control_conditions),
+            descripton: self.descripton.clone(),
+            ..*self
+        }
+    }
+
     pub fn add_new_conditon(
         &mut self,
         condition :Rc<RefCell<IOElementCoditions>>
@@ -254,12 +779,46 @@ impl SubprogramStep {
             FrameTypes::State => {
                 self.state_conditions.push(condition.clone())
             },
-            FrameTypes::Control => {
+            FrameTypes::Control | FrameTypes::Flag => {
                 self.control_conditions.push(condition.clone())
             }
         }
     }
 
+    pub fn uses_io_element(&self, uid: usize) -> bool {
+        conditions_use_io_element(&self.state_conditions, uid) ||
+            conditions_use_io_element(&self.control_conditions, uid)
+    }
+
+    pub fn remove_io_element(&mut self, uid: usize) {
+        remove_io_element_conditions(&mut self.state_conditions, uid);
+        remove_io_element_conditions(&mut self.control_conditions, uid);
+    }
+
+    pub fn replace_io_element(&self, uid: usize, element: Rc<RefCell<IOElement>>) {
+        replace_io_element_conditions(&self.state_conditions, uid, element.clone());
+        replace_io_element_conditions(&self.control_conditions, uid, element);
+    }
+
+    // The frame type is the column group of the cell, elements such as internal flags
+    // appear in both groups
+    pub fn cycle_io_element_state(&mut self, uid: usize, frame_type: FrameTypes) {
+        let io_config = unsafe {
+            &IO_CONFIG
+        }.as_ref().unwrap();
+
+        if let Some(element) = io_config.borrow().get_element_by_uid(uid) {
+            match frame_type {
+                FrameTypes::State => cycle_io_element_conditions(
+                    &mut self.state_conditions, element.clone(), frame_type
+                ),
+                FrameTypes::Control | FrameTypes::Flag => cycle_io_element_conditions(
+                    &mut self.control_conditions, element.clone(), frame_type
+                ),
+            }
+        }
+    }
+
     pub fn get_conditions(
         &self, frame_type: FrameTypes
     ) -> Vec<Rc<RefCell<IOElementCoditions>>> {
@@ -267,7 +826,7 @@ impl SubprogramStep {
             FrameTypes::State => {
                 self.state_conditions.clone()
             },
-            FrameTypes::Control => {
+            FrameTypes::Control | FrameTypes::Flag => {
                 self.control_conditions.clone()
             }
         }
@@ -280,7 +839,7 @@ impl SubprogramStep {
             FrameTypes::State => {
                 self.state_conditions.last().unwrap().clone()
             },
-            FrameTypes::Control => {
+            FrameTypes::Control | FrameTypes::Flag => {
                 self.control_conditions.last().unwrap().clone()
             }
         }
@@ -315,6 +874,9 @@ impl SubprogramStep {
             },
             SubprogramStepMessage::DescriptionChanged(descripton) => {
                 self.descripton = descripton
+            },
+            SubprogramStepMessage::CycleIOElementState(IOElementId(uid), frame_type) => {
+                self.cycle_io_element_state(uid, frame_type)
             }
             SubprogramStepMessage::IOElementCoditionsMessage(i, message) => {
                 match message {
@@ -323,7 +885,7 @@ impl SubprogramStep {
                             FrameTypes::State => {
                                 self.state_conditions.remove(i);
                             },
-                            FrameTypes::Control => {
+                            FrameTypes::Control | FrameTypes::Flag => {
                                 self.control_conditions.remove(i);
                             }
                         }
@@ -336,7 +898,7 @@ impl SubprogramStep {
                                     mut_condition.update(message);
                                 }
                             },
-                            FrameTypes::Control => {
+                            FrameTypes::Control | FrameTypes::Flag => {
                                 if let Some(condition) = self.control_conditions.get_mut(i) {
                                     let mut mut_condition = condition.borrow_mut();
                                     mut_condition.update(message);
@@ -356,6 +918,9 @@ pub enum SubprogramMessage {
     AddNewSubprogramStep,
     SubprogramEdit,
     SubprogramDelete,
+    SubprogramMove(MoveDirection),
+    SubprogramMoveTo(usize),
+    SubprogramDuplicate,
     SubprogramTypeSelected(SubprogramTypes),
     SubprogramDescrptionChanged(String),
     SubprogramStepMessage(usize, SubprogramStepMessage),
@@ -363,6 +928,7 @@ pub enum SubprogramMessage {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Subprogram {
+    uid: usize,
     pub address: usize,
     name: String,
     priority_type: SubprogramTypes,
@@ -373,6 +939,7 @@ pub struct Subprogram {
 impl Subprogram {
     pub fn new() -> Self {
         Subprogram {
+            uid: generate_uid(),
             address: 0,
             name: String::new(),
             priority_type: SubprogramTypes::Dflt,
@@ -385,6 +952,35 @@ impl Subprogram {
         (self.address, self.name.clone(), self.priority_type, self.steps.clone())
     }
 
+    pub fn uses_io_element(&self, uid: usize) -> bool {
+        self.steps.iter().any(|step| step.borrow().uses_io_element(uid))
+    }
+
+    pub fn get_uid(&self) -> usize {
+        self.uid
+    }
+
+    pub fn deep_copy(&self, io_config: &IOConfig) -> Self {
+        Subprogram {
+            name: self.name.clone(),
+            steps: self.steps.iter()
+                .map(|step| Rc::new(RefCell::new(step.borrow().deep_copy(io_config))))
+                .collect(),
+            ..*self
+        }
+    }
+
+    pub fn duplicate(&self) -> Self {
+        Subprogram {
+            uid: generate_uid(),
+            name: self.name.clone(),
+            steps: self.steps.iter()
+                .map(|step| Rc::new(RefCell::new(step.borrow().duplicate())))
+                .collect(),
+            ..*self
+        }
+    }
+
     pub fn get_current_editable_step_id(&self) -> usize {
         self.current_step_edit
     }
@@ -405,6 +1001,15 @@ impl Subprogram {
         self.steps.len()
     }
 
+    fn update_steps_ids(&mut self) {
+        for i in 0..self.steps.len() {
+            if let Some(step) = self.steps.get_mut(i) {
+                let mut mut_step = step.borrow_mut();
+                mut_step.update(SubprogramStepMessage::ChangeId(i + 1));
+            }
+        }
+    }
+
     pub fn update(
         &mut self,
         message: SubprogramMessage
@@ -415,14 +1020,21 @@ impl Subprogram {
                 match message {
                     SubprogramStepMessage::DeleteStep => {
                         self.steps.remove(i);
+                        self.update_steps_ids();
+                    },
+                    SubprogramStepMessage::MoveStep(direction) => {
+                        move_element(&mut self.steps, i, direction);
+                        self.update_steps_ids();
+                    },
+                    SubprogramStepMessage::MoveStepTo(target) => {
+                        move_element_to(&mut self.steps, i, target);
+                        self.update_steps_ids();
+                    },
+                    SubprogramStepMessage::DuplicateStep => {
+                        let step = self.steps[i].borrow().duplicate();
 
-                        for i in 0..self.steps.len() {
-                            if let Some(step) = self.steps.get_mut(i) {
-                                let mut mut_step = step.borrow_mut();
-                                mut_step.update(SubprogramStepMessage::ChangeId(i + 1));
-                            }
-
-                        }
+                        self.steps.insert(i + 1, Rc::new(RefCell::new(step)));
+                        self.update_steps_ids();
                     },
                     _ => {
                         if let Some(step) = self.steps.get_mut(i) {
@@ -484,6 +1096,15 @@ impl SubprogramConfig {
         }
     }
 
+    pub fn deep_copy(&self, io_config: &IOConfig) -> Self {
+        SubprogramConfig {
+            subprograms: self.subprograms.iter()
+                .map(|subprogram| Rc::new(RefCell::new(subprogram.borrow().deep_copy(io_config))))
+                .collect(),
+            ..*self
+        }
+    }
+
     pub fn get_last_subprogram(&self) -> Rc<RefCell<Subprogram>> {
         self.subprograms.last().unwrap().clone()
     }
@@ -508,6 +1129,86 @@ impl SubprogramConfig {
         self.subprograms.clone()
     }
 
+    pub fn get_io_element_usages(&self, uid: usize) -> Vec<IOElementUsage> {
+        let mut usages: Vec<IOElementUsage> = vec![];
+
+        for subprogram in &self.subprograms {
+            let subprogram = subprogram.borrow();
+
+            for (i, step) in subprogram.steps.iter().enumerate() {
+                if step.borrow().uses_io_element(uid) {
+                    usages.push(IOElementUsage::SubprogramStep(
+                        subprogram.name.clone(), i + 1
+                    ));
+                }
+            }
+        }
+
+        usages
+    }
+
+    pub fn remove_io_element_references(&self, uid: usize) {
+        for subprogram in &self.subprograms {
+            for step in &subprogram.borrow().steps {
+                step.borrow_mut().remove_io_element(uid);
+            }
+        }
+    }
+
+    pub fn replace_io_element_references(&self, uid: usize, element: Rc<RefCell<IOElement>>) {
+        for subprogram in &self.subprograms {
+            for step in &subprogram.borrow().steps {
+                step.borrow().replace_io_element(uid, element.clone());
+            }
+        }
+    }
+
+    pub fn get_transition_targets(&self) -> Vec<TransitionTarget> {
+        let mut targets: Vec<TransitionTarget> = vec![];
+
+        for subprogram in &self.subprograms {
+            let subprogram = subprogram.borrow();
+
+            targets.push(TransitionTarget::new(subprogram.get_uid(), None));
+
+            for step in &subprogram.steps {
+                targets.push(TransitionTarget::new(
+                    subprogram.get_uid(), Some(step.borrow().get_uid())
+                ));
+            }
+        }
+
+        targets
+    }
+
+    pub fn get_transition_target_data(
+        &self, target: &TransitionTarget
+    ) -> Option<(String, Option<usize>, usize)> {
+        let subprogram = self.subprograms.iter()
+            .find(|subprogram| subprogram.borrow().get_uid() == target.subprogram)?
+            .borrow();
+
+        match target.step {
+            None => Some((subprogram.name.clone(), None, subprogram.address)),
+            Some(step_uid) => {
+                let position = subprogram.steps.iter()
+                    .position(|step| step.borrow().get_uid() == step_uid)?;
+
+                Some((
+                    subprogram.name.clone(),
+                    Some(position + 1),
+                    subprogram.address + position
+                ))
+            }
+        }
+    }
+
+    pub fn resolve_transition_target(&self, target: &TransitionTarget) -> Option<usize> {
+        let (_, _, address) = self.get_transition_target_data(target)?;
+
+        Some(address)
+    }
+
     pub fn update_addresses(&mut self) {
         self.last_address = 1;
         for i in 0..self.subprograms.len() {
@@ -529,6 +1230,17 @@ impl SubprogramConfig {
                     SubprogramMessage::SubprogramDelete => {
                         self.subprograms.remove(i);
                     },
+                    SubprogramMessage::SubprogramMove(direction) => {
+                        move_element(&mut self.subprograms, i, direction);
+                    },
+                    SubprogramMessage::SubprogramMoveTo(target) => {
+                        move_element_to(&mut self.subprograms, i, target);
+                    },
+                    SubprogramMessage::SubprogramDuplicate => {
+                        let subprogram = self.subprograms[i].borrow().duplicate();
+
+                        self.subprograms.insert(i + 1, Rc::new(RefCell::new(subprogram)));
+                    },
                     SubprogramMessage::SubprogramEdit => {
                         self.current_subprogram_edit = i;
                     },
@@ -552,6 +1264,45 @@ impl SubprogramConfig {
 
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransitionTarget {
+    subprogram: usize,
+    step: Option<usize>,
+}
+
+impl TransitionTarget {
+    pub fn new(subprogram: usize, step: Option<usize>) -> Self {
+        TransitionTarget {
+            subprogram: subprogram,
+            step: step
+        }
+    }
+
+    pub fn get_data(&self) -> (usize, Option<usize>) {
+        (self.subprogram, self.step)
+    }
+}
+
+impl std::fmt::Display for TransitionTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let subprogramconfig = unsafe {
+            &SUBPROGRAMS_CONFIG
+        }.as_ref().unwrap();
+
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+
+        match subprogramconfig.borrow().get_transition_target_data(self) {
+            Some((name, Some(step), address)) => write!(
+                f, "{} / {} {} ({})",
+                name, config.get_field(SUBPROGRAM_STEP).to_string(), step, address
+            ),
+            Some((name, None, address)) => write!(f, "{} ({})", name, address),
+            None => write!(f, "-"),
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub enum ConditionsConfigElementMessage {
@@ -562,7 +1313,11 @@ pub enum ConditionsConfigElementMessage {
     CriticalPicked(bool),
     BlockedPicked(bool),
     DescriptionChanged(String),
-    AddressSelected(usize)
+    TransitionTargetSelected(TransitionTarget),
+    CycleIOElementState(IOElementId, FrameTypes),
+    MoveCondition(MoveDirection),
+    MoveConditionTo(usize),
+    DuplicateCondition,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -573,7 +1328,7 @@ pub struct ConditionsConfigElement {
     pub active_condion: FrameTypes,
     critical: bool,
     blocked: bool,
-    address: usize
+    transition: Option<TransitionTarget>
 }
 
 impl ConditionsConfigElement {
@@ -585,7 +1340,7 @@ impl ConditionsConfigElement {
             active_condion: FrameTypes::State,
             critical: false,
             blocked: false,
-            address: 0
+            transition: None
         }
     }
 
@@ -599,12 +1354,46 @@ impl ConditionsConfigElement {
             FrameTypes::State => {
                 self.state_conditions.push(condition.clone())
             },
-            FrameTypes::Control => {
+            FrameTypes::Control | FrameTypes::Flag => {
                 self.control_conditions.push(condition.clone())
             }
         }
     }
 
+    pub fn uses_io_element(&self, uid: usize) -> bool {
+        conditions_use_io_element(&self.state_conditions, uid) ||
+            conditions_use_io_element(&self.control_conditions, uid)
+    }
+
+    pub fn remove_io_element(&mut self, uid: usize) {
+        remove_io_element_conditions(&mut self.state_conditions, uid);
+        remove_io_element_conditions(&mut self.control_conditions, uid);
+    }
+
+    pub fn replace_io_element(&self, uid: usize, element: Rc<RefCell<IOElement>>) {
+        replace_io_element_conditions(&self.state_conditions, uid, element.clone());
+        replace_io_element_conditions(&self.control_conditions, uid, element);
+    }
+
+    // The frame type is the column group of the cell, elements such as internal flags
+    // appear in both groups
+    pub fn cycle_io_element_state(&mut self, uid: usize, frame_type: FrameTypes) {
+        let io_config = unsafe {
+            &IO_CONFIG
+        }.as_ref().unwrap();
+
+        if let Some(element) = io_config.borrow().get_element_by_uid(uid) {
+            match frame_type {
+                FrameTypes::State => cycle_io_element_conditions(
+                    &mut self.state_conditions, element.clone(), frame_type
+                ),
+                FrameTypes::Control | FrameTypes::Flag => cycle_io_element_conditions(
+                    &mut self.control_conditions, element.clone(), frame_type
+                ),
+            }
+        }
+    }
+
     pub fn get_conditions(
         &self, frame_type: FrameTypes
     ) -> Vec<Rc<RefCell<IOElementCoditions>>> {
@@ -612,7 +1401,7 @@ impl ConditionsConfigElement {
             FrameTypes::State => {
                 self.state_conditions.clone()
             },
-            FrameTypes::Control => {
+            FrameTypes::Control | FrameTypes::Flag => {
                 self.control_conditions.clone()
             }
         }
@@ -625,7 +1414,7 @@ impl ConditionsConfigElement {
             FrameTypes::State => {
                 self.state_conditions.last().unwrap().clone()
             },
-            FrameTypes::Control => {
+            FrameTypes::Control | FrameTypes::Flag => {
                 self.control_conditions.last().unwrap().clone()
             }
         }
@@ -633,7 +1422,7 @@ impl ConditionsConfigElement {
 
     pub fn get_data(&self) -> (
         String, Vec<Rc<RefCell<IOElementCoditions>>>,
-        Vec<Rc<RefCell<IOElementCoditions>>>, bool, bool, usize
+        Vec<Rc<RefCell<IOElementCoditions>>>, bool, bool, Option<TransitionTarget>
     ) {
         (
             self.description.clone(),
@@ -641,10 +1430,43 @@ impl ConditionsConfigElement {
             self.control_conditions.clone(),
             self.blocked,
             self.critical,
-            self.address
+            self.transition
         )
     }
 
+    // Numeric address is only known after SubprogramConfig::update_addresses
+    pub fn deep_copy(&self, io_config: &IOConfig) -> Self {
+        ConditionsConfigElement {
+            description: self.description.clone(),
+            state_conditions: deep_copy_conditions(&self.state_conditions, io_config),
+            control_conditions: deep_copy_conditions(&self.control_conditions, io_config),
+            ..*self
+        }
+    }
+
+    pub fn duplicate(&self) -> Self {
+        ConditionsConfigElement {
+            description: self.description.clone(),
+            state_conditions: duplicate_conditions(&self.state_conditions),
+            control_conditions: duplicate_conditions(&self.control_conditions),
+            ..*self
+        }
+    }
+
+    pub fn get_transition_address(&self) -> Option<usize> {
+        let subprogramconfig = unsafe {
+            &SUBPROGRAMS_CONFIG
+        }.as_ref().unwrap();
+
+        subprogramconfig.borrow()
+            .resolve_transition_target(self.transition.as_ref()?)
+    }
+
+    // Target picked but its subprogram or step deleted since
+    pub fn has_unresolved_transition(&self) -> bool {
+        self.transition.is_some() && self.get_transition_address().is_none()
+    }
+
     pub fn update(
         &mut self,
         message: ConditionsConfigElementMessage
@@ -662,8 +1484,11 @@ impl ConditionsConfigElement {
             ConditionsConfigElementMessage::DescriptionChanged(new_descr) => {
                 self.description = new_descr
             },
-            ConditionsConfigElementMessage::AddressSelected(address) => {
-                self.address = address
+            ConditionsConfigElementMessage::TransitionTargetSelected(target) => {
+                self.transition = Some(target)
+            },
+            ConditionsConfigElementMessage::CycleIOElementState(IOElementId(uid), frame_type) => {
+                self.cycle_io_element_state(uid, frame_type)
             },
             ConditionsConfigElementMessage::IOElementCoditionsMessage(i, message) => {
                 match message {
@@ -672,7 +1497,7 @@ impl ConditionsConfigElement {
                             FrameTypes::State => {
                                 self.state_conditions.remove(i);
                             },
-                            FrameTypes::Control => {
+                            FrameTypes::Control | FrameTypes::Flag => {
                                 self.control_conditions.remove(i);
                             }
                         }
@@ -685,7 +1510,7 @@ impl ConditionsConfigElement {
                                     mut_condition.update(message);
                                 }
                             },
-                            FrameTypes::Control => {
+                            FrameTypes::Control | FrameTypes::Flag => {
                                 if let Some(condition) = self.control_conditions.get_mut(i) {
                                     let mut mut_condition = condition.borrow_mut();
                                     mut_condition.update(message);
@@ -728,6 +1553,16 @@ impl CondtionsConfig {
         }
     }
 
+    pub fn deep_copy(&self, io_config: &IOConfig) -> Self {
+        CondtionsConfig {
+            conditions: self.conditions.iter()
+                .map(|condition| Rc::new(RefCell::new(condition.borrow().deep_copy(io_config))))
+                .collect(),
+            state: self.state.clone(),
+            ..*self
+        }
+    }
+
     pub fn get_last_condtions(&self) -> Rc<RefCell<ConditionsConfigElement>> {
         self.conditions.last().unwrap().clone()
     }
@@ -745,13 +1580,39 @@ impl CondtionsConfig {
     }
 
     pub fn sort_conditions(&mut self) {
-        self.conditions.sort_by(|a, b| a.borrow().address.cmp(&b.borrow().address));
+        self.conditions.sort_by(|a, b| {
+            a.borrow().get_transition_address().cmp(&b.borrow().get_transition_address())
+        });
     }
 
     pub fn get_conditions(&self) -> Vec<Rc<RefCell<ConditionsConfigElement>>> {
         self.conditions.clone()
     }
 
+    pub fn get_io_element_usages(&self, uid: usize) -> Vec<IOElementUsage> {
+        let mut usages: Vec<IOElementUsage> = vec![];
+
+        for condition in &self.conditions {
+            if condition.borrow().uses_io_element(uid) {
+                usages.push(IOElementUsage::Condition(condition.borrow().description.clone()));
+            }
+        }
+
+        usages
+    }
+
+    pub fn remove_io_element_references(&self, uid: usize) {
+        for condition in &self.conditions {
+            condition.borrow_mut().remove_io_element(uid);
+        }
+    }
+
+    pub fn replace_io_element_references(&self, uid: usize, element: Rc<RefCell<IOElement>>) {
+        for condition in &self.conditions {
+            condition.borrow().replace_io_element(uid, element.clone());
+        }
+    }
+
     pub fn update(
         &mut self,
         message: CondtionsConfigMessage
@@ -762,6 +1623,17 @@ impl CondtionsConfig {
                     ConditionsConfigElementMessage::DeleteCondition => {
                         self.conditions.remove(i);
                     },
+                    ConditionsConfigElementMessage::MoveCondition(direction) => {
+                        move_element(&mut self.conditions, i, direction);
+                    },
+                    ConditionsConfigElementMessage::MoveConditionTo(target) => {
+                        move_element_to(&mut self.conditions, i, target);
+                    },
+                    ConditionsConfigElementMessage::DuplicateCondition => {
+                        let condition = self.conditions[i].borrow().duplicate();
+
+                        self.conditions.insert(i + 1, Rc::new(RefCell::new(condition)));
+                    },
                     ConditionsConfigElementMessage::PickConditions(_) => {
                         self.current_condition_edit = i;
                     },
@@ -1,19 +1,42 @@
 use iced::{Font, Length, Text, HorizontalAlignment};
 use json::{self, JsonValue};
-use std::{collections::HashMap, fs::File, io::BufReader, io::Read, rc::Rc};
+use std::{
+    cell::RefCell, collections::HashMap, env, fs::{self, File}, io::BufReader, io::Read,
+    path::{Path, PathBuf}, rc::Rc
+};
 
-use crate::configuration::language_pack_conastants::{FIELD_TYPE_STATE, FIELD_TYPE_CONTROL, FIELD_SIGNAL_INPUT, FIELD_SIGNAL_OUTPUT, SUBPROGRAM_TYPE_DEFAULT, SUBPROGRAM_TYPE_CRITICAL, SUBPROGRAM_TYPE_BLOCKED, IO_STATE_ACTIVE, IO_STATE_INACTIVE, IO_STATE_ANY};
+use crate::configuration::language_pack_conastants::{FIELD_TYPE_STATE, FIELD_TYPE_CONTROL, FIELD_TYPE_FLAG, FIELD_SIGNAL_INPUT, FIELD_SIGNAL_OUTPUT, KIND_DISCRETE, KIND_TIMER, KIND_COUNTER, KIND_ANALOG, SUBPROGRAM_TYPE_DEFAULT, SUBPROGRAM_TYPE_CRITICAL, SUBPROGRAM_TYPE_BLOCKED, IO_STATE_ACTIVE, IO_STATE_INACTIVE, IO_STATE_ANY, THEME_LIGHT, THEME_DARK};
+use crate::encoding::Encoding;
 
 use self::language_pack_conastants::DEFAULT;
 
-pub static DEFAULT_LANGUAGE_PACK: &str = "./src/languages/US.json";
-pub static DELETE_BUTTON_PATH: &str = "./src/images/DeleteButton.svg";
-pub static FONTS_PATH: &str = "./src/fonts/icons.ttf";
+// The default pack is built in, so the binary does not depend on the source tree
+pub static DEFAULT_LANGUAGE_PACK: &str = include_str!("./languages/US.json");
+pub static DEFAULT_LANGUAGE_PACK_NAME: &str = "<built-in>";
+pub static LANGUAGES_DIR: &str = "languages";
+pub static APPLICATION_DIR: &str = "tvpplc_generator";
+pub static SETTINGS_FILE: &str = "settings.json";
+pub static DEFAULT_WINDOW_SIZE: (u32, u32) = (1024, 768);
+pub static DEFAULT_OUTPUT_DIR: &str = ".";
+pub static DEFAULT_PROJECT_FILE: &str = "tvpplc_project.json";
+// Longest worksheet name Excel accepts
+const SHEET_NAME_LIMIT: usize = 31;
+const RECENT_PROJECTS_LIMIT: usize = 5;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FrameTypes {
     State,
     Control,
+    Flag,
+}
+
+// Physical IO elements are wired to a channel, the other kinds live in the PLC program
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElementKind {
+    Discrete,
+    Timer,
+    Counter,
+    Analog,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -35,6 +58,19 @@ pub enum SubprogramTypes{
     Blocked
 }
 
+// UI scale in percent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UiScale(pub u16);
+
+pub static UI_SCALES_ALL: &[UiScale] = &[
+    UiScale(50),
+    UiScale(75),
+    UiScale(100),
+    UiScale(125),
+    UiScale(150),
+    UiScale(200),
+];
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum IOElementStates{
     Active,
@@ -43,18 +79,64 @@ pub enum IOElementStates{
 }
 
 
-pub static mut GLOBAL_CONFIG: Option<Rc<Config>> = None;
+pub static mut GLOBAL_CONFIG: Option<Rc<RefCell<Config>>> = None;
+
+// Per user directory for settings and additional language packs
+pub fn get_user_config_dir() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .or_else(|| env::var_os("APPDATA"))
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(base.join(APPLICATION_DIR))
+}
+
+// Per user directory for data files overriding the built-in ones
+pub fn get_user_data_dir() -> Option<PathBuf> {
+    let base = env::var_os("XDG_DATA_HOME")
+        .or_else(|| env::var_os("APPDATA"))
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))?;
+
+    Some(base.join(APPLICATION_DIR))
+}
+
+// Directories searched for language packs, later ones override earlier ones
+pub fn get_language_pack_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = vec![];
+
+    if let Some(dir) = env::current_exe().ok().and_then(|exe| exe.parent().map(PathBuf::from)) {
+        dirs.push(dir.join(LANGUAGES_DIR));
+    }
+
+    if let Some(dir) = get_user_data_dir() {
+        dirs.push(dir.join(LANGUAGES_DIR));
+    }
+
+    dirs
+}
 
 pub mod language_pack_conastants {
     pub static BUTTON_ADD_NEW: &str = "BUTTON_ADD_NEW";
     pub static IOCONFIG_EMPTY: &str = "IOCONFIG_EMPTY";
     pub static BUTTON_BACK: &str = "BUTTON_BACK";
+    pub static BUTTON_CANCEL: &str = "BUTTON_CANCEL";
+    pub static BUTTON_MATRIX_VIEW: &str = "BUTTON_MATRIX_VIEW";
+    pub static BUTTON_LIST_VIEW: &str = "BUTTON_LIST_VIEW";
+    pub static BUTTON_MOVE_UP: &str = "BUTTON_MOVE_UP";
+    pub static BUTTON_MOVE_DOWN: &str = "BUTTON_MOVE_DOWN";
+    pub static BUTTON_DUPLICATE: &str = "BUTTON_DUPLICATE";
+    pub static BUTTON_REMOVE_REFERENCES: &str = "BUTTON_REMOVE_REFERENCES";
+    pub static BUTTON_REASSIGN_REFERENCES: &str = "BUTTON_REASSIGN_REFERENCES";
     pub static BUTTON_EDIT_STATES_SUBPROGRAM_STEP: &str = "BUTTON_EDIT_STATES_SUBPROGRAM_STEP";
     pub static BUTTON_EDIT_CONTROLS_SUBPROGRAM_STEP: &str = "BUTTON_EDIT_CONTROLS_SUBPROGRAM_STEP";
     pub static SUBPROGRAM_STEP: &str = "SUBPROGRAM_STEP";
     pub static OPERATOR: &str = "OPERATOR";
     pub static BUTTON_FINISH: &str = "BUTTON_FINISH";
     pub static BUTTON_GENERATE_TABLE: &str = "BUTTON_GENERATE_TABLE";
+    pub static GENERATE_UNRESOLVED_TRANSITION: &str = "GENERATE_UNRESOLVED_TRANSITION";
+    pub static GENERATE_DONE: &str = "GENERATE_DONE";
+    pub static GENERATE_FAILED: &str = "GENERATE_FAILED";
     pub static BUTTON_NEXT: &str = "BUTTON_NEXT";
     pub static BUTTON_TO_CONDITIONS: &str = "BUTTON_TO_CONDITIONS";
     pub static BUTTON_TO_IO_CONFIGURATION: &str = "BUTTON_TO_IO_CONFIGURATION";
@@ -62,8 +144,25 @@ pub mod language_pack_conastants {
     pub static CREATE_NEW: &str = "CREATE_NEW";
     pub static DEFAULT: &str = "DEFAULT";
     pub static FIELD_ADDRESS: &str = "FIELD_ADDRESS";
+    pub static FIELD_CONDITION: &str = "FIELD_CONDITION";
     pub static FIELD_DESCRIPTION: &str = "FIELD_DESCRIPTION";
     pub static FIELD_HW: &str = "FIELD_HW";
+    pub static FIELD_KIND: &str = "FIELD_KIND";
+    pub static KIND_DISCRETE: &str = "KIND_DISCRETE";
+    pub static KIND_TIMER: &str = "KIND_TIMER";
+    pub static KIND_COUNTER: &str = "KIND_COUNTER";
+    pub static KIND_ANALOG: &str = "KIND_ANALOG";
+    pub static FIELD_TIMER_MODE: &str = "FIELD_TIMER_MODE";
+    pub static FIELD_PRESET_MS: &str = "FIELD_PRESET_MS";
+    pub static FIELD_PRESET: &str = "FIELD_PRESET";
+    pub static FIELD_COUNTER_MODE: &str = "FIELD_COUNTER_MODE";
+    pub static FIELD_PRESET_COUNT: &str = "FIELD_PRESET_COUNT";
+    pub static FIELD_LOW: &str = "FIELD_LOW";
+    pub static FIELD_HIGH: &str = "FIELD_HIGH";
+    pub static FIELD_HYSTERESIS: &str = "FIELD_HYSTERESIS";
+    pub static ANALOG_ISSUE_LIMITS: &str = "ANALOG_ISSUE_LIMITS";
+    pub static FIELD_LOGIC: &str = "FIELD_LOGIC";
+    pub static FIELD_DECLARATION: &str = "FIELD_DECLARATION";
     pub static FIELD_NAME: &str = "FIELD_NAME";
     pub static FIELD_SIGNAL: &str = "FIELD_SIGNAL";
     pub static FIELD_SIGNAL_INPUT: &str = "FIELD_SIGNAL_INPUT";
@@ -71,7 +170,90 @@ pub mod language_pack_conastants {
     pub static FIELD_TYPE: &str = "FIELD_TYPE";
     pub static FIELD_TYPE_CONTROL: &str = "FIELD_TYPE_CONTROL";
     pub static FIELD_TYPE_STATE: &str = "FIELD_TYPE_STATE";
+    pub static FIELD_TYPE_FLAG: &str = "FIELD_TYPE_FLAG";
     pub static INFO: &str = "INFO";
+    pub static FIELD_LANGUAGE: &str = "FIELD_LANGUAGE";
+    pub static FIELD_DOCUMENT_LANGUAGE: &str = "FIELD_DOCUMENT_LANGUAGE";
+    pub static FIELD_BILINGUAL_HEADERS: &str = "FIELD_BILINGUAL_HEADERS";
+    pub static FIELD_OUTPUT_DIR: &str = "FIELD_OUTPUT_DIR";
+    pub static BUTTON_SETTINGS: &str = "BUTTON_SETTINGS";
+    pub static FIELD_THEME: &str = "FIELD_THEME";
+    pub static FIELD_UI_SCALE: &str = "FIELD_UI_SCALE";
+    pub static FIELD_ENCODING: &str = "FIELD_ENCODING";
+    pub static THEME_LIGHT: &str = "THEME_LIGHT";
+    pub static THEME_DARK: &str = "THEME_DARK";
+    pub static NAVIGATION_IO_CONFIGURATION: &str = "NAVIGATION_IO_CONFIGURATION";
+    pub static NAVIGATION_SUBPROGRAMS: &str = "NAVIGATION_SUBPROGRAMS";
+    pub static NAVIGATION_CONDITIONS: &str = "NAVIGATION_CONDITIONS";
+    pub static NAVIGATION_GENERATE: &str = "NAVIGATION_GENERATE";
+    pub static NAVIGATION_PREVIEW: &str = "NAVIGATION_PREVIEW";
+    pub static NAVIGATION_VALIDATION: &str = "NAVIGATION_VALIDATION";
+    pub static NAVIGATION_SIMULATION: &str = "NAVIGATION_SIMULATION";
+    pub static VALIDATION_EMPTY_CONDITION: &str = "VALIDATION_EMPTY_CONDITION";
+    pub static VALIDATION_NO_ISSUES: &str = "VALIDATION_NO_ISSUES";
+    pub static SIMULATION_IO_STATES: &str = "SIMULATION_IO_STATES";
+    pub static SIMULATION_STEPS: &str = "SIMULATION_STEPS";
+    pub static SIMULATION_CONDITIONS: &str = "SIMULATION_CONDITIONS";
+    pub static SIMULATION_NONE: &str = "SIMULATION_NONE";
+    pub static BUTTON_RESET: &str = "BUTTON_RESET";
+    pub static BUTTON_EDIT_DESCRIPTION: &str = "BUTTON_EDIT_DESCRIPTION";
+    pub static BUTTON_SHORTCUTS: &str = "BUTTON_SHORTCUTS";
+    pub static SHORTCUT_ADD_NEW: &str = "SHORTCUT_ADD_NEW";
+    pub static SHORTCUT_DELETE: &str = "SHORTCUT_DELETE";
+    pub static SHORTCUT_MOVE_FOCUS: &str = "SHORTCUT_MOVE_FOCUS";
+    pub static SHORTCUT_CYCLE_STATE: &str = "SHORTCUT_CYCLE_STATE";
+    pub static SHORTCUT_GENERATE: &str = "SHORTCUT_GENERATE";
+    pub static SHORTCUT_SAVE_PROJECT: &str = "SHORTCUT_SAVE_PROJECT";
+    pub static SHORTCUT_UNDO: &str = "SHORTCUT_UNDO";
+    pub static SHORTCUT_REDO: &str = "SHORTCUT_REDO";
+    pub static SHORTCUT_BACK: &str = "SHORTCUT_BACK";
+    pub static SHORTCUT_CHEAT_SHEET: &str = "SHORTCUT_CHEAT_SHEET";
+    pub static SHORTCUT_COPY: &str = "SHORTCUT_COPY";
+    pub static SHORTCUT_COPY_ALL: &str = "SHORTCUT_COPY_ALL";
+    pub static SHORTCUT_PASTE: &str = "SHORTCUT_PASTE";
+    pub static BUTTON_IMPORT_CSV: &str = "BUTTON_IMPORT_CSV";
+    pub static FIELD_CSV_FILE: &str = "FIELD_CSV_FILE";
+    pub static BUTTON_LOAD: &str = "BUTTON_LOAD";
+    pub static FIELD_REPLACE_IO: &str = "FIELD_REPLACE_IO";
+    pub static BUTTON_IMPORT: &str = "BUTTON_IMPORT";
+    pub static IMPORT_VALID_ROWS: &str = "IMPORT_VALID_ROWS";
+    pub static IMPORT_ISSUE_EMPTY_NAME: &str = "IMPORT_ISSUE_EMPTY_NAME";
+    pub static IMPORT_ISSUE_DUPLICATE_NAME: &str = "IMPORT_ISSUE_DUPLICATE_NAME";
+    pub static IMPORT_ISSUE_ADDRESS_RANGE: &str = "IMPORT_ISSUE_ADDRESS_RANGE";
+    pub static IMPORT_ISSUE_UNKNOWN_TYPE: &str = "IMPORT_ISSUE_UNKNOWN_TYPE";
+    pub static IMPORT_ISSUE_UNKNOWN_SIGNAL: &str = "IMPORT_ISSUE_UNKNOWN_SIGNAL";
+    pub static FIELD_RACK: &str = "FIELD_RACK";
+    pub static FIELD_SLOT: &str = "FIELD_SLOT";
+    pub static FIELD_CHANNEL: &str = "FIELD_CHANNEL";
+    pub static FIELD_CHANNELS: &str = "FIELD_CHANNELS";
+    pub static FIELD_IEC_ADDRESS: &str = "FIELD_IEC_ADDRESS";
+    pub static BUTTON_HARDWARE: &str = "BUTTON_HARDWARE";
+    pub static BUTTON_ADD_MODULE: &str = "BUTTON_ADD_MODULE";
+    pub static HARDWARE_EMPTY: &str = "HARDWARE_EMPTY";
+    pub static HW_ISSUE_DUPLICATE: &str = "HW_ISSUE_DUPLICATE";
+    pub static HW_ISSUE_NO_MODULE: &str = "HW_ISSUE_NO_MODULE";
+    pub static HW_ISSUE_SIGNAL_MISMATCH: &str = "HW_ISSUE_SIGNAL_MISMATCH";
+    pub static HW_ISSUE_CHANNEL_RANGE: &str = "HW_ISSUE_CHANNEL_RANGE";
+    pub static HW_ISSUE_DUPLICATE_MODULE: &str = "HW_ISSUE_DUPLICATE_MODULE";
+    pub static BUTTON_ASSIGN_ADDRESSES: &str = "BUTTON_ASSIGN_ADDRESSES";
+    pub static FIELD_KEEP_ADDRESSES: &str = "FIELD_KEEP_ADDRESSES";
+    pub static BUTTON_APPLY: &str = "BUTTON_APPLY";
+    pub static ASSIGN_NO_CHANGES: &str = "ASSIGN_NO_CHANGES";
+    pub static ASSIGN_NO_FREE_CHANNEL: &str = "ASSIGN_NO_FREE_CHANNEL";
+    pub static ASSIGN_CHANGED: &str = "ASSIGN_CHANGED";
+    pub static BUTTON_BULK_CREATE: &str = "BUTTON_BULK_CREATE";
+    pub static FIELD_PATTERN: &str = "FIELD_PATTERN";
+    pub static FIELD_FROM: &str = "FIELD_FROM";
+    pub static FIELD_TO: &str = "FIELD_TO";
+    pub static FIELD_FIRST_ADDRESS: &str = "FIELD_FIRST_ADDRESS";
+    pub static BUTTON_CREATE: &str = "BUTTON_CREATE";
+    pub static BULK_RANGE_LIMIT: &str = "BULK_RANGE_LIMIT";
+    pub static BULK_RANGE_ORDER: &str = "BULK_RANGE_ORDER";
+    pub static FIELD_FILTER: &str = "FIELD_FILTER";
+    pub static FIELD_SORT: &str = "FIELD_SORT";
+    pub static SORT_LIST_ORDER: &str = "SORT_LIST_ORDER";
+    pub static FIELD_USES_IO: &str = "FIELD_USES_IO";
+    pub static BUTTON_CLEAR: &str = "BUTTON_CLEAR";
     pub static LOAD_TABLE: &str = "LOAD_TABLE";
     pub static SUBPROGRAM_TYPE_BLOCKED: &str = "SUBPROGRAM_TYPE_BLOCKED";
     pub static SUBPROGRAM_TYPE_CRITICAL: &str = "SUBPROGRAM_TYPE_CRITICAL";
@@ -81,21 +263,214 @@ pub mod language_pack_conastants {
     pub static IO_STATE_ANY: &str = "IO_STATE_ANY";
     pub static TABLE_CONTENT_SENSOR_STATES: &str = "TABLE_CONTENT_SENSOR_STATES";
     pub static TABLE_CONTENT_CONTROL_STATES: &str = "TABLE_CONTENT_CONTROL_STATES";
+    pub static TABLE_CONTENT_FLAGS: &str = "TABLE_CONTENT_FLAGS";
+    pub static TABLE_CONTENT_FLAG_TEST: &str = "TABLE_CONTENT_FLAG_TEST";
+    pub static TABLE_CONTENT_FLAG_SET: &str = "TABLE_CONTENT_FLAG_SET";
     pub static TABLE_CONTENT_TRASITION_ADDRESS: &str = "TABLE_CONTENT_TRASITION_ADDRESS";
     pub static TABLE_CONTENT_SIGN_OF_TRANSITION: &str = "TABLE_CONTENT_SIGN_OF_TRANSITION";
     pub static TABLE_CONTENT_SIGN_OF_BLOCKING: &str = "TABLE_CONTENT_SIGN_OF_BLOCKING";
     pub static TABLE_CONTENT_DESCRIPTION: &str = "TABLE_CONTENT_DESCRIPTION";
     pub static TABLE_SHEET_CONDITIONS: &str = "TABLE_SHEET_CONDITIONS";
     pub static TABLE_SHEET_SUBPROGRAMS: &str = "TABLE_SHEET_SUBPROGRAMS";
+    pub static TABLE_SHEET_TIMERS: &str = "TABLE_SHEET_TIMERS";
+    pub static TABLE_SHEET_COUNTERS: &str = "TABLE_SHEET_COUNTERS";
+    pub static TABLE_SHEET_ANALOG: &str = "TABLE_SHEET_ANALOG";
+    pub static TABLE_SHEET_FLAGS: &str = "TABLE_SHEET_FLAGS";
     pub static TABLE_CONTENT_SIGN_OF_FINISH: &str = "TABLE_CONTENT_SIGN_OF_FINISH";
     pub static TABLE_CONTENT_SUBPROGRAM_INITIAL: &str = "TABLE_CONTENT_SUBPROGRAM_INITIAL";
     pub static CONDITIONS_CONFIG_EMPTY: &str = "CONDITIONS_CONFIG_EMPTY";
     pub static SUBPROGRAM_CONFIG_EMPTY: &str = "SUBPROGRAM_CONFIG_EMPTY";
     pub static SUBPROGRAM_STEPS_EMPTY: &str = "SUBPROGRAM_STEPS_EMPTY";
+    pub static IO_ELEMENT_USAGES: &str = "IO_ELEMENT_USAGES";
+    pub static LANGUAGE_PACK_ISSUES: &str = "LANGUAGE_PACK_ISSUES";
+    pub static SETTINGS_SAVE_FAILED: &str = "SETTINGS_SAVE_FAILED";
+    pub static SETTINGS_EXIT_AGAIN: &str = "SETTINGS_EXIT_AGAIN";
+    pub static FIELD_PROJECT_FILE: &str = "FIELD_PROJECT_FILE";
+    pub static RECENT_PROJECTS: &str = "RECENT_PROJECTS";
+    pub static BUTTON_SAVE_PROJECT: &str = "BUTTON_SAVE_PROJECT";
+    pub static PROJECT_SAVED: &str = "PROJECT_SAVED";
+    pub static PROJECT_SAVE_FAILED: &str = "PROJECT_SAVE_FAILED";
+    pub static PROJECT_OPEN_FAILED: &str = "PROJECT_OPEN_FAILED";
 
+    // Every field a language pack is expected to define
+    pub static ALL_FIELDS: &[&str] = &[
+        BUTTON_ADD_NEW,
+        IOCONFIG_EMPTY,
+        BUTTON_BACK,
+        BUTTON_CANCEL,
+        BUTTON_MATRIX_VIEW,
+        BUTTON_LIST_VIEW,
+        BUTTON_MOVE_UP,
+        BUTTON_MOVE_DOWN,
+        BUTTON_DUPLICATE,
+        BUTTON_REMOVE_REFERENCES,
+        BUTTON_REASSIGN_REFERENCES,
+        BUTTON_EDIT_STATES_SUBPROGRAM_STEP,
+        BUTTON_EDIT_CONTROLS_SUBPROGRAM_STEP,
+        SUBPROGRAM_STEP,
+        OPERATOR,
+        BUTTON_FINISH,
+        BUTTON_GENERATE_TABLE,
+        GENERATE_UNRESOLVED_TRANSITION,
+        GENERATE_DONE,
+        GENERATE_FAILED,
+        BUTTON_NEXT,
+        BUTTON_TO_CONDITIONS,
+        BUTTON_TO_IO_CONFIGURATION,
+        BUTTON_TO_SUBPROGRAMMS_CONFIGURATION,
+        CREATE_NEW,
+        FIELD_ADDRESS,
+        FIELD_CONDITION,
+        FIELD_DESCRIPTION,
+        FIELD_HW,
+        FIELD_KIND,
+        KIND_DISCRETE,
+        KIND_TIMER,
+        KIND_COUNTER,
+        KIND_ANALOG,
+        FIELD_TIMER_MODE,
+        FIELD_PRESET_MS,
+        FIELD_PRESET,
+        FIELD_COUNTER_MODE,
+        FIELD_PRESET_COUNT,
+        FIELD_LOW,
+        FIELD_HIGH,
+        FIELD_HYSTERESIS,
+        ANALOG_ISSUE_LIMITS,
+        FIELD_LOGIC,
+        FIELD_DECLARATION,
+        FIELD_NAME,
+        FIELD_SIGNAL,
+        FIELD_SIGNAL_INPUT,
+        FIELD_SIGNAL_OUTPUT,
+        FIELD_TYPE,
+        FIELD_TYPE_CONTROL,
+        FIELD_TYPE_STATE,
+        FIELD_TYPE_FLAG,
+        INFO,
+        FIELD_LANGUAGE,
+        FIELD_DOCUMENT_LANGUAGE,
+        FIELD_BILINGUAL_HEADERS,
+        LOAD_TABLE,
+        SUBPROGRAM_TYPE_BLOCKED,
+        SUBPROGRAM_TYPE_CRITICAL,
+        SUBPROGRAM_TYPE_DEFAULT,
+        IO_STATE_ACTIVE,
+        IO_STATE_INACTIVE,
+        IO_STATE_ANY,
+        TABLE_CONTENT_SENSOR_STATES,
+        TABLE_CONTENT_CONTROL_STATES,
+        TABLE_CONTENT_FLAGS,
+        TABLE_CONTENT_FLAG_TEST,
+        TABLE_CONTENT_FLAG_SET,
+        TABLE_CONTENT_TRASITION_ADDRESS,
+        TABLE_CONTENT_SIGN_OF_TRANSITION,
+        TABLE_CONTENT_SIGN_OF_BLOCKING,
+        TABLE_CONTENT_DESCRIPTION,
+        TABLE_SHEET_CONDITIONS,
+        TABLE_SHEET_SUBPROGRAMS,
+        TABLE_SHEET_TIMERS,
+        TABLE_SHEET_COUNTERS,
+        TABLE_SHEET_ANALOG,
+        TABLE_SHEET_FLAGS,
+        TABLE_CONTENT_SIGN_OF_FINISH,
+        TABLE_CONTENT_SUBPROGRAM_INITIAL,
+        CONDITIONS_CONFIG_EMPTY,
+        SUBPROGRAM_CONFIG_EMPTY,
+        SUBPROGRAM_STEPS_EMPTY,
+        IO_ELEMENT_USAGES,
+        LANGUAGE_PACK_ISSUES,
+        SETTINGS_SAVE_FAILED,
+        SETTINGS_EXIT_AGAIN,
+        FIELD_PROJECT_FILE,
+        RECENT_PROJECTS,
+        BUTTON_SAVE_PROJECT,
+        PROJECT_SAVED,
+        PROJECT_SAVE_FAILED,
+        PROJECT_OPEN_FAILED,
+        FIELD_OUTPUT_DIR,
+        BUTTON_SETTINGS,
+        FIELD_THEME,
+        FIELD_UI_SCALE,
+        FIELD_ENCODING,
+        THEME_LIGHT,
+        THEME_DARK,
+        NAVIGATION_IO_CONFIGURATION,
+        NAVIGATION_SUBPROGRAMS,
+        NAVIGATION_CONDITIONS,
+        NAVIGATION_GENERATE,
+        NAVIGATION_PREVIEW,
+        NAVIGATION_VALIDATION,
+        NAVIGATION_SIMULATION,
+        VALIDATION_EMPTY_CONDITION,
+        VALIDATION_NO_ISSUES,
+        SIMULATION_IO_STATES,
+        SIMULATION_STEPS,
+        SIMULATION_CONDITIONS,
+        SIMULATION_NONE,
+        BUTTON_RESET,
+        BUTTON_EDIT_DESCRIPTION,
+        BUTTON_SHORTCUTS,
+        SHORTCUT_ADD_NEW,
+        SHORTCUT_DELETE,
+        SHORTCUT_MOVE_FOCUS,
+        SHORTCUT_CYCLE_STATE,
+        SHORTCUT_GENERATE,
+        SHORTCUT_SAVE_PROJECT,
+        SHORTCUT_UNDO,
+        SHORTCUT_REDO,
+        SHORTCUT_BACK,
+        SHORTCUT_CHEAT_SHEET,
+        SHORTCUT_COPY,
+        SHORTCUT_COPY_ALL,
+        SHORTCUT_PASTE,
+        BUTTON_IMPORT_CSV,
+        FIELD_CSV_FILE,
+        BUTTON_LOAD,
+        FIELD_REPLACE_IO,
+        BUTTON_IMPORT,
+        IMPORT_VALID_ROWS,
+        IMPORT_ISSUE_EMPTY_NAME,
+        IMPORT_ISSUE_DUPLICATE_NAME,
+        IMPORT_ISSUE_ADDRESS_RANGE,
+        IMPORT_ISSUE_UNKNOWN_TYPE,
+        IMPORT_ISSUE_UNKNOWN_SIGNAL,
+        FIELD_RACK,
+        FIELD_SLOT,
+        FIELD_CHANNEL,
+        FIELD_CHANNELS,
+        FIELD_IEC_ADDRESS,
+        BUTTON_HARDWARE,
+        BUTTON_ADD_MODULE,
+        HARDWARE_EMPTY,
+        HW_ISSUE_DUPLICATE,
+        HW_ISSUE_NO_MODULE,
+        HW_ISSUE_SIGNAL_MISMATCH,
+        HW_ISSUE_CHANNEL_RANGE,
+        HW_ISSUE_DUPLICATE_MODULE,
+        BUTTON_ASSIGN_ADDRESSES,
+        FIELD_KEEP_ADDRESSES,
+        BUTTON_APPLY,
+        ASSIGN_NO_CHANGES,
+        ASSIGN_NO_FREE_CHANNEL,
+        ASSIGN_CHANGED,
+        BUTTON_BULK_CREATE,
+        FIELD_PATTERN,
+        FIELD_FROM,
+        FIELD_TO,
+        FIELD_FIRST_ADDRESS,
+        BUTTON_CREATE,
+        BULK_RANGE_LIMIT,
+        BULK_RANGE_ORDER,
+        FIELD_FILTER,
+        FIELD_SORT,
+        SORT_LIST_ORDER,
+        FIELD_USES_IO,
+        BUTTON_CLEAR,
+    ];
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LanguagePack {
     file_path: String,
     content_str: String,
@@ -112,17 +487,43 @@ impl LanguagePack {
     }
 
     pub fn load_language_pack(&mut self, path: String) -> std::io::Result<()> {
-        let file = File::open(path)?;
+        let file = File::open(path.as_str())?;
         let mut buf_reader = BufReader::new(file);
+        let mut content = String::new();
 
-        self.content_str = String::new();
-        buf_reader.read_to_string(&mut self.content_str)?;
+        buf_reader.read_to_string(&mut content)?;
+
+        self.parse_language_pack(path, content)
+    }
 
-        self.content_json = json::parse(self.content_str.as_str()).unwrap();
+    pub fn parse_language_pack(&mut self, path: String, content: String) -> std::io::Result<()> {
+        self.file_path = path;
+        self.content_str = content;
+        self.content_json = json::parse(self.content_str.as_str()).map_err(|error| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, error.to_string())
+        })?;
 
         Ok(())
     }
 
+    pub fn check(&self) -> LanguagePackReport {
+        let keys: Vec<&str> = self.content_json.entries().map(|(key, _)| key).collect();
+
+        LanguagePackReport {
+            file_path: self.file_path.clone(),
+            error: None,
+            missing: language_pack_conastants::ALL_FIELDS.iter()
+                .filter(|field| !keys.contains(field))
+                .map(|field| field.to_string())
+                .collect(),
+            extra: keys.iter()
+                .filter(|key| !language_pack_conastants::ALL_FIELDS.contains(key))
+                .map(|key| key.to_string())
+                .collect(),
+            duplicate: get_duplicate_keys(self.content_str.as_str()),
+        }
+    }
+
     pub fn get_value(&self, name: &str) -> JsonValue {
         if JsonValue::Null != self.content_json {
             self.content_json[name].clone()
@@ -132,94 +533,743 @@ impl LanguagePack {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguagePackReport {
+    pub file_path: String,
+    pub error: Option<String>,
+    pub missing: Vec<String>,
+    pub extra: Vec<String>,
+    pub duplicate: Vec<String>,
+}
+
+impl LanguagePackReport {
+    pub fn from_error(file_path: &str, error: std::io::Error) -> Self {
+        LanguagePackReport {
+            file_path: file_path.to_string(),
+            error: Some(error.to_string()),
+            missing: vec![],
+            extra: vec![],
+            duplicate: vec![],
+        }
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.error.is_none() && self.missing.is_empty() && self.extra.is_empty()
+            && self.duplicate.is_empty()
+    }
+}
+
+impl std::fmt::Display for LanguagePackReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(error) = &self.error {
+            return write!(f, "{}: {}", self.file_path, error)
+        }
+
+        write!(f, "{}:", self.file_path)?;
+
+        for (title, keys) in [
+            ("missing", &self.missing), ("extra", &self.extra), ("duplicate", &self.duplicate)
+        ] {
+            if !keys.is_empty() {
+                write!(f, " {} {}", title, keys.join(", "))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// The JSON parser keeps the last value of a repeated key, so duplicates are
+// searched in the source text. Only keys of the top level object are checked.
+fn get_duplicate_keys(content: &str) -> Vec<String> {
+    let mut keys: Vec<String> = vec![];
+    let mut duplicates: Vec<String> = vec![];
+    let mut last_string: Option<String> = None;
+    let mut depth = 0;
+    let mut chars = content.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '[' => {
+                depth += 1;
+                last_string = None;
+            },
+            '}' | ']' => {
+                depth -= 1;
+                last_string = None;
+            },
+            '"' => {
+                let mut string = String::new();
+
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        },
+                        '"' => break,
+                        _ => string.push(c)
+                    }
+                }
+
+                last_string = Some(string);
+            },
+            ':' => {
+                if let Some(key) = last_string.take().filter(|_| depth == 1) {
+                    if !keys.contains(&key) {
+                        keys.push(key);
+                    } else if !duplicates.contains(&key) {
+                        duplicates.push(key);
+                    }
+                }
+            },
+            c if c.is_whitespace() => {},
+            _ => last_string = None
+        }
+    }
+
+    duplicates
+}
+
+// Language pack files found at the path, which can be a file or a directory
+pub fn find_language_packs(path: &str) -> Vec<PathBuf> {
+    let path = PathBuf::from(path);
+
+    if path.is_file() {
+        return vec![path]
+    }
+
+    let entries = match fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(_) => return vec![]
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().map_or(false, |extension| extension == "json"))
+        .collect();
+    paths.sort();
+
+    paths
+}
+
+pub fn get_default_language_pack() -> LanguagePack {
+    let mut language_pack = LanguagePack::new();
+
+    // A broken built-in pack is reported by the checker instead of stopping the application
+    language_pack.parse_language_pack(
+        DEFAULT_LANGUAGE_PACK_NAME.to_string(), DEFAULT_LANGUAGE_PACK.to_string()
+    ).ok();
+
+    language_pack
+}
+
+pub fn check_default_language_pack() -> LanguagePackReport {
+    let mut language_pack = LanguagePack::new();
+
+    match language_pack.parse_language_pack(
+        DEFAULT_LANGUAGE_PACK_NAME.to_string(), DEFAULT_LANGUAGE_PACK.to_string()
+    ) {
+        Ok(_) => language_pack.check(),
+        Err(error) => LanguagePackReport::from_error(DEFAULT_LANGUAGE_PACK_NAME, error)
+    }
+}
+
+pub fn check_language_pack(path: &str) -> LanguagePackReport {
+    let mut language_pack = LanguagePack::new();
+
+    match language_pack.load_language_pack(path.to_string()) {
+        Ok(_) => language_pack.check(),
+        Err(error) => LanguagePackReport::from_error(path, error)
+    }
+}
+
 pub mod style_config {
-    use iced::{button, Background, Color, Vector};
+    use iced::{
+        button, checkbox, container, pick_list, text_input, Background, Color, Vector
+    };
+    use iced_style::menu;
+
+    use super::GLOBAL_CONFIG;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Theme {
+        Light,
+        Dark,
+    }
+
+    pub static THEMES_ALL: &[Theme] = &[
+        Theme::Light,
+        Theme::Dark,
+    ];
+
+    pub struct Palette {
+        pub background: Color,
+        pub surface: Color,
+        pub text: Color,
+        pub border: Color,
+        pub primary: Color,
+        pub secondary: Color,
+        pub cell: Color,
+        pub cell_text: Color,
+    }
+
+    impl Theme {
+        pub fn palette(&self) -> Palette {
+            match self {
+                Theme::Light => Palette {
+                    background: Color::WHITE,
+                    surface: Color::WHITE,
+                    text: Color::BLACK,
+                    border: Color::from_rgb(0.7, 0.7, 0.7),
+                    primary: Color::from_rgb(0.11, 0.42, 0.87),
+                    secondary: Color::from_rgb(0.5, 0.5, 0.5),
+                    cell: Color::from_rgb(0.9, 0.9, 0.9),
+                    cell_text: Color::BLACK,
+                },
+                Theme::Dark => Palette {
+                    background: Color::from_rgb8(0x20, 0x22, 0x25),
+                    surface: Color::from_rgb8(0x36, 0x39, 0x3F),
+                    text: Color::from_rgb8(0xEE, 0xEE, 0xEE),
+                    border: Color::from_rgb8(0x55, 0x58, 0x5E),
+                    primary: Color::from_rgb(0.11, 0.42, 0.87),
+                    secondary: Color::from_rgb(0.35, 0.35, 0.35),
+                    cell: Color::from_rgb8(0x36, 0x39, 0x3F),
+                    cell_text: Color::from_rgb8(0xEE, 0xEE, 0xEE),
+                },
+            }
+        }
+    }
+
+    // Stylesheets are drawn outside of updates, so the config is never borrowed mutably here
+    pub fn palette() -> Palette {
+        unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().map_or(Theme::Light, |config| config.borrow().get_theme()).palette()
+    }
 
     pub enum Button {
         Primary,
         Secondary,
+        Cell,
+        CellSelected,
+        // Row actions next to the fields of a list entry
+        Icon,
     }
 
     impl button::StyleSheet for Button {
         fn active(&self) -> button::Style {
+            let palette = palette();
+
             button::Style {
                 background: Some(Background::Color(match self {
-                    Button::Primary => Color::from_rgb(0.11, 0.42, 0.87),
-                    Button::Secondary => Color::from_rgb(0.5, 0.5, 0.5),
+                    Button::Primary => palette.primary,
+                    Button::Secondary => palette.secondary,
+                    Button::Cell => palette.cell,
+                    Button::CellSelected => palette.primary,
+                    Button::Icon => palette.surface,
                 })),
                 border_radius: 5.0,
+                border_width: match self {
+                    Button::Icon => 1.0,
+                    _ => 0.0,
+                },
+                border_color: palette.border,
                 shadow_offset: Vector::new(1.0, 1.0),
-                text_color: Color::from_rgb8(0xEE, 0xEE, 0xEE),
+                text_color: match self {
+                    Button::Cell => palette.cell_text,
+                    Button::Icon => palette.text,
+                    _ => Color::from_rgb8(0xEE, 0xEE, 0xEE),
+                },
                 ..button::Style::default()
             }
         }
 
         fn hovered(&self) -> button::Style {
             button::Style {
-                text_color: Color::WHITE,
+                text_color: match self {
+                    Button::Cell => palette().cell_text,
+                    Button::Icon => palette().primary,
+                    _ => Color::WHITE,
+                },
                 shadow_offset: Vector::new(1.0, 2.0),
                 ..self.active()
             }
         }
     }
 
+    // Row of a list, the focused one is outlined for keyboard editing
+    pub struct ListRow {
+        pub focused: bool,
+    }
+
+    impl container::StyleSheet for ListRow {
+        fn style(&self) -> container::Style {
+            container::Style {
+                border_radius: 5.0,
+                border_width: if self.focused {2.0} else {0.0},
+                border_color: palette().primary,
+                ..container::Style::default()
+            }
+        }
+    }
+
+    // Cell of the table preview, headers get the heavier border of the generated sheet
+    pub struct SheetCell {
+        pub header: bool,
+    }
+
+    impl container::StyleSheet for SheetCell {
+        fn style(&self) -> container::Style {
+            let palette = palette();
+
+            container::Style {
+                text_color: Some(palette.cell_text),
+                background: Some(Background::Color(palette.cell)),
+                border_width: if self.header {2.0} else {1.0},
+                border_color: palette.border,
+                ..container::Style::default()
+            }
+        }
+    }
+
+    // Window content, sets the default text color of the theme
+    pub struct Root;
+
+    impl container::StyleSheet for Root {
+        fn style(&self) -> container::Style {
+            let palette = palette();
+
+            container::Style {
+                text_color: Some(palette.text),
+                background: Some(Background::Color(palette.background)),
+                ..container::Style::default()
+            }
+        }
+    }
+
+    pub struct TextInput;
+
+    impl text_input::StyleSheet for TextInput {
+        fn active(&self) -> text_input::Style {
+            let palette = palette();
+
+            text_input::Style {
+                background: Background::Color(palette.surface),
+                border_radius: 5.0,
+                border_width: 1.0,
+                border_color: palette.border,
+            }
+        }
+
+        fn focused(&self) -> text_input::Style {
+            text_input::Style {
+                border_color: palette().primary,
+                ..self.active()
+            }
+        }
+
+        fn placeholder_color(&self) -> Color {
+            palette().border
+        }
+
+        fn value_color(&self) -> Color {
+            palette().text
+        }
+
+        fn selection_color(&self) -> Color {
+            Color {
+                a: 0.5,
+                ..palette().primary
+            }
+        }
+    }
+
+    pub struct PickList;
+
+    impl pick_list::StyleSheet for PickList {
+        fn menu(&self) -> menu::Style {
+            let palette = palette();
+
+            menu::Style {
+                text_color: palette.text,
+                background: Background::Color(palette.surface),
+                border_width: 1.0,
+                border_color: palette.border,
+                selected_text_color: Color::WHITE,
+                selected_background: Background::Color(palette.primary),
+            }
+        }
+
+        fn active(&self) -> pick_list::Style {
+            let palette = palette();
+
+            pick_list::Style {
+                text_color: palette.text,
+                background: Background::Color(palette.surface),
+                border_radius: 5.0,
+                border_width: 1.0,
+                border_color: palette.border,
+                icon_size: 0.7,
+            }
+        }
+
+        fn hovered(&self) -> pick_list::Style {
+            pick_list::Style {
+                border_color: palette().primary,
+                ..self.active()
+            }
+        }
+    }
+
+    pub struct Checkbox;
+
+    impl checkbox::StyleSheet for Checkbox {
+        fn active(&self, _is_checked: bool) -> checkbox::Style {
+            let palette = palette();
+
+            checkbox::Style {
+                background: Background::Color(palette.surface),
+                checkmark_color: palette.primary,
+                border_radius: 5.0,
+                border_width: 1.0,
+                border_color: palette.border,
+            }
+        }
+
+        fn hovered(&self, is_checked: bool) -> checkbox::Style {
+            checkbox::Style {
+                border_color: palette().primary,
+                ..self.active(is_checked)
+            }
+        }
+    }
+
     pub const FONT_SIZE: u16 = 40;
     pub const DEFAULT_PADDING: u16 = 50;
     pub const DEFAULT_SPACING: u16 = 40;
     pub const SUBPRORAM_DESCRIPTION_WIDTH: u16 = 300;
     pub const SUBPRORAM_DESCRIPTION_HEIGTH: u16 = 300;
+    pub const MATRIX_CELL_WIDTH: u16 = 60;
+    pub const NAVIGATION_WIDTH: u16 = 250;
 }
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct Config {
     active_language_pack: String,
+    // Language of the generated documents, the UI language if not set. Saved with the
+    // project along with the bilingual headers
+    document_language_pack: Option<String>,
+    bilingual_headers: bool,
+    // File the project was opened from or saved to last
+    project_path: Option<String>,
+    // Most recent first
+    recent_projects: Vec<String>,
+    // Encoding of imported CSV files
+    encoding: Encoding,
     languages_pack: HashMap<String, LanguagePack>,
+    language_pack_reports: Vec<LanguagePackReport>,
+    window_size: (u32, u32),
+    output_dir: String,
+    theme: style_config::Theme,
+    ui_scale: UiScale,
 }
 
 impl Config {
     pub fn new() -> Self {
-        Config {
+        let mut config = Config {
             languages_pack: {
                 let mut map = HashMap::new();
-                let mut default = LanguagePack::new();
-
-                default.load_language_pack(String::from(DEFAULT_LANGUAGE_PACK));
+                let default = get_default_language_pack();
 
+                map.insert(
+                    default.get_value(language_pack_conastants::INFO).to_string(),
+                    default.clone()
+                );
                 map.insert(String::from(DEFAULT), default);
 
                 map
             },
             active_language_pack: String::from(DEFAULT),
+            document_language_pack: None,
+            bilingual_headers: false,
+            project_path: None,
+            recent_projects: vec![],
+            encoding: Encoding::Utf8,
+            language_pack_reports: vec![],
+            window_size: DEFAULT_WINDOW_SIZE,
+            output_dir: String::from(DEFAULT_OUTPUT_DIR),
+            theme: style_config::Theme::Light,
+            ui_scale: UiScale(100),
+        };
+
+        let report = check_default_language_pack();
+        if !report.is_clean() {
+            config.language_pack_reports.push(report);
+        }
+
+        for dir in get_language_pack_dirs() {
+            config.search_language_packs(dir.to_str().unwrap_or_default());
         }
+
+        config.active_language_pack = config.languages_pack.get(DEFAULT).unwrap()
+            .get_value(language_pack_conastants::INFO).to_string();
+
+        config
     }
 
     pub fn get_field(&self, name: &str) -> JsonValue {
-        let mut lp = self.languages_pack.get(&self.active_language_pack);
-        let mut value = lp.unwrap().get_value(name);
+        self.get_language_field(self.active_language_pack.as_str(), name)
+    }
+
+    fn get_language_field(&self, language: &str, name: &str) -> JsonValue {
+        let mut value = match self.languages_pack.get(language) {
+            Some(lp) => lp.get_value(name),
+            None => JsonValue::Null
+        };
 
-        if JsonValue::Null == value && String::from(DEFAULT) != self.active_language_pack {
-            lp = self.languages_pack.get(&String::from(DEFAULT));
-            value = lp.unwrap().get_value(name);
+        if JsonValue::Null == value && DEFAULT != language {
+            value = self.languages_pack.get(DEFAULT).unwrap().get_value(name);
         }
 
         value
     }
 
+    // Text for the generated documents, with the UI language added for bilingual headers
+    pub fn get_document_field(&self, name: &str) -> String {
+        let language = self.get_document_language_pack();
+        let value = self.get_language_field(language.as_str(), name).to_string();
+
+        if self.bilingual_headers && language != self.active_language_pack {
+            format!("{} / {}", value, self.get_field(name))
+        } else {
+            value
+        }
+    }
+
+    // Worksheet names are kept in the document language only, Excel refuses names longer
+    // than 31 characters or containing any of the characters below
+    pub fn get_sheet_name(&self, name: &str) -> String {
+        self.get_language_field(self.get_document_language_pack().as_str(), name)
+            .to_string()
+            .chars()
+            .filter(|c| !['/', '\\', '?', '*', ':', '[', ']'].contains(c))
+            .take(SHEET_NAME_LIMIT)
+            .collect()
+    }
+
+    pub fn get_document_language_pack(&self) -> String {
+        self.document_language_pack.clone()
+            .unwrap_or_else(|| self.active_language_pack.clone())
+    }
+
+    pub fn set_document_language_pack(&mut self, name: &str) {
+        if self.languages_pack.contains_key(name) {
+            self.document_language_pack = Some(name.to_string());
+        }
+    }
+
+    pub fn reset_document_language_pack(&mut self) {
+        self.document_language_pack = None;
+    }
+
+    pub fn is_bilingual_headers(&self) -> bool {
+        self.bilingual_headers
+    }
+
+    pub fn set_bilingual_headers(&mut self, bilingual: bool) {
+        self.bilingual_headers = bilingual;
+    }
+
     fn add_language_pack(&mut self, path: &str) {
         let mut language_pack = LanguagePack::new();
 
-        language_pack.load_language_pack(path.to_string());
+        if let Err(error) = language_pack.load_language_pack(path.to_string()) {
+            self.language_pack_reports.push(LanguagePackReport::from_error(path, error));
+            return
+        }
+
+        let report = language_pack.check();
+        if !report.is_clean() {
+            self.language_pack_reports.push(report);
+        }
 
         let info = language_pack.get_value(language_pack_conastants::INFO);
 
-        let value = self.languages_pack.get(info.to_string().as_str());
-        if None == Some(value) {
+        // Packs found later replace the ones with the same name, the fallback pack is kept
+        if !info.is_null() {
             self.languages_pack.insert(info.to_string(), language_pack);
         }
     }
 
     pub fn search_language_packs(&mut self, path: &str) {
-        //TODO: Add language packs searching
+        for path in find_language_packs(path) {
+            if let Some(path) = path.to_str() {
+                self.add_language_pack(path);
+            }
+        }
+    }
+
+    pub fn get_language_packs(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.languages_pack.keys()
+            .filter(|name| name.as_str() != DEFAULT)
+            .cloned()
+            .collect();
+        names.sort();
+
+        names
+    }
+
+    // Problems found in the loaded language packs
+    pub fn get_language_pack_reports(&self) -> Vec<LanguagePackReport> {
+        self.language_pack_reports.clone()
+    }
+
+    pub fn get_active_language_pack(&self) -> String {
+        self.active_language_pack.clone()
+    }
+
+    pub fn set_active_language_pack(&mut self, name: &str) {
+        if self.languages_pack.contains_key(name) {
+            self.active_language_pack = name.to_string();
+        }
+    }
+
+    pub fn get_window_size(&self) -> (u32, u32) {
+        self.window_size
+    }
+
+    pub fn set_window_size(&mut self, size: (u32, u32)) {
+        self.window_size = size;
+    }
+
+    pub fn get_output_dir(&self) -> String {
+        self.output_dir.clone()
+    }
+
+    pub fn set_output_dir(&mut self, dir: String) {
+        self.output_dir = dir;
+    }
+
+    pub fn get_theme(&self) -> style_config::Theme {
+        self.theme
+    }
+
+    pub fn set_theme(&mut self, theme: style_config::Theme) {
+        self.theme = theme;
+    }
+
+    // A new project is saved to the output directory
+    pub fn get_project_path(&self) -> String {
+        match &self.project_path {
+            Some(path) => path.clone(),
+            None => Path::new(self.output_dir.as_str()).join(DEFAULT_PROJECT_FILE)
+                .to_string_lossy().to_string()
+        }
+    }
+
+    pub fn set_project_path(&mut self, path: String) {
+        self.project_path = Some(path);
+    }
+
+    pub fn get_encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    pub fn set_encoding(&mut self, encoding: Encoding) {
+        self.encoding = encoding;
+    }
+
+    pub fn get_recent_projects(&self) -> Vec<String> {
+        self.recent_projects.clone()
+    }
+
+    pub fn add_recent_project(&mut self, path: String) {
+        self.recent_projects.retain(|recent| *recent != path);
+        self.recent_projects.insert(0, path);
+        self.recent_projects.truncate(RECENT_PROJECTS_LIMIT);
+    }
+
+    pub fn get_ui_scale(&self) -> UiScale {
+        self.ui_scale
+    }
+
+    pub fn set_ui_scale(&mut self, scale: UiScale) {
+        self.ui_scale = scale;
+    }
+
+    pub fn load_settings(&mut self) {
+        let path = match get_user_config_dir() {
+            Some(dir) => dir.join(SETTINGS_FILE),
+            None => return
+        };
+
+        let settings = match fs::read_to_string(path).ok()
+            .and_then(|content| json::parse(content.as_str()).ok()) {
+            Some(settings) => settings,
+            None => return
+        };
+
+        if let Some(language) = settings["language"].as_str() {
+            self.set_active_language_pack(language);
+        }
+
+        if let (Some(width), Some(height)) = (
+            settings["window"]["width"].as_u32(), settings["window"]["height"].as_u32()
+        ) {
+            self.window_size = (width, height);
+        }
+
+        if let Some(dir) = settings["output_dir"].as_str() {
+            self.output_dir = dir.to_string();
+        }
+
+        self.recent_projects = settings["recent_projects"].members()
+            .filter_map(|path| path.as_str())
+            .map(String::from)
+            .take(RECENT_PROJECTS_LIMIT)
+            .collect();
+
+        if let Some(encoding) = settings["encoding"].as_str().and_then(Encoding::from_name) {
+            self.encoding = encoding;
+        }
+
+        match settings["theme"].as_str() {
+            Some("dark") => self.theme = style_config::Theme::Dark,
+            Some("light") => self.theme = style_config::Theme::Light,
+            _ => {}
+        }
+
+        if let Some(scale) = settings["ui_scale"].as_u16()
+            .map(UiScale)
+            .filter(|scale| UI_SCALES_ALL.contains(scale)) {
+            self.ui_scale = scale;
+        }
+    }
+
+    pub fn save_settings(&self) -> std::io::Result<()> {
+        let dir = match get_user_config_dir() {
+            Some(dir) => dir,
+            None => return Ok(())
+        };
+
+        let settings = json::object! {
+            language: self.active_language_pack.as_str(),
+            window: {
+                width: self.window_size.0,
+                height: self.window_size.1
+            },
+            output_dir: self.output_dir.as_str(),
+            recent_projects: self.recent_projects.clone(),
+            encoding: self.encoding.to_name(),
+            theme: match self.theme {
+                style_config::Theme::Light => "light",
+                style_config::Theme::Dark => "dark",
+            },
+            ui_scale: self.ui_scale.0
+        };
+
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join(SETTINGS_FILE), settings.pretty(4))
     }
 }
 
@@ -227,10 +1277,11 @@ impl std::fmt::Display for FrameTypes {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let config = unsafe {
             &GLOBAL_CONFIG
-        }.as_ref().unwrap();
+        }.as_ref().unwrap().borrow();
 
         let state_string = config.get_field(FIELD_TYPE_STATE).to_string();
         let control_string = config.get_field(FIELD_TYPE_CONTROL).to_string();
+        let flag_string = config.get_field(FIELD_TYPE_FLAG).to_string();
 
         write!(
             f,
@@ -238,16 +1289,34 @@ impl std::fmt::Display for FrameTypes {
             match self {
                 FrameTypes::State => state_string.as_str(),
                 FrameTypes::Control => control_string.as_str(),
+                FrameTypes::Flag => flag_string.as_str(),
             }
         )
     }
 }
 
+impl std::fmt::Display for ElementKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+
+        let field = match self {
+            ElementKind::Discrete => KIND_DISCRETE,
+            ElementKind::Timer => KIND_TIMER,
+            ElementKind::Counter => KIND_COUNTER,
+            ElementKind::Analog => KIND_ANALOG,
+        };
+
+        write!(f, "{}", config.get_field(field).to_string())
+    }
+}
+
 impl std::fmt::Display for SignalTypes {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let config = unsafe {
             &GLOBAL_CONFIG
-        }.as_ref().unwrap();
+        }.as_ref().unwrap().borrow();
 
         let input_string = config.get_field(FIELD_SIGNAL_INPUT).to_string();
         let output_string = config.get_field(FIELD_SIGNAL_OUTPUT).to_string();
@@ -303,7 +1372,7 @@ impl std::fmt::Display for SubprogramTypes {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let config = unsafe {
             &GLOBAL_CONFIG
-        }.as_ref().unwrap();
+        }.as_ref().unwrap().borrow();
 
         let deafult_string = config.get_field(SUBPROGRAM_TYPE_DEFAULT).to_string();
         let critical_string = config.get_field(SUBPROGRAM_TYPE_CRITICAL).to_string();
@@ -321,11 +1390,37 @@ impl std::fmt::Display for SubprogramTypes {
     }
 }
 
+impl std::fmt::Display for UiScale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}%", self.0)
+    }
+}
+
+impl std::fmt::Display for style_config::Theme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+
+        let light_string = config.get_field(THEME_LIGHT).to_string();
+        let dark_string = config.get_field(THEME_DARK).to_string();
+
+        write!(
+            f,
+            "{}",
+            match self {
+                style_config::Theme::Light => light_string.as_str(),
+                style_config::Theme::Dark => dark_string.as_str(),
+            }
+        )
+    }
+}
+
 impl std::fmt::Display for IOElementStates {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let config = unsafe {
             &GLOBAL_CONFIG
-        }.as_ref().unwrap();
+        }.as_ref().unwrap().borrow();
 
         let active_string = config.get_field(IO_STATE_ACTIVE).to_string();
         let inactive_string = config.get_field(IO_STATE_INACTIVE).to_string();
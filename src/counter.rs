@@ -0,0 +1,42 @@
+// IEC 61131-3 counter function blocks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CounterMode {
+    CTU,
+    CTD,
+}
+
+pub static COUNTER_MODES_ALL: &[CounterMode] = &[
+    CounterMode::CTU,
+    CounterMode::CTD,
+];
+
+impl std::fmt::Display for CounterMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+// Steps count with an active control and reset the counter with an inactive one,
+// conditions check the output Q set once the preset is reached
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Counter {
+    pub mode: CounterMode,
+    pub preset: u32,
+}
+
+impl Counter {
+    pub fn new() -> Self {
+        Counter {
+            mode: CounterMode::CTU,
+            preset: 10,
+        }
+    }
+
+    // Count and reset inputs of the function block, a down counter is reloaded with LD
+    pub fn get_control_pins(&self) -> &'static str {
+        match self.mode {
+            CounterMode::CTU => "CU/R",
+            CounterMode::CTD => "CD/LD",
+        }
+    }
+}
@@ -0,0 +1,417 @@
+use std::{collections::HashMap, fs, io};
+
+use crate::configs::{IOElement, IOElementMessage};
+use crate::configuration::{
+    language_pack_conastants::{
+        IMPORT_ISSUE_EMPTY_NAME, IMPORT_ISSUE_DUPLICATE_NAME, IMPORT_ISSUE_ADDRESS_RANGE,
+        IMPORT_ISSUE_UNKNOWN_TYPE, IMPORT_ISSUE_UNKNOWN_SIGNAL
+    },
+    FrameTypes, SignalTypes, GLOBAL_CONFIG
+};
+use crate::encoding::decode;
+use crate::hardware::HwAddress;
+use crate::tsv::{parse_frame_type, parse_signal_type};
+
+#[derive(Debug, Clone)]
+pub enum IOImportMessage {
+    PathChanged(String),
+    Load,
+    ColumnSelected(ImportField, ImportColumn),
+    ReplaceToggled(bool),
+    Cancel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportField {
+    Name,
+    FrameType,
+    SignalType,
+    HwAddress,
+}
+
+pub static IMPORT_FIELDS_ALL: &[ImportField] = &[
+    ImportField::Name,
+    ImportField::FrameType,
+    ImportField::SignalType,
+    ImportField::HwAddress,
+];
+
+// Column of the CSV file, None leaves the field at its default
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportColumn {
+    pub index: Option<usize>,
+    pub title: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColumnMapping {
+    pub name: Option<usize>,
+    pub frame_type: Option<usize>,
+    pub signal_type: Option<usize>,
+    pub hw_address: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportIssue {
+    EmptyName,
+    DuplicateName,
+    AddressOutOfRange(String),
+    UnknownFrameType(String),
+    UnknownSignalType(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportRow {
+    pub line: usize,
+    pub name: String,
+    pub frame_type: FrameTypes,
+    pub signal_type: SignalTypes,
+    pub hw_address: HwAddress,
+    pub issues: Vec<ImportIssue>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CsvTable {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    // Line in the file of each row, counted from 1
+    pub lines: Vec<usize>,
+}
+
+// CAE tools export with comma, semicolon or tab depending on the locale
+fn detect_delimiter(first_line: &str) -> char {
+    [';', '\t', ',']
+        .iter()
+        .copied()
+        .max_by_key(|delimiter| first_line.matches(*delimiter).count())
+        .filter(|delimiter| first_line.contains(*delimiter))
+        .unwrap_or(',')
+}
+
+fn parse_line(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            },
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => {
+                fields.push(field.trim().to_string());
+                field.clear();
+            },
+            c => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+
+    fields
+}
+
+// The first non-empty line holds the column names and sets the delimiter
+pub fn parse_csv(content: &str) -> CsvTable {
+    let content = content.trim_start_matches('\u{feff}');
+    let mut lines = content.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+
+    let header = lines.next();
+    let delimiter = detect_delimiter(header.map_or("", |(_, line)| line));
+    let headers = header.map_or(vec![], |(_, line)| parse_line(line, delimiter));
+    let (lines, rows) = lines.map(|(i, line)| (i + 1, parse_line(line, delimiter))).unzip();
+
+    CsvTable {
+        headers: headers,
+        rows: rows,
+        lines: lines,
+    }
+}
+
+// Decoded with the encoding from the settings
+pub fn load_csv(path: &str) -> io::Result<CsvTable> {
+    let encoding = unsafe {
+        &GLOBAL_CONFIG
+    }.as_ref().unwrap().borrow().get_encoding();
+
+    let content = decode(fs::read(path)?, encoding)?;
+
+    Ok(parse_csv(content.as_str()))
+}
+
+impl std::fmt::Display for ImportColumn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.index {
+            Some(_) => write!(f, "{}", self.title),
+            None => write!(f, "-"),
+        }
+    }
+}
+
+impl CsvTable {
+    pub fn get_columns(&self) -> Vec<ImportColumn> {
+        let mut columns = vec![ImportColumn {index: None, title: String::new()}];
+
+        for (i, title) in self.headers.iter().enumerate() {
+            columns.push(ImportColumn {index: Some(i), title: title.clone()});
+        }
+
+        columns
+    }
+
+    // Column given by its title or its number counted from 1
+    pub fn find_column(&self, column: &str) -> Option<usize> {
+        self.headers.iter()
+            .position(|title| title.eq_ignore_ascii_case(column.trim()))
+            .or_else(|| {
+                column.trim().parse::<usize>().ok()
+                    .filter(|i| *i >= 1 && *i <= self.headers.len())
+                    .map(|i| i - 1)
+            })
+    }
+}
+
+impl ColumnMapping {
+    // Picks columns by the usual titles of IO list exports. Exact titles are matched
+    // before partial ones and a column is given to one field only, so "Signal type"
+    // goes to the signal type and not to the frame type
+    pub fn guess(headers: &Vec<String>) -> Self {
+        let keywords: &[(ImportField, &[&str])] = &[
+            (ImportField::Name, &["name", "tag", "symbol"]),
+            (ImportField::FrameType, &["frame type", "frame", "type", "kind"]),
+            (ImportField::SignalType, &["signal type", "signal", "direction", "dir"]),
+            (ImportField::HwAddress, &["hw address", "hw", "address", "addr", "channel"]),
+        ];
+        let titles: Vec<String> = headers.iter().map(|title| title.trim().to_lowercase()).collect();
+        let mut mapping = ColumnMapping {
+            name: None,
+            frame_type: None,
+            signal_type: None,
+            hw_address: None,
+        };
+
+        for exact in [true, false] {
+            for (field, keywords) in keywords {
+                if mapping.get(*field).is_some() {
+                    continue;
+                }
+
+                let column = titles.iter().enumerate().position(|(i, title)| {
+                    !IMPORT_FIELDS_ALL.iter().any(|field| mapping.get(*field) == Some(i)) &&
+                        keywords.iter().any(|keyword| if exact {
+                            title == keyword
+                        } else {
+                            title.contains(keyword)
+                        })
+                });
+
+                mapping.set(*field, column);
+            }
+        }
+
+        mapping
+    }
+
+    pub fn get(&self, field: ImportField) -> Option<usize> {
+        match field {
+            ImportField::Name => self.name,
+            ImportField::FrameType => self.frame_type,
+            ImportField::SignalType => self.signal_type,
+            ImportField::HwAddress => self.hw_address,
+        }
+    }
+
+    pub fn set(&mut self, field: ImportField, column: Option<usize>) {
+        match field {
+            ImportField::Name => self.name = column,
+            ImportField::FrameType => self.frame_type = column,
+            ImportField::SignalType => self.signal_type = column,
+            ImportField::HwAddress => self.hw_address = column,
+        }
+    }
+}
+
+impl ImportRow {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn to_element(&self) -> IOElement {
+        let mut element = IOElement::new();
+
+        element.update(IOElementMessage::NameInputChanged(self.name.clone()));
+        element.update(IOElementMessage::FrameTypeSelected(self.frame_type));
+        element.update(IOElementMessage::SignalTypeSelected(self.signal_type));
+        element.update(IOElementMessage::HwSelected(self.hw_address.to_string()));
+
+        element
+    }
+}
+
+// Existing names are only checked when the import is merged into the current list
+pub fn map_rows(
+    table: &CsvTable, mapping: &ColumnMapping, existing_names: &Vec<String>
+) -> Vec<ImportRow> {
+    let mut names: HashMap<String, usize> = HashMap::new();
+
+    for name in existing_names {
+        *names.entry(name.clone()).or_insert(0) += 1;
+    }
+
+    let get = |row: &Vec<String>, column: Option<usize>| {
+        column.and_then(|i| row.get(i)).cloned().unwrap_or_default()
+    };
+
+    for row in &table.rows {
+        *names.entry(get(row, mapping.name)).or_insert(0) += 1;
+    }
+
+    table.rows.iter().zip(&table.lines).map(|(row, line)| {
+        let name = get(row, mapping.name);
+        let frame_type = get(row, mapping.frame_type);
+        let signal_type = get(row, mapping.signal_type);
+        let hw_address = get(row, mapping.hw_address);
+        let mut issues = vec![];
+
+        if name.is_empty() {
+            issues.push(ImportIssue::EmptyName);
+        } else if names[&name] > 1 {
+            issues.push(ImportIssue::DuplicateName);
+        }
+
+        let parsed_frame_type = match frame_type.as_str() {
+            "" => Some(FrameTypes::State),
+            value => parse_frame_type(value),
+        };
+        if parsed_frame_type.is_none() {
+            issues.push(ImportIssue::UnknownFrameType(frame_type));
+        }
+
+        let parsed_signal_type = match signal_type.as_str() {
+            "" => Some(SignalTypes::Input),
+            value => parse_signal_type(value),
+        };
+        if parsed_signal_type.is_none() {
+            issues.push(ImportIssue::UnknownSignalType(signal_type));
+        }
+
+        let parsed_hw_address = match hw_address.as_str() {
+            "" => Some(HwAddress::default()),
+            value => HwAddress::parse(value),
+        };
+        if parsed_hw_address.is_none() {
+            issues.push(ImportIssue::AddressOutOfRange(hw_address));
+        }
+
+        ImportRow {
+            line: *line,
+            name: name,
+            frame_type: parsed_frame_type.unwrap_or(FrameTypes::State),
+            signal_type: parsed_signal_type.unwrap_or(SignalTypes::Input),
+            hw_address: parsed_hw_address.unwrap_or_default(),
+            issues: issues,
+        }
+    }).collect()
+}
+
+impl std::fmt::Display for ImportIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+
+        match self {
+            ImportIssue::EmptyName => write!(
+                f, "{}", config.get_field(IMPORT_ISSUE_EMPTY_NAME).to_string()
+            ),
+            ImportIssue::DuplicateName => write!(
+                f, "{}", config.get_field(IMPORT_ISSUE_DUPLICATE_NAME).to_string()
+            ),
+            ImportIssue::AddressOutOfRange(value) => write!(
+                f, "{} \"{}\"", config.get_field(IMPORT_ISSUE_ADDRESS_RANGE).to_string(), value
+            ),
+            ImportIssue::UnknownFrameType(value) => write!(
+                f, "{} \"{}\"", config.get_field(IMPORT_ISSUE_UNKNOWN_TYPE).to_string(), value
+            ),
+            ImportIssue::UnknownSignalType(value) => write!(
+                f, "{} \"{}\"", config.get_field(IMPORT_ISSUE_UNKNOWN_SIGNAL).to_string(), value
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(titles: &[&str]) -> Vec<String> {
+        titles.iter().map(|title| title.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_csv_takes_the_delimiter_from_the_header_line() {
+        let table = parse_csv("\n\nName;Type;Signal\nA;State;Input,x\n");
+
+        assert_eq!(table.headers, headers(&["Name", "Type", "Signal"]));
+        assert_eq!(table.rows, vec![headers(&["A", "State", "Input,x"])]);
+        assert_eq!(table.lines, vec![4]);
+    }
+
+    #[test]
+    fn parse_csv_keeps_quoted_delimiters_and_quotes() {
+        let table = parse_csv("Name,Comment\n\"Valve, main\",\"said \"\"open\"\"\"\n");
+
+        assert_eq!(table.rows, vec![headers(&["Valve, main", "said \"open\""])]);
+    }
+
+    #[test]
+    fn parse_csv_skips_the_byte_order_mark() {
+        let table = parse_csv("\u{feff}Name\tHW\nA\t0.1\n");
+
+        assert_eq!(table.headers, headers(&["Name", "HW"]));
+        assert_eq!(table.rows, vec![headers(&["A", "0.1"])]);
+    }
+
+    #[test]
+    fn guess_matches_exact_titles_before_partial_ones() {
+        let mapping = ColumnMapping::guess(&headers(&["Signal type", "Type", "Name", "HW address"]));
+
+        assert_eq!(mapping.name, Some(2));
+        assert_eq!(mapping.frame_type, Some(1));
+        assert_eq!(mapping.signal_type, Some(0));
+        assert_eq!(mapping.hw_address, Some(3));
+    }
+
+    #[test]
+    fn guess_gives_a_column_to_one_field_only() {
+        let mapping = ColumnMapping::guess(&headers(&["Tag name", "Frame", "Direction"]));
+
+        assert_eq!(mapping.name, Some(0));
+        assert_eq!(mapping.frame_type, Some(1));
+        assert_eq!(mapping.signal_type, Some(2));
+        assert_eq!(mapping.hw_address, None);
+    }
+
+    #[test]
+    fn map_rows_reports_names_and_addresses() {
+        let table = parse_csv("Name,HW\nA,1.2\n,3\nA,4\nB,1.2.3.4\nC,0.1.2\n");
+        let mapping = ColumnMapping::guess(&table.headers);
+        let rows = map_rows(&table, &mapping, &vec![String::from("B")]);
+        let issues: Vec<Vec<ImportIssue>> = rows.iter().map(|row| row.issues.clone()).collect();
+
+        assert_eq!(issues, vec![
+            vec![ImportIssue::DuplicateName],
+            vec![ImportIssue::EmptyName],
+            vec![ImportIssue::DuplicateName],
+            vec![
+                ImportIssue::DuplicateName,
+                ImportIssue::AddressOutOfRange(String::from("1.2.3.4"))
+            ],
+            vec![],
+        ]);
+        assert_eq!(rows[4].hw_address, HwAddress {rack: 0, slot: 1, channel: 2});
+        assert_eq!(rows.iter().map(|row| row.line).collect::<Vec<usize>>(), vec![2, 3, 4, 5, 6]);
+    }
+}
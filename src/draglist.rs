@@ -0,0 +1,142 @@
+use iced_native::{
+    event, layout, mouse, overlay, Clipboard, Element, Event, Hasher, Layout, Length, Point,
+    Rectangle, Widget
+};
+
+// Row picked up by the mouse, kept in the view so the row can be highlighted while dragged
+#[derive(Debug, Default)]
+pub struct State {
+    grabbed: Option<usize>,
+}
+
+impl State {
+    pub fn new() -> Self {
+        State::default()
+    }
+
+    pub fn get_grabbed(&self) -> Option<usize> {
+        self.grabbed
+    }
+}
+
+// Column whose rows are reordered by dragging. A press on a row outside of its inputs and
+// buttons picks the row up, releasing it over another row reports the move by the positions
+// of both rows in the column
+pub struct DragList<'a, Message, Renderer> {
+    state: &'a mut State,
+    content: Element<'a, Message, Renderer>,
+    on_move: Box<dyn Fn(usize, usize) -> Message + 'a>,
+}
+
+impl<'a, Message, Renderer> DragList<'a, Message, Renderer> {
+    pub fn new<T, F>(state: &'a mut State, content: T, on_move: F) -> Self
+    where
+        T: Into<Element<'a, Message, Renderer>>,
+        F: 'a + Fn(usize, usize) -> Message,
+    {
+        DragList {
+            state: state,
+            content: content.into(),
+            on_move: Box::new(on_move),
+        }
+    }
+}
+
+// Gaps between the rows belong to the row above them
+fn get_row_at(layout: Layout<'_>, cursor_position: Point) -> Option<usize> {
+    if !layout.bounds().contains(cursor_position) {
+        return None;
+    }
+
+    let last = layout.children()
+        .filter(|row| row.bounds().y <= cursor_position.y)
+        .count();
+
+    Some(last.saturating_sub(1))
+}
+
+impl<'a, Message, Renderer> Widget<Message, Renderer> for DragList<'a, Message, Renderer>
+where
+    Renderer: iced_native::Renderer,
+{
+    fn width(&self) -> Length {
+        self.content.width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.layout(renderer, limits)
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        messages: &mut Vec<Message>,
+    ) -> event::Status {
+        let status = self.content.on_event(
+            event.clone(), layout, cursor_position, renderer, clipboard, messages
+        );
+
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+                if status == event::Status::Ignored => {
+                let grabbed = layout.children()
+                    .position(|row| row.bounds().contains(cursor_position));
+
+                if grabbed.is_some() {
+                    self.state.grabbed = grabbed;
+                    return event::Status::Captured;
+                }
+            },
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                if let Some(from) = self.state.grabbed.take() {
+                    match get_row_at(layout, cursor_position) {
+                        Some(to) if to != from => messages.push((self.on_move)(from, to)),
+                        _ => {}
+                    }
+
+                    return event::Status::Captured;
+                }
+            },
+            _ => {}
+        }
+
+        status
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        defaults: &Renderer::Defaults,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) -> Renderer::Output {
+        self.content.draw(renderer, defaults, layout, cursor_position, viewport)
+    }
+
+    fn hash_layout(&self, state: &mut Hasher) {
+        self.content.hash_layout(state);
+    }
+
+    fn overlay(&mut self, layout: Layout<'_>) -> Option<overlay::Element<'_, Message, Renderer>> {
+        self.content.overlay(layout)
+    }
+}
+
+impl<'a, Message, Renderer> From<DragList<'a, Message, Renderer>> for Element<'a, Message, Renderer>
+where
+    Message: 'a,
+    Renderer: 'a + iced_native::Renderer,
+{
+    fn from(drag_list: DragList<'a, Message, Renderer>) -> Self {
+        Element::new(drag_list)
+    }
+}
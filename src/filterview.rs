@@ -0,0 +1,99 @@
+use iced::{
+    button, Align, Button, Column, Element, Length, Text, Row, TextInput,
+    text_input, pick_list, PickList
+};
+
+use crate::configuration::{
+    language_pack_conastants::{FIELD_FILTER, FIELD_SORT, FIELD_USES_IO, BUTTON_CLEAR},
+    style_config::{self, DEFAULT_SPACING},
+    GLOBAL_CONFIG
+};
+use crate::configs::{IOElementId, IO_CONFIG};
+use crate::listfilter::{FilterMessage, ListFilter, SortColumn};
+
+#[derive(Debug)]
+pub struct FilterView {
+    query_input: text_input::State,
+    sort_list: pick_list::State<SortColumn>,
+    usages_list: pick_list::State<IOElementId>,
+    clear_button: button::State,
+    columns: &'static [SortColumn],
+    // Only lists referencing IO elements can be narrowed to the usages of one
+    usages: bool,
+    filter: ListFilter,
+}
+
+impl<'a> FilterView {
+    pub fn new(columns: &'static [SortColumn], usages: bool) -> Self {
+        FilterView {
+            query_input: text_input::State::new(),
+            sort_list: pick_list::State::default(),
+            usages_list: pick_list::State::default(),
+            clear_button: button::State::new(),
+            columns: columns,
+            usages: usages,
+            filter: ListFilter::new(),
+        }
+    }
+
+    pub fn update(&mut self, message: FilterMessage) {
+        self.filter.update(message)
+    }
+
+    pub fn get_filter(&self) -> &ListFilter {
+        &self.filter
+    }
+
+    pub fn view(&'a mut self) -> Element<'a, FilterMessage> {
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+
+        let query_input = TextInput::new(
+            &mut self.query_input,
+            config.get_field(FIELD_FILTER).to_string().as_str(),
+            self.filter.query.as_str(),
+            FilterMessage::QueryChanged
+        ).size(25).width(Length::Units(300)).padding(5).style(style_config::TextInput);
+
+        let sort_list = PickList::new(
+            &mut self.sort_list,
+            self.columns,
+            Some(self.filter.sort),
+            FilterMessage::SortSelected
+        ).style(style_config::PickList);
+
+        let mut row = Row::new()
+            .spacing(DEFAULT_SPACING)
+            .align_items(Align::End)
+            .push(query_input)
+            .push(Column::new()
+                .push(Text::new(config.get_field(FIELD_SORT).to_string()))
+                .push(sort_list));
+
+        if self.usages {
+            let mut elements = vec![IOElementId(0)];
+            elements.extend(unsafe {
+                &IO_CONFIG
+            }.as_ref().unwrap().borrow().get_all_elelments().iter()
+                .map(|element| IOElementId(element.borrow().get_uid())));
+
+            row = row.push(Column::new()
+                .push(Text::new(config.get_field(FIELD_USES_IO).to_string()))
+                .push(PickList::new(
+                    &mut self.usages_list,
+                    elements,
+                    Some(self.filter.usages_of),
+                    FilterMessage::UsagesSelected
+                ).style(style_config::PickList)));
+        }
+
+        row
+            .push(Button::new(
+                &mut self.clear_button,
+                Text::new(config.get_field(BUTTON_CLEAR).to_string())
+            ).style(style_config::Button::Secondary)
+                .on_press(FilterMessage::Clear))
+            .into()
+    }
+}
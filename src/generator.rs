@@ -1,18 +1,20 @@
 use std::cell::RefCell;
+use std::collections::HashSet;
 use std::rc::Rc;
 use std::slice::SliceIndex;
+use std::path::Path;
 
 use crate::configs::{
     IO_CONFIG, SUBPROGRAMS_CONFIG, CONDTIONS_CONFIG, IOElementCoditions, IOElement
 };
-use crate::configuration::language_pack_conastants::{TABLE_SHEET_CONDITIONS, TABLE_CONTENT_DESCRIPTION, TABLE_CONTENT_SENSOR_STATES, TABLE_CONTENT_CONTROL_STATES, TABLE_CONTENT_SIGN_OF_TRANSITION, TABLE_CONTENT_TRASITION_ADDRESS, TABLE_CONTENT_SIGN_OF_BLOCKING, TABLE_SHEET_SUBPROGRAMS, FIELD_ADDRESS, OPERATOR, TABLE_CONTENT_SIGN_OF_FINISH, TABLE_CONTENT_SUBPROGRAM_INITIAL};
+use crate::configuration::language_pack_conastants::{TABLE_SHEET_CONDITIONS, TABLE_CONTENT_DESCRIPTION, TABLE_CONTENT_SENSOR_STATES, TABLE_CONTENT_CONTROL_STATES, TABLE_CONTENT_SIGN_OF_TRANSITION, TABLE_CONTENT_TRASITION_ADDRESS, TABLE_CONTENT_SIGN_OF_BLOCKING, TABLE_SHEET_SUBPROGRAMS, FIELD_ADDRESS, OPERATOR, TABLE_CONTENT_SIGN_OF_FINISH, TABLE_CONTENT_SUBPROGRAM_INITIAL, TABLE_SHEET_TIMERS, FIELD_NAME, FIELD_TIMER_MODE, FIELD_PRESET, FIELD_PRESET_MS, TABLE_SHEET_COUNTERS, FIELD_COUNTER_MODE, TABLE_SHEET_ANALOG, TABLE_SHEET_FLAGS, FIELD_DECLARATION, FIELD_HW, FIELD_LOW, FIELD_HIGH, FIELD_HYSTERESIS, FIELD_LOGIC, TABLE_CONTENT_FLAGS, TABLE_CONTENT_FLAG_TEST, TABLE_CONTENT_FLAG_SET, GENERATE_UNRESOLVED_TRANSITION, ANALOG_ISSUE_LIMITS};
 use crate::configuration:: {
-    GLOBAL_CONFIG, FrameTypes, IOElementStates, Operators,
+    GLOBAL_CONFIG, FrameTypes, IOElementStates, Operators, ElementKind,
 };
 
 use xlsxwriter::*;
 
-static RESULT_TABLE: &str = "./tpvg_generated_table.xlsx";
+static RESULT_TABLE: &str = "tpvg_generated_table.xlsx";
 
 static STATE_ACTIVE: &str = "10";
 static STATE_INACTIVE: &str = "01";
@@ -22,7 +24,196 @@ static STATE_BLOCKED: &str = "11";
 static OPERATOR_AND: &str = "&";
 static OPERATOR_OR: &str = "|";
 
-fn get_conditions_state<'a>(
+// Written in place of a transition address that can no longer be resolved
+static ADDRESS_UNRESOLVED: &str = "?";
+
+#[derive(Debug)]
+pub enum GenerateError {
+    Xlsx(XlsxError),
+    UnresolvedTransition(String),
+    InvalidAnalogBand(String),
+}
+
+impl From<XlsxError> for GenerateError {
+    fn from(error: XlsxError) -> Self {
+        GenerateError::Xlsx(error)
+    }
+}
+
+impl std::fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+
+        match self {
+            GenerateError::Xlsx(error) => write!(f, "{}", error),
+            GenerateError::UnresolvedTransition(description) => write!(
+                f, "{}: {}",
+                config.get_field(GENERATE_UNRESOLVED_TRANSITION).to_string(), description
+            ),
+            GenerateError::InvalidAnalogBand(name) => write!(
+                f, "{}: {}", config.get_field(ANALOG_ISSUE_LIMITS).to_string(), name
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellFormat {
+    Rotated,
+    Description,
+    Default,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellValue {
+    Text(String),
+    Number(f64),
+}
+
+// Single cell or merged range of a generated sheet, rows and columns are inclusive
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cell {
+    pub first_row: u32,
+    pub first_col: u16,
+    pub last_row: u32,
+    pub last_col: u16,
+    pub value: CellValue,
+    pub format: CellFormat,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sheet {
+    cells: Vec<Cell>,
+}
+
+impl Sheet {
+    pub fn new() -> Self {
+        Sheet {
+            cells: vec![]
+        }
+    }
+
+    fn write_string(&mut self, row: u32, col: u16, text: &str, format: CellFormat) {
+        self.merge_range(row, col, row, col, text, format)
+    }
+
+    fn write_number(&mut self, row: u32, col: u16, number: f64, format: CellFormat) {
+        self.cells.push(Cell {
+            first_row: row,
+            first_col: col,
+            last_row: row,
+            last_col: col,
+            value: CellValue::Number(number),
+            format: format,
+        })
+    }
+
+    fn merge_range(
+        &mut self,
+        first_row: u32, first_col: u16, last_row: u32, last_col: u16,
+        text: &str, format: CellFormat
+    ) {
+        self.cells.push(Cell {
+            first_row: first_row,
+            first_col: first_col,
+            last_row: last_row,
+            last_col: last_col,
+            value: CellValue::Text(String::from(text)),
+            format: format,
+        })
+    }
+
+    pub fn get_cells(&self) -> &Vec<Cell> {
+        &self.cells
+    }
+
+    // Number of rows and columns
+    pub fn get_size(&self) -> (u32, u16) {
+        self.cells.iter().fold((0, 0), |(rows, cols), cell| {
+            (rows.max(cell.last_row + 1), cols.max(cell.last_col + 1))
+        })
+    }
+}
+
+impl std::fmt::Display for CellValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CellValue::Text(text) => write!(f, "{}", text),
+            CellValue::Number(number) => write!(f, "{}", number),
+        }
+    }
+}
+
+fn write_sheet<'a>(
+    worksheet: &mut Worksheet,
+    sheet: &Sheet,
+    get_format: impl Fn(CellFormat) -> &'a Format<'a>
+) -> Result<(), XlsxError> {
+    for cell in sheet.get_cells() {
+        let format = Some(get_format(cell.format));
+
+        if cell.first_row != cell.last_row || cell.first_col != cell.last_col {
+            worksheet.merge_range(
+                cell.first_row, cell.first_col, cell.last_row, cell.last_col,
+                cell.value.to_string().as_str(), format
+            )?;
+            continue;
+        }
+
+        match &cell.value {
+            CellValue::Text(text) => {
+                worksheet.write_string(cell.first_row, cell.first_col, text.as_str(), format)?
+            },
+            CellValue::Number(number) => {
+                worksheet.write_number(cell.first_row, cell.first_col, *number, format)?
+            },
+        }
+    }
+
+    Ok(())
+}
+
+// Column title of the IO element, with its direct address when the hardware is declared.
+// Timers and counters are checked through their output Q and driven through their inputs
+fn get_io_element_title(element: &IOElement, frame_type: FrameTypes) -> String {
+    let ioconfig = unsafe {
+        &IO_CONFIG
+    }.as_ref().unwrap();
+    let (name, ..) = element.get_data();
+
+    if ElementKind::Timer == element.get_kind() {
+        let timer = element.get_timer();
+        let pin = match frame_type {
+            FrameTypes::State => "Q",
+            FrameTypes::Control | FrameTypes::Flag => "IN",
+        };
+
+        return format!("{}.{} ({} {})", name, pin, timer.mode, timer.get_preset());
+    }
+
+    if ElementKind::Counter == element.get_kind() {
+        let counter = element.get_counter();
+        let pin = match frame_type {
+            FrameTypes::State => "Q",
+            FrameTypes::Control | FrameTypes::Flag => counter.get_control_pins(),
+        };
+
+        return format!("{}.{} ({} {})", name, pin, counter.mode, counter.preset);
+    }
+
+    if ElementKind::Analog == element.get_kind() {
+        return format!("{} ({})", name, element.get_analog().get_range());
+    }
+
+    match ioconfig.borrow().get_iec_address(element) {
+        Some(address) => format!("{} ({})", name, address),
+        None => name
+    }
+}
+
+pub fn get_conditions_state<'a>(
     conditions: &'a Vec<Rc<RefCell<IOElementCoditions>>>,
     element: &'a Rc<RefCell<IOElement>>
 ) -> String {
@@ -33,11 +224,10 @@ fn get_conditions_state<'a>(
         let (io_element, state_type, _) = condition.borrow().get_data();
 
         if io_element != None {
-            let (name_first, ..) = io_element.unwrap().borrow().get_data();
-            let (name_second, ..) = element.borrow().get_data();
+            let uid_first = io_element.unwrap().borrow().get_uid();
+            let uid_second = element.borrow().get_uid();
 
-            if name_first == name_second {
-                println!("Matched: state conditons {} and {}", name_first, name_second);
+            if uid_first == uid_second {
                 match state_type {
                     IOElementStates::Active => {
                         content = String::from(STATE_ACTIVE);
@@ -59,29 +249,103 @@ fn get_conditions_state<'a>(
 
 }
 
-fn fill_condtions_sheet<'a>(
-    conditions_sheet: &'a mut Worksheet,
-    rotadet_format: &'a Format,
-    description_format: &'a Format,
-    default_format: &'a Format,
-) -> Result<String, XlsxError> {
+// State and control columns, internal flags get a column group of their own
+fn get_column_elements() -> (
+    Vec<Rc<RefCell<IOElement>>>, Vec<Rc<RefCell<IOElement>>>, Vec<Rc<RefCell<IOElement>>>
+) {
+    let ioconfig = unsafe {
+        &IO_CONFIG
+    }.as_ref().unwrap().borrow();
+    let get_elements = |frame_type: FrameTypes| ioconfig
+        .get_elements_by_frame_type(frame_type).into_iter()
+        .filter(|element| !element.borrow().is_flag())
+        .collect();
+
+    (
+        get_elements(FrameTypes::State),
+        get_elements(FrameTypes::Control),
+        ioconfig.get_elements_by_frame_type(FrameTypes::Flag)
+    )
+}
+
+// Every flag takes a test column filled from the state conditions and a set/reset column
+// filled from the control conditions
+fn write_flag_titles(
+    sheet: &mut Sheet,
+    flag_elements: &Vec<Rc<RefCell<IOElement>>>,
+    offset_col: u16,
+    description_row: u32,
+    number_row: u32
+) {
+    if flag_elements.is_empty() {
+        return;
+    }
+
+    let config = unsafe {
+        &GLOBAL_CONFIG
+    }.as_ref().unwrap().borrow();
+    let actions = [TABLE_CONTENT_FLAG_TEST, TABLE_CONTENT_FLAG_SET];
+
+    sheet.merge_range(
+        0, offset_col + 1, 0, offset_col + 2 * flag_elements.len() as u16,
+        config.get_document_field(TABLE_CONTENT_FLAGS).as_str(),
+        CellFormat::Description
+    );
+
+    let mut index = 1;
+    for flag_element in flag_elements {
+        let (name, ..) = flag_element.borrow().get_data();
+
+        for action in actions.iter() {
+            sheet.write_string(
+                description_row, offset_col + index,
+                format!("{} ({})", name, config.get_document_field(action)).as_str(),
+                CellFormat::Rotated
+            );
+            sheet.write_number(
+                number_row, offset_col + index,
+                index as f64, CellFormat::Description
+            );
+
+            index += 1;
+        }
+    }
+}
+
+fn write_flag_states(
+    sheet: &mut Sheet,
+    flag_elements: &Vec<Rc<RefCell<IOElement>>>,
+    offset_col: u16,
+    row: u32,
+    states: &Vec<Rc<RefCell<IOElementCoditions>>>,
+    controls: &Vec<Rc<RefCell<IOElementCoditions>>>
+) {
+    let mut index = 1;
+    for flag_element in flag_elements {
+        for conditions in [states, controls].iter() {
+            sheet.write_string(
+                row, offset_col + index,
+                get_conditions_state(conditions, flag_element).as_str(),
+                CellFormat::Default
+            );
+
+            index += 1;
+        }
+    }
+}
+
+// Layout of the conditions sheet, shared by the XLSX output and the preview
+pub fn build_conditions_sheet() -> Sheet {
+    let mut conditions_sheet = Sheet::new();
     let config = unsafe {
         &GLOBAL_CONFIG
-    }.as_ref().unwrap();
+    }.as_ref().unwrap().borrow();
 
     let conditionsconfig = unsafe {
         &CONDTIONS_CONFIG
     }.as_ref().unwrap();
 
-    let ioconfig = unsafe {
-        &IO_CONFIG
-    }.as_ref().unwrap();
-
-    let state_elements = ioconfig.borrow()
-        .get_elements_by_frame_type(FrameTypes::State);
-
-    let control_elements = ioconfig.borrow()
-        .get_elements_by_frame_type(FrameTypes::Control);
+    let (state_elements, control_elements, flag_elements) = get_column_elements();
 
     let conditions_list = conditionsconfig.borrow().get_conditions();
 
@@ -91,7 +355,9 @@ fn fill_condtions_sheet<'a>(
         description_offset_col + state_elements.len() as u16;
     let control_elements_offset_col: u16 =
         state_elements_offset_col + control_elements.len() as u16;
-    let transition_sign_offset_col = control_elements_offset_col + 1;
+    let flag_elements_offset_col: u16 =
+        control_elements_offset_col + 2 * flag_elements.len() as u16;
+    let transition_sign_offset_col = flag_elements_offset_col + 1;
     let address_offset_col = transition_sign_offset_col + 1;
     let blocked_sign_offset_col = address_offset_col + 1;
     let states_description_offset_row: u32 = 1;
@@ -100,51 +366,51 @@ fn fill_condtions_sheet<'a>(
     // Top descripton fields
     conditions_sheet.merge_range(
         0, 0, description_offset_row, description_offset_col,
-        config.get_field(TABLE_CONTENT_DESCRIPTION).to_string().as_str(),
-        Some(description_format)
-    )?;
+        config.get_document_field(TABLE_CONTENT_DESCRIPTION).as_str(),
+        CellFormat::Description
+    );
     conditions_sheet.merge_range(
         0, description_offset_col + 1, 0, state_elements_offset_col,
-        config.get_field(TABLE_CONTENT_SENSOR_STATES).to_string().as_str(),
-        Some(description_format)
-    )?;
+        config.get_document_field(TABLE_CONTENT_SENSOR_STATES).as_str(),
+        CellFormat::Description
+    );
     conditions_sheet.merge_range(
         0, state_elements_offset_col + 1, 0, control_elements_offset_col,
-        config.get_field(TABLE_CONTENT_CONTROL_STATES).to_string().as_str(),
-        Some(description_format)
-    )?;
+        config.get_document_field(TABLE_CONTENT_CONTROL_STATES).as_str(),
+        CellFormat::Description
+    );
     conditions_sheet.merge_range(
-        0, control_elements_offset_col + 1,
+        0, flag_elements_offset_col + 1,
         description_offset_row, transition_sign_offset_col,
-        config.get_field(TABLE_CONTENT_SIGN_OF_TRANSITION).to_string().as_str(),
-        Some(rotadet_format)
-    )?;
+        config.get_document_field(TABLE_CONTENT_SIGN_OF_TRANSITION).as_str(),
+        CellFormat::Rotated
+    );
     conditions_sheet.merge_range(
         0, transition_sign_offset_col + 1,
         description_offset_row, address_offset_col,
-        config.get_field(TABLE_CONTENT_TRASITION_ADDRESS).to_string().as_str(),
-        Some(rotadet_format)
-    )?;
+        config.get_document_field(TABLE_CONTENT_TRASITION_ADDRESS).as_str(),
+        CellFormat::Rotated
+    );
     conditions_sheet.merge_range(
         0, address_offset_col + 1,
         description_offset_row, blocked_sign_offset_col,
-        config.get_field(TABLE_CONTENT_SIGN_OF_BLOCKING).to_string().as_str(),
-        Some(rotadet_format)
-    )?;
+        config.get_document_field(TABLE_CONTENT_SIGN_OF_BLOCKING).as_str(),
+        CellFormat::Rotated
+    );
 
     let mut index = 1;
     for state_elemnt in state_elements.clone() {
-        let (name, ..) = state_elemnt.borrow().get_data();
+        let name = get_io_element_title(&state_elemnt.borrow(), FrameTypes::State);
         conditions_sheet.write_string(
             states_description_offset_row,
             description_offset_col + index as u16,
-            name.as_str(), Some(rotadet_format)
-        )?;
+            name.as_str(), CellFormat::Rotated
+        );
         conditions_sheet.write_number(
             states_number_offset_row,
             description_offset_col + index as u16,
-            index as f64, Some(description_format)
-        )?;
+            index as f64, CellFormat::Description
+        );
 
         index += 1;
     }
@@ -152,36 +418,43 @@ fn fill_condtions_sheet<'a>(
     index = 1;
 
     for control_element in control_elements.clone() {
-        let (name, ..) = control_element.borrow().get_data();
+        let name = get_io_element_title(&control_element.borrow(), FrameTypes::Control);
         conditions_sheet.write_string(
             states_description_offset_row,
             state_elements_offset_col + index as u16,
-            name.as_str(), Some(rotadet_format)
-        )?;
+            name.as_str(), CellFormat::Rotated
+        );
         conditions_sheet.write_number(
             states_number_offset_row,
             state_elements_offset_col + index as u16,
-            index as f64, Some(description_format)
-        )?;
+            index as f64, CellFormat::Description
+        );
 
         index += 1;
     }
 
+    write_flag_titles(
+        &mut conditions_sheet, &flag_elements, control_elements_offset_col,
+        states_description_offset_row, states_number_offset_row
+    );
+
     // Data fields
 
     index = 1;
 
     for condition in conditions_list {
-        let (description, states, controls, blocked, critical, address) =
+        let (description, states, controls, blocked, critical, _) =
             condition.borrow().get_data();
+        let address = condition.borrow().get_transition_address();
+        let unresolved = condition.borrow().has_unresolved_transition();
         let mut states_index = 0;
 
         conditions_sheet.merge_range(
             description_offset_row + index as u32, 0,
             description_offset_row + index as u32, description_offset_col,
             description.as_str(),
-            Some(description_format)
-        )?;
+            CellFormat::Description
+        );
 
         for state in state_elements.clone() {
             let content = get_conditions_state(&states, &state);
@@ -191,8 +464,8 @@ fn fill_condtions_sheet<'a>(
             conditions_sheet.write_string(
                 description_offset_row + index as u32,
                 description_offset_col + states_index as u16,
-                content.as_str(), Some(default_format)
-            )?;
+                content.as_str(), CellFormat::Default
+            );
         }
 
         states_index = 0;
@@ -205,61 +478,66 @@ fn fill_condtions_sheet<'a>(
             conditions_sheet.write_string(
                 description_offset_row + index as u32,
                 state_elements_offset_col + states_index as u16,
-                content.as_str(), Some(default_format)
-            )?;
+                content.as_str(), CellFormat::Default
+            );
         }
 
+        write_flag_states(
+            &mut conditions_sheet, &flag_elements, control_elements_offset_col,
+            description_offset_row + index as u32, &states, &controls
+        );
+
         conditions_sheet.write_string(
             description_offset_row + index as u32,
             transition_sign_offset_col,
             if blocked {STATE_ACTIVE} else {""},
-            Some(default_format)
-        )?;
+            CellFormat::Default
+        );
 
-        conditions_sheet.write_number(
-            description_offset_row + index as u32,
-            address_offset_col,
-            address as f64,
-            Some(default_format)
-        )?;
+        // Rows without a target keep address 0, a lost target is marked instead of
+        // jumping to a valid address
+        if unresolved {
+            conditions_sheet.write_string(
+                description_offset_row + index as u32,
+                address_offset_col,
+                ADDRESS_UNRESOLVED,
+                CellFormat::Default
+            );
+        } else {
+            conditions_sheet.write_number(
+                description_offset_row + index as u32,
+                address_offset_col,
+                address.unwrap_or(0) as f64,
+                CellFormat::Default
+            );
+        }
 
         conditions_sheet.write_string(
             description_offset_row + index as u32,
             blocked_sign_offset_col,
             if critical {STATE_ACTIVE} else {""},
-            Some(default_format)
-        )?;
+            CellFormat::Default
+        );
 
         index += 1;
 
     }
 
-    Ok(String::from("Ok"))
+    conditions_sheet
 }
 
-fn fill_subprograms_sheet<'a>(
-    subprograms_sheet: &'a mut Worksheet,
-    rotadet_format: &'a Format,
-    description_format: &'a Format,
-    default_format: &'a Format,
-) -> Result<String, XlsxError> {
+// Layout of the subprograms sheet, shared by the XLSX output and the preview
+pub fn build_subprograms_sheet() -> Sheet {
+    let mut subprograms_sheet = Sheet::new();
     let config = unsafe {
         &GLOBAL_CONFIG
-    }.as_ref().unwrap();
+    }.as_ref().unwrap().borrow();
 
     let subprogramconfig = unsafe {
         &SUBPROGRAMS_CONFIG
     }.as_ref().unwrap();
 
-    let ioconfig = unsafe {
-        &IO_CONFIG
-    }.as_ref().unwrap();
-
-    let state_elements = ioconfig.borrow()
-        .get_elements_by_frame_type(FrameTypes::State);
-
-    let control_elements = ioconfig.borrow()
-        .get_elements_by_frame_type(FrameTypes::Control);
+    let (state_elements, control_elements, flag_elements) = get_column_elements();
 
     let subprograms_list = subprogramconfig.borrow().get_subprograms();
 
@@ -271,7 +549,9 @@ fn fill_subprograms_sheet<'a>(
         operator_offset_col + state_elements.len() as u16;
     let control_elements_offset_col: u16 =
         state_elements_offset_col + control_elements.len() as u16;
-    let end_sign_offset_col = control_elements_offset_col + 1;
+    let flag_elements_offset_col: u16 =
+        control_elements_offset_col + 2 * flag_elements.len() as u16;
+    let end_sign_offset_col = flag_elements_offset_col + 1;
     let states_description_offset_row: u32 = 1;
     let states_number_offset_row: u32 = 2;
     let subprogram_step_description_offest_col: u16 = 1;
@@ -279,51 +559,51 @@ fn fill_subprograms_sheet<'a>(
     // Top descripton fields
     subprograms_sheet.merge_range(
         0, 0, description_offset_row, description_offset_col,
-        config.get_field(TABLE_CONTENT_DESCRIPTION).to_string().as_str(),
-        Some(description_format)
-    )?;
+        config.get_document_field(TABLE_CONTENT_DESCRIPTION).as_str(),
+        CellFormat::Description
+    );
     subprograms_sheet.merge_range(
         0, address_offset_col,
         description_offset_row, address_offset_col,
-        config.get_field(FIELD_ADDRESS).to_string().as_str(),
-        Some(rotadet_format)
-    )?;
+        config.get_document_field(FIELD_ADDRESS).as_str(),
+        CellFormat::Rotated
+    );
     subprograms_sheet.merge_range(
         0, operator_offset_col,
         description_offset_row, operator_offset_col,
-        config.get_field(OPERATOR).to_string().as_str(),
-        Some(description_format)
-    )?;
+        config.get_document_field(OPERATOR).as_str(),
+        CellFormat::Description
+    );
     subprograms_sheet.merge_range(
         0, operator_offset_col + 1, 0, state_elements_offset_col,
-        config.get_field(TABLE_CONTENT_SENSOR_STATES).to_string().as_str(),
-        Some(description_format)
-    )?;
+        config.get_document_field(TABLE_CONTENT_SENSOR_STATES).as_str(),
+        CellFormat::Description
+    );
     subprograms_sheet.merge_range(
         0, state_elements_offset_col + 1, 0, control_elements_offset_col,
-        config.get_field(TABLE_CONTENT_CONTROL_STATES).to_string().as_str(),
-        Some(description_format)
-    )?;
+        config.get_document_field(TABLE_CONTENT_CONTROL_STATES).as_str(),
+        CellFormat::Description
+    );
     subprograms_sheet.merge_range(
-        0, control_elements_offset_col + 1,
+        0, flag_elements_offset_col + 1,
         description_offset_row, end_sign_offset_col,
-        config.get_field(TABLE_CONTENT_SIGN_OF_FINISH).to_string().as_str(),
-        Some(rotadet_format)
-    )?;
+        config.get_document_field(TABLE_CONTENT_SIGN_OF_FINISH).as_str(),
+        CellFormat::Rotated
+    );
 
     let mut index = 1;
     for state_elemnt in state_elements.clone() {
-        let (name, ..) = state_elemnt.borrow().get_data();
+        let name = get_io_element_title(&state_elemnt.borrow(), FrameTypes::State);
         subprograms_sheet.write_string(
             states_description_offset_row,
             operator_offset_col + index as u16,
-            name.as_str(), Some(rotadet_format)
-        )?;
+            name.as_str(), CellFormat::Rotated
+        );
         subprograms_sheet.write_number(
             states_number_offset_row,
             operator_offset_col + index as u16,
-            index as f64, Some(description_format)
-        )?;
+            index as f64, CellFormat::Description
+        );
 
         index += 1;
     }
@@ -331,21 +611,26 @@ fn fill_subprograms_sheet<'a>(
     index = 1;
 
     for control_element in control_elements.clone() {
-        let (name, ..) = control_element.borrow().get_data();
+        let name = get_io_element_title(&control_element.borrow(), FrameTypes::Control);
         subprograms_sheet.write_string(
             states_description_offset_row,
             state_elements_offset_col + index as u16,
-            name.as_str(), Some(rotadet_format)
-        )?;
+            name.as_str(), CellFormat::Rotated
+        );
         subprograms_sheet.write_number(
             states_number_offset_row,
             state_elements_offset_col + index as u16,
-            index as f64, Some(description_format)
-        )?;
+            index as f64, CellFormat::Description
+        );
 
         index += 1;
     }
 
+    write_flag_titles(
+        &mut subprograms_sheet, &flag_elements, control_elements_offset_col,
+        states_description_offset_row, states_number_offset_row
+    );
+
     // Data fields
 
     index = 1;
@@ -353,24 +638,24 @@ fn fill_subprograms_sheet<'a>(
     subprograms_sheet.write_string(
         description_offset_row + index as u32, 0,
         "",
-        Some(description_format)
-    )?;
+        CellFormat::Description
+    );
     subprograms_sheet.merge_range(
         description_offset_row + index as u32, 1,
         description_offset_row + index as u32, description_offset_col,
-        config.get_field(TABLE_CONTENT_SUBPROGRAM_INITIAL).to_string().as_str(),
-        Some(default_format)
-    )?;
+        config.get_document_field(TABLE_CONTENT_SUBPROGRAM_INITIAL).as_str(),
+        CellFormat::Default
+    );
     subprograms_sheet.write_number (
         description_offset_row + index as u32, address_offset_col,
         0.,
-        Some(default_format)
-    )?;
+        CellFormat::Default
+    );
     subprograms_sheet.write_string(
         description_offset_row + index as u32, operator_offset_col,
         OPERATOR_AND,
-        Some(default_format)
-    )?;
+        CellFormat::Default
+    );
 
     let mut states_index: usize = 1;
 
@@ -378,8 +663,8 @@ fn fill_subprograms_sheet<'a>(
         subprograms_sheet.write_string(
             description_offset_row + index as u32,
             operator_offset_col + states_index as u16,
-            "", Some(default_format)
-        )?;
+            "", CellFormat::Default
+        );
 
         states_index += 1;
     }
@@ -390,18 +675,23 @@ fn fill_subprograms_sheet<'a>(
         subprograms_sheet.write_string(
             description_offset_row + index as u32,
             state_elements_offset_col + states_index as u16,
-            "", Some(default_format)
-        )?;
+            "", CellFormat::Default
+        );
 
         states_index += 1;
     }
 
+    write_flag_states(
+        &mut subprograms_sheet, &flag_elements, control_elements_offset_col,
+        description_offset_row + index as u32, &Vec::new(), &Vec::new()
+    );
+
     subprograms_sheet.write_string(
         description_offset_row + index as u32,
         end_sign_offset_col,
         STATE_ACTIVE,
-        Some(default_format)
-    )?;
+        CellFormat::Default
+    );
 
     index += 1;
 
@@ -414,8 +704,8 @@ fn fill_subprograms_sheet<'a>(
             description_offset_row + index as u32, 0,
             description_offset_row + (index + steps.len() - 1) as u32, 0,
             description.as_str(),
-            Some(rotadet_format)
-        )?;
+            CellFormat::Rotated
+        );
 
         for step in steps {
             let (_, operator, states, controls, description) = step.borrow().get_data();
@@ -426,20 +716,20 @@ fn fill_subprograms_sheet<'a>(
                 description_offset_row + index as u32, subprogram_step_description_offest_col,
                 description_offset_row + index as u32, description_offset_col,
                 description.as_str(),
-                Some(default_format)
-            )?;
+                CellFormat::Default
+            );
 
             subprograms_sheet.write_number (
                 description_offset_row + index as u32, address_offset_col,
                 (address + subprogram_index) as f64,
-                Some(default_format)
-            )?;
+                CellFormat::Default
+            );
 
             subprograms_sheet.write_string(
                 description_offset_row + index as u32, operator_offset_col,
                 if operator == Operators::AND {OPERATOR_AND}else{OPERATOR_OR},
-                Some(default_format)
-            )?;
+                CellFormat::Default
+            );
 
             for state in state_elements.clone() {
                 let content = get_conditions_state(&states, &state);
@@ -449,8 +739,8 @@ fn fill_subprograms_sheet<'a>(
                 subprograms_sheet.write_string(
                     description_offset_row + index as u32,
                     operator_offset_col + states_index as u16,
-                    content.as_str(), Some(default_format)
-                )?;
+                    content.as_str(), CellFormat::Default
+                );
             }
 
             states_index = 0;
@@ -463,9 +753,14 @@ fn fill_subprograms_sheet<'a>(
                 subprograms_sheet.write_string(
                     description_offset_row + index as u32,
                     state_elements_offset_col + states_index as u16,
-                    content.as_str(), Some(default_format)
-                )?;
+                    content.as_str(), CellFormat::Default
+                );
             }
+
+            write_flag_states(
+                &mut subprograms_sheet, &flag_elements, control_elements_offset_col,
+                description_offset_row + index as u32, &states, &controls
+            );
             subprogram_index += 1;
             index += 1;
         }
@@ -474,15 +769,248 @@ fn fill_subprograms_sheet<'a>(
             description_offset_row + (index - 1) as u32,
             end_sign_offset_col,
             STATE_ACTIVE,
-            Some(default_format)
-        )?;
+            CellFormat::Default
+        );
+    }
+
+    subprograms_sheet
+}
+
+// Layout of the timers sheet listing the preset of every timer
+pub fn build_timers_sheet() -> Sheet {
+    let mut timers_sheet = Sheet::new();
+    let config = unsafe {
+        &GLOBAL_CONFIG
+    }.as_ref().unwrap().borrow();
+
+    let ioconfig = unsafe {
+        &IO_CONFIG
+    }.as_ref().unwrap();
+
+    let timers = ioconfig.borrow().get_all_elelments().into_iter()
+        .filter(|element| ElementKind::Timer == element.borrow().get_kind());
+
+    let headers = [FIELD_NAME, FIELD_TIMER_MODE, FIELD_PRESET, FIELD_PRESET_MS];
+    for (col, header) in headers.iter().enumerate() {
+        timers_sheet.write_string(
+            0, col as u16,
+            config.get_document_field(header).as_str(),
+            CellFormat::Description
+        );
+    }
+
+    for (i, element) in timers.enumerate() {
+        let row = i as u32 + 1;
+        let element = element.borrow();
+        let (name, ..) = element.get_data();
+        let timer = element.get_timer();
+
+        timers_sheet.write_string(row, 0, name.as_str(), CellFormat::Default);
+        timers_sheet.write_string(row, 1, timer.mode.to_string().as_str(), CellFormat::Default);
+        timers_sheet.write_string(row, 2, timer.get_preset().as_str(), CellFormat::Default);
+        timers_sheet.write_number(row, 3, timer.preset_ms as f64, CellFormat::Default);
+    }
+
+    timers_sheet
+}
+
+// Layout of the counters sheet listing the preset of every counter
+pub fn build_counters_sheet() -> Sheet {
+    let mut counters_sheet = Sheet::new();
+    let config = unsafe {
+        &GLOBAL_CONFIG
+    }.as_ref().unwrap().borrow();
+
+    let ioconfig = unsafe {
+        &IO_CONFIG
+    }.as_ref().unwrap();
+
+    let counters = ioconfig.borrow().get_all_elelments().into_iter()
+        .filter(|element| ElementKind::Counter == element.borrow().get_kind());
+
+    let headers = [FIELD_NAME, FIELD_COUNTER_MODE, FIELD_PRESET];
+    for (col, header) in headers.iter().enumerate() {
+        counters_sheet.write_string(
+            0, col as u16,
+            config.get_document_field(header).as_str(),
+            CellFormat::Description
+        );
+    }
+
+    for (i, element) in counters.enumerate() {
+        let row = i as u32 + 1;
+        let element = element.borrow();
+        let (name, ..) = element.get_data();
+        let counter = element.get_counter();
+
+        counters_sheet.write_string(row, 0, name.as_str(), CellFormat::Default);
+        counters_sheet.write_string(row, 1, counter.mode.to_string().as_str(), CellFormat::Default);
+        counters_sheet.write_number(row, 2, counter.preset as f64, CellFormat::Default);
+    }
+
+    counters_sheet
+}
+
+// Layout of the analog bands sheet with the comparator of every band, the transmitter
+// input is named after its channel
+pub fn build_analog_sheet() -> Sheet {
+    let mut analog_sheet = Sheet::new();
+    let config = unsafe {
+        &GLOBAL_CONFIG
+    }.as_ref().unwrap().borrow();
+
+    let ioconfig = unsafe {
+        &IO_CONFIG
+    }.as_ref().unwrap();
+
+    let bands = ioconfig.borrow().get_all_elelments().into_iter()
+        .filter(|element| ElementKind::Analog == element.borrow().get_kind());
+
+    let headers = [FIELD_NAME, FIELD_HW, FIELD_LOW, FIELD_HIGH, FIELD_HYSTERESIS, FIELD_LOGIC];
+    for (col, header) in headers.iter().enumerate() {
+        analog_sheet.write_string(
+            0, col as u16,
+            config.get_document_field(header).as_str(),
+            CellFormat::Description
+        );
+    }
+
+    for (i, element) in bands.enumerate() {
+        let row = i as u32 + 1;
+        let element = element.borrow();
+        let (name, _, _, hw_address) = element.get_data();
+        let analog = element.get_analog();
+        let input = format!(
+            "AI_{}_{}_{}", hw_address.rack, hw_address.slot, hw_address.channel
+        );
+
+        analog_sheet.write_string(row, 0, name.as_str(), CellFormat::Default);
+        analog_sheet.write_string(row, 1, hw_address.to_string().as_str(), CellFormat::Default);
+        analog_sheet.write_string(row, 2, analog.low.as_str(), CellFormat::Default);
+        analog_sheet.write_string(row, 3, analog.high.as_str(), CellFormat::Default);
+        analog_sheet.write_string(row, 4, analog.hysteresis.as_str(), CellFormat::Default);
+        // Shown in the preview only, generation stops on invalid bands
+        let logic = if analog.is_valid() {
+            analog.get_logic(&input)
+        } else {
+            config.get_field(ANALOG_ISSUE_LIMITS).to_string()
+        };
+
+        analog_sheet.write_string(row, 5, logic.as_str(), CellFormat::Default);
+    }
+
+    analog_sheet
+}
+
+// IEC 61131-3 identifiers of the flags: letters, digits and single underscores, not
+// starting with a digit. Identifiers are case insensitive, clashes get a number appended
+fn get_flag_identifiers(names: &[String]) -> Vec<String> {
+    let mut used: HashSet<String> = HashSet::new();
+
+    names.iter().map(|name| {
+        let words: Vec<String> = name
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(String::from)
+            .collect();
+        let base = match words.first() {
+            None => String::from("FLAG"),
+            Some(word) if word.starts_with(|c: char| c.is_ascii_digit()) => {
+                format!("FLAG_{}", words.join("_"))
+            },
+            Some(_) => words.join("_"),
+        };
+
+        let mut identifier = base.clone();
+        let mut number = 1;
+        while used.contains(&identifier.to_lowercase()) {
+            number += 1;
+            identifier = format!("{}_{}", base, number);
+        }
+        used.insert(identifier.to_lowercase());
+
+        identifier
+    }).collect()
+}
+
+// Layout of the flags sheet: the declaration block code backends paste as the internal
+// variables of the program, the original names are kept in the comments
+pub fn build_flags_sheet() -> Sheet {
+    let mut flags_sheet = Sheet::new();
+    let config = unsafe {
+        &GLOBAL_CONFIG
+    }.as_ref().unwrap().borrow();
+
+    let names: Vec<String> = unsafe {
+        &IO_CONFIG
+    }.as_ref().unwrap().borrow().get_elements_by_frame_type(FrameTypes::Flag).iter()
+        .map(|element| element.borrow().get_data().0)
+        .collect();
+
+    let headers = [FIELD_NAME, FIELD_DECLARATION];
+    for (col, header) in headers.iter().enumerate() {
+        flags_sheet.write_string(
+            0, col as u16,
+            config.get_document_field(header).as_str(),
+            CellFormat::Description
+        );
     }
 
-    Ok(String::from("Ok"))
+    flags_sheet.write_string(1, 1, "VAR", CellFormat::Default);
+
+    for (i, (name, identifier)) in names.iter().zip(get_flag_identifiers(&names)).enumerate() {
+        let row = i as u32 + 2;
+
+        flags_sheet.write_string(row, 0, name.as_str(), CellFormat::Default);
+        flags_sheet.write_string(
+            row, 1,
+            format!("    {} : BOOL; (* {} *)", identifier, name.replace("*)", "* )")).as_str(),
+            CellFormat::Default
+        );
+    }
+
+    flags_sheet.write_string(names.len() as u32 + 2, 1, "END_VAR", CellFormat::Default);
+
+    flags_sheet
 }
 
-pub fn generate_tables() -> Result<String, XlsxError>{
-    let workbook = Workbook::new(RESULT_TABLE);
+pub fn generate_tables() -> Result<String, GenerateError>{
+    unsafe {
+        &SUBPROGRAMS_CONFIG
+    }.as_ref().unwrap().borrow_mut().update_addresses();
+
+    let unresolved = unsafe {
+        &CONDTIONS_CONFIG
+    }.as_ref().unwrap().borrow().get_conditions().into_iter()
+        .find(|condition| condition.borrow().has_unresolved_transition());
+
+    if let Some(condition) = unresolved {
+        let (description, ..) = condition.borrow().get_data();
+
+        return Err(GenerateError::UnresolvedTransition(description));
+    }
+
+    let invalid_band = unsafe {
+        &IO_CONFIG
+    }.as_ref().unwrap().borrow().get_all_elelments().into_iter()
+        .find(|element| {
+            let element = element.borrow();
+
+            ElementKind::Analog == element.get_kind() && !element.get_analog().is_valid()
+        });
+
+    if let Some(element) = invalid_band {
+        let (name, ..) = element.borrow().get_data();
+
+        return Err(GenerateError::InvalidAnalogBand(name));
+    }
+
+    let config = unsafe {
+        &GLOBAL_CONFIG
+    }.as_ref().unwrap().borrow();
+
+    let result_table = Path::new(config.get_output_dir().as_str()).join(RESULT_TABLE);
+    let workbook = Workbook::new(result_table.to_string_lossy().as_ref());
     let format_rotated_description = workbook.add_format()
         .set_align(FormatAlignment::Center)
         .set_align(FormatAlignment::VerticalCenter)
@@ -505,35 +1033,93 @@ pub fn generate_tables() -> Result<String, XlsxError>{
         .set_border_left(FormatBorder::Thin)
         .set_border_right(FormatBorder::Thin)
         .set_border_bottom(FormatBorder::Thin);
-
-
-    let config = unsafe {
-        &GLOBAL_CONFIG
-    }.as_ref().unwrap();
+    let get_format = |format: CellFormat| match format {
+        CellFormat::Rotated => &format_rotated_description,
+        CellFormat::Description => &format_description,
+        CellFormat::Default => &format_default,
+    };
 
     let mut condtions_sheet = workbook.add_worksheet(
-        Some(config.get_field(TABLE_SHEET_CONDITIONS).to_string().as_str())
+        Some(config.get_sheet_name(TABLE_SHEET_CONDITIONS).as_str())
     )?;
 
-    fill_condtions_sheet(
-        &mut condtions_sheet,
-        &format_rotated_description,
-        &format_description,
-        &format_default
-    )?;
+    write_sheet(&mut condtions_sheet, &build_conditions_sheet(), get_format)?;
 
     let mut subprograms_sheet = workbook.add_worksheet(
-        Some(config.get_field(TABLE_SHEET_SUBPROGRAMS).to_string().as_str())
+        Some(config.get_sheet_name(TABLE_SHEET_SUBPROGRAMS).as_str())
     )?;
 
-    fill_subprograms_sheet(
-        &mut subprograms_sheet,
-        &format_rotated_description,
-        &format_description,
-        &format_default
-    )?;
+    write_sheet(&mut subprograms_sheet, &build_subprograms_sheet(), get_format)?;
+
+    let kinds: Vec<ElementKind> = unsafe {
+        &IO_CONFIG
+    }.as_ref().unwrap().borrow().get_all_elelments().iter()
+        .map(|element| element.borrow().get_kind())
+        .collect();
+
+    if kinds.contains(&ElementKind::Timer) {
+        let mut timers_sheet = workbook.add_worksheet(
+            Some(config.get_sheet_name(TABLE_SHEET_TIMERS).as_str())
+        )?;
+
+        write_sheet(&mut timers_sheet, &build_timers_sheet(), get_format)?;
+    }
+
+    if kinds.contains(&ElementKind::Counter) {
+        let mut counters_sheet = workbook.add_worksheet(
+            Some(config.get_sheet_name(TABLE_SHEET_COUNTERS).as_str())
+        )?;
+
+        write_sheet(&mut counters_sheet, &build_counters_sheet(), get_format)?;
+    }
+
+    if kinds.contains(&ElementKind::Analog) {
+        let mut analog_sheet = workbook.add_worksheet(
+            Some(config.get_sheet_name(TABLE_SHEET_ANALOG).as_str())
+        )?;
+
+        write_sheet(&mut analog_sheet, &build_analog_sheet(), get_format)?;
+    }
+
+    let has_flags = unsafe {
+        &IO_CONFIG
+    }.as_ref().unwrap().borrow().get_all_elelments().iter()
+        .any(|element| element.borrow().is_flag());
+
+    if has_flags {
+        let mut flags_sheet = workbook.add_worksheet(
+            Some(config.get_sheet_name(TABLE_SHEET_FLAGS).as_str())
+        )?;
+
+        write_sheet(&mut flags_sheet, &build_flags_sheet(), get_format)?;
+    }
 
     workbook.close()?;
 
-    Ok(String::from("Ok"))
+    Ok(result_table.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identifiers(names: &[&str]) -> Vec<String> {
+        get_flag_identifiers(&names.iter().map(|name| name.to_string()).collect::<Vec<String>>())
+    }
+
+    #[test]
+    fn flag_identifiers_are_valid_iec_names() {
+        assert_eq!(
+            identifiers(&["Belt running", "__cycle--done__", "1st pass", "Насос"]),
+            vec!["Belt_running", "cycle_done", "FLAG_1st_pass", "FLAG"]
+        );
+    }
+
+    #[test]
+    fn flag_identifiers_are_unique_ignoring_case() {
+        assert_eq!(
+            identifiers(&["Ready", "READY", "ready 2", "Ready"]),
+            vec!["Ready", "READY_2", "ready_2_2", "Ready_3"]
+        );
+    }
 }
@@ -0,0 +1,345 @@
+use crate::configuration::{
+    language_pack_conastants::{
+        HW_ISSUE_DUPLICATE, HW_ISSUE_NO_MODULE, HW_ISSUE_SIGNAL_MISMATCH, HW_ISSUE_CHANNEL_RANGE,
+        HW_ISSUE_DUPLICATE_MODULE, ASSIGN_NO_FREE_CHANNEL
+    },
+    SignalTypes, GLOBAL_CONFIG
+};
+
+// Location of a point in modular IO, for discrete signals the channel is the bit of the module
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct HwAddress {
+    pub rack: u8,
+    pub slot: u8,
+    pub channel: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HwField {
+    Rack,
+    Slot,
+    Channel,
+}
+
+impl HwAddress {
+    // "rack.slot.channel", leading parts may be left out: "slot.channel" or "channel"
+    pub fn parse(text: &str) -> Option<Self> {
+        let parts = text.trim().split('.')
+            .map(|part| part.trim().parse::<u16>().ok())
+            .collect::<Option<Vec<u16>>>()?;
+        let byte = |value: u16| if value > u8::MAX as u16 { None } else { Some(value as u8) };
+
+        match parts.as_slice() {
+            [channel] => Some(HwAddress {rack: 0, slot: 0, channel: *channel}),
+            [slot, channel] => Some(HwAddress {rack: 0, slot: byte(*slot)?, channel: *channel}),
+            [rack, slot, channel] => Some(HwAddress {
+                rack: byte(*rack)?, slot: byte(*slot)?, channel: *channel
+            }),
+            _ => None
+        }
+    }
+
+    // An emptied field counts as zero so the input can be cleared while typing
+    pub fn with_field(self, field: HwField, text: &str) -> Option<Self> {
+        let text = match text.trim() {
+            "" => "0",
+            text => text,
+        };
+        let mut address = self;
+
+        match field {
+            HwField::Rack => address.rack = text.parse().ok()?,
+            HwField::Slot => address.slot = text.parse().ok()?,
+            HwField::Channel => address.channel = text.parse().ok()?,
+        }
+
+        Some(address)
+    }
+
+    pub fn get_field(&self, field: HwField) -> u16 {
+        match field {
+            HwField::Rack => self.rack as u16,
+            HwField::Slot => self.slot as u16,
+            HwField::Channel => self.channel,
+        }
+    }
+}
+
+impl std::fmt::Display for HwAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.rack, self.slot, self.channel)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum HwModuleMessage {
+    RackChanged(String),
+    SlotChanged(String),
+    SignalTypeSelected(SignalTypes),
+    ChannelsChanged(String),
+    DeleteModule,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HwModule {
+    pub rack: u8,
+    pub slot: u8,
+    pub signal_type: SignalTypes,
+    pub channels: u16,
+}
+
+impl HwModule {
+    pub fn new() -> Self {
+        HwModule {
+            rack: 0,
+            slot: 0,
+            signal_type: SignalTypes::Input,
+            channels: 8,
+        }
+    }
+
+    pub fn update(&mut self, message: HwModuleMessage) {
+        let parse = |text: &str| match text.trim() {
+            "" => Some(0),
+            text => text.parse::<u16>().ok(),
+        };
+
+        match message {
+            HwModuleMessage::RackChanged(rack) => {
+                if let Some(rack) = parse(&rack).filter(|rack| *rack <= u8::MAX as u16) {
+                    self.rack = rack as u8
+                }
+            },
+            HwModuleMessage::SlotChanged(slot) => {
+                if let Some(slot) = parse(&slot).filter(|slot| *slot <= u8::MAX as u16) {
+                    self.slot = slot as u8
+                }
+            },
+            HwModuleMessage::SignalTypeSelected(signal_type) => {
+                self.signal_type = signal_type
+            },
+            HwModuleMessage::ChannelsChanged(channels) => {
+                if let Some(channels) = parse(&channels) {
+                    self.channels = channels
+                }
+            },
+            _ => ()
+        }
+    }
+
+    // Modules start on a byte boundary of the process image
+    fn get_size(&self) -> u32 {
+        (self.channels as u32 + 7) / 8
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum HwLayoutMessage {
+    AddModule,
+    ModuleMessage(usize, HwModuleMessage),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HwIssue {
+    DuplicateChannel(String),
+    NoModule,
+    SignalMismatch,
+    ChannelOutOfRange,
+    DuplicateModule,
+}
+
+impl std::fmt::Display for HwIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+
+        match self {
+            HwIssue::DuplicateChannel(name) => write!(
+                f, "{} {}", config.get_field(HW_ISSUE_DUPLICATE).to_string(), name
+            ),
+            HwIssue::NoModule => write!(
+                f, "{}", config.get_field(HW_ISSUE_NO_MODULE).to_string()
+            ),
+            HwIssue::SignalMismatch => write!(
+                f, "{}", config.get_field(HW_ISSUE_SIGNAL_MISMATCH).to_string()
+            ),
+            HwIssue::ChannelOutOfRange => write!(
+                f, "{}", config.get_field(HW_ISSUE_CHANNEL_RANGE).to_string()
+            ),
+            HwIssue::DuplicateModule => write!(
+                f, "{}", config.get_field(HW_ISSUE_DUPLICATE_MODULE).to_string()
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum HwAssignMessage {
+    KeepToggled(bool),
+    Close,
+}
+
+// Address change planned by the automatic assignment, None when the channels ran out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HwAssignment {
+    pub uid: usize,
+    pub name: String,
+    pub from: HwAddress,
+    pub to: Option<HwAddress>,
+}
+
+impl std::fmt::Display for HwAssignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.to {
+            Some(to) => write!(f, "{}: {} -> {}", self.name, self.from, to),
+            None => {
+                let config = unsafe {
+                    &GLOBAL_CONFIG
+                }.as_ref().unwrap().borrow();
+
+                write!(
+                    f, "{}: {}", self.name, config.get_field(ASSIGN_NO_FREE_CHANNEL).to_string()
+                )
+            }
+        }
+    }
+}
+
+// Declared modules, an empty layout leaves the addresses unchecked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HwLayout {
+    modules: Vec<HwModule>,
+}
+
+impl HwLayout {
+    pub fn new() -> Self {
+        HwLayout {
+            modules: vec![]
+        }
+    }
+
+    pub fn update(&mut self, message: HwLayoutMessage) {
+        match message {
+            HwLayoutMessage::AddModule => {
+                // Continues in the next free slot after the last module
+                let mut module = match self.modules.last() {
+                    Some(last) => *last,
+                    None => HwModule::new()
+                };
+
+                while self.get_module(module.rack, module.slot).is_some() && module.slot < u8::MAX {
+                    module.slot += 1;
+                }
+
+                self.modules.push(module)
+            },
+            HwLayoutMessage::ModuleMessage(i, HwModuleMessage::DeleteModule) => {
+                if i < self.modules.len() {
+                    self.modules.remove(i);
+                }
+            },
+            HwLayoutMessage::ModuleMessage(i, message) => {
+                if let Some(module) = self.modules.get_mut(i) {
+                    module.update(message)
+                }
+            },
+        }
+    }
+
+    pub fn get_modules(&self) -> Vec<HwModule> {
+        self.modules.clone()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty()
+    }
+
+    pub fn get_module(&self, rack: u8, slot: u8) -> Option<&HwModule> {
+        self.modules.iter().find(|module| module.rack == rack && module.slot == slot)
+    }
+
+    // Only the first of the modules declared in the same rack and slot is used
+    pub fn is_duplicate(&self, rack: u8, slot: u8) -> bool {
+        self.modules.iter().filter(|module| module.rack == rack && module.slot == slot).count() > 1
+    }
+
+    // Input and output modules are mapped separately in rack and slot order
+    fn get_offset(&self, module: &HwModule) -> u32 {
+        self.modules.iter()
+            .filter(|other| other.signal_type == module.signal_type)
+            .filter(|other| {
+                self.get_module(other.rack, other.slot)
+                    .map_or(false, |first| std::ptr::eq(first, *other))
+            })
+            .filter(|other| (other.rack, other.slot) < (module.rack, module.slot))
+            .map(HwModule::get_size)
+            .sum()
+    }
+
+    pub fn check(&self, address: HwAddress, signal_type: SignalTypes) -> Vec<HwIssue> {
+        if self.is_empty() {
+            return vec![];
+        }
+
+        match self.get_module(address.rack, address.slot) {
+            Some(module) => {
+                let mut issues = vec![];
+
+                if self.is_duplicate(address.rack, address.slot) {
+                    issues.push(HwIssue::DuplicateModule);
+                }
+                if module.signal_type != signal_type {
+                    issues.push(HwIssue::SignalMismatch);
+                }
+                if address.channel >= module.channels {
+                    issues.push(HwIssue::ChannelOutOfRange);
+                }
+
+                issues
+            },
+            None => vec![HwIssue::NoModule]
+        }
+    }
+
+    // Channels available to the signal type in rack and slot order. Without declared
+    // modules inputs take slot 0 and outputs slot 1
+    pub fn get_channels(&self, signal_type: SignalTypes) -> Vec<HwAddress> {
+        if self.is_empty() {
+            let slot = match signal_type {
+                SignalTypes::Input => 0,
+                SignalTypes::Output => 1,
+            };
+
+            return (0..=u16::MAX)
+                .map(|channel| HwAddress {rack: 0, slot: slot, channel: channel})
+                .collect();
+        }
+
+        let mut modules: Vec<&HwModule> = self.modules.iter()
+            .filter(|module| module.signal_type == signal_type)
+            .collect();
+        modules.sort_by_key(|module| (module.rack, module.slot));
+        modules.dedup_by_key(|module| (module.rack, module.slot));
+
+        modules.iter()
+            .flat_map(|module| (0..module.channels).map(move |channel| HwAddress {
+                rack: module.rack, slot: module.slot, channel: channel
+            }))
+            .collect()
+    }
+
+    // IEC 61131-3 direct address, e.g. %IX1.3 for the twelfth input bit
+    pub fn get_iec_address(&self, address: HwAddress, signal_type: SignalTypes) -> Option<String> {
+        let module = self.get_module(address.rack, address.slot)
+            .filter(|module| module.signal_type == signal_type)
+            .filter(|module| address.channel < module.channels)?;
+        let byte = self.get_offset(module) + address.channel as u32 / 8;
+        let area = match signal_type {
+            SignalTypes::Input => "I",
+            SignalTypes::Output => "Q",
+        };
+
+        Some(format!("%{}X{}.{}", area, byte, address.channel % 8))
+    }
+}
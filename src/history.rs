@@ -0,0 +1,229 @@
+use crate::configs::{
+    IOConfig, SubprogramConfig, CondtionsConfig, IO_CONFIG, SUBPROGRAMS_CONFIG,
+    CONDTIONS_CONFIG, IOConfigMessage, IOElementMessage, SubprogramConfigMessage,
+    SubprogramMessage, SubprogramStepMessage, CondtionsConfigMessage,
+    ConditionsConfigElementMessage, get_io_element_usages
+};
+use crate::hardware::{HwLayoutMessage, HwModuleMessage};
+use crate::view::PresetViewMessage;
+
+const HISTORY_DEPTH: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Edit {
+    None,
+    Discrete,
+    // Consecutive edits of the same text field are merged into one step
+    Text(String),
+}
+
+#[derive(Debug)]
+pub struct ModelSnapshot {
+    io_config: IOConfig,
+    subprograms_config: SubprogramConfig,
+    conditions_config: CondtionsConfig,
+}
+
+impl ModelSnapshot {
+    pub fn take() -> Self {
+        let io_config = unsafe {
+            &IO_CONFIG
+        }.as_ref().unwrap().borrow().deep_copy();
+
+        let subprograms_config = unsafe {
+            &SUBPROGRAMS_CONFIG
+        }.as_ref().unwrap().borrow().deep_copy(&io_config);
+
+        let conditions_config = unsafe {
+            &CONDTIONS_CONFIG
+        }.as_ref().unwrap().borrow().deep_copy(&io_config);
+
+        ModelSnapshot {
+            io_config: io_config,
+            subprograms_config: subprograms_config,
+            conditions_config: conditions_config,
+        }
+    }
+
+    pub fn restore(self) {
+        *unsafe {
+            &IO_CONFIG
+        }.as_ref().unwrap().borrow_mut() = self.io_config;
+
+        *unsafe {
+            &SUBPROGRAMS_CONFIG
+        }.as_ref().unwrap().borrow_mut() = self.subprograms_config;
+
+        *unsafe {
+            &CONDTIONS_CONFIG
+        }.as_ref().unwrap().borrow_mut() = self.conditions_config;
+    }
+}
+
+#[derive(Debug)]
+pub struct History {
+    undo_stack: Vec<ModelSnapshot>,
+    redo_stack: Vec<ModelSnapshot>,
+    last_edit: Edit,
+}
+
+impl History {
+    pub fn new() -> Self {
+        History {
+            undo_stack: vec![],
+            redo_stack: vec![],
+            last_edit: Edit::None,
+        }
+    }
+
+    // Must be called before the message is applied to the model
+    pub fn record(&mut self, message: &PresetViewMessage) {
+        let edit = get_edit(message);
+
+        match edit {
+            Edit::None => return,
+            Edit::Text(_) if edit == self.last_edit => return,
+            _ => {}
+        }
+
+        self.undo_stack.push(ModelSnapshot::take());
+        if self.undo_stack.len() > HISTORY_DEPTH {
+            self.undo_stack.remove(0);
+        }
+
+        self.redo_stack.clear();
+        self.last_edit = edit;
+    }
+
+    pub fn undo(&mut self) -> bool {
+        match self.undo_stack.pop() {
+            Some(snapshot) => {
+                self.redo_stack.push(ModelSnapshot::take());
+                snapshot.restore();
+                self.last_edit = Edit::None;
+                true
+            },
+            None => false
+        }
+    }
+
+    pub fn redo(&mut self) -> bool {
+        match self.redo_stack.pop() {
+            Some(snapshot) => {
+                self.undo_stack.push(ModelSnapshot::take());
+                snapshot.restore();
+                self.last_edit = Edit::None;
+                true
+            },
+            None => false
+        }
+    }
+}
+
+fn get_edit(message: &PresetViewMessage) -> Edit {
+    match message {
+        PresetViewMessage::IOConfigMessage(message) => get_io_edit(message),
+        PresetViewMessage::SubprogramConfigMessage(message) => get_subprogram_edit(message),
+        PresetViewMessage::CondtionsConfigMessage(message) => get_conditions_edit(message),
+        _ => Edit::None
+    }
+}
+
+fn get_io_edit(message: &IOConfigMessage) -> Edit {
+    match message {
+        IOConfigMessage::IOElementMessage(i, message) => {
+            match message {
+                IOElementMessage::NameInputChanged(_) => Edit::Text(format!("io/{}/name", i)),
+                IOElementMessage::HwSelected(_) => Edit::Text(format!("io/{}/hw", i)),
+                IOElementMessage::HwFieldChanged(field, _) => {
+                    Edit::Text(format!("io/{}/hw/{:?}", i, field))
+                },
+                IOElementMessage::TimerPresetChanged(_) => {
+                    Edit::Text(format!("io/{}/preset", i))
+                },
+                IOElementMessage::CounterPresetChanged(_) => {
+                    Edit::Text(format!("io/{}/counter_preset", i))
+                },
+                IOElementMessage::AnalogLowChanged(_) => Edit::Text(format!("io/{}/low", i)),
+                IOElementMessage::AnalogHighChanged(_) => Edit::Text(format!("io/{}/high", i)),
+                IOElementMessage::AnalogHysteresisChanged(_) => {
+                    Edit::Text(format!("io/{}/hysteresis", i))
+                },
+                IOElementMessage::DeleteElement => {
+                    let io_config = unsafe {
+                        &IO_CONFIG
+                    }.as_ref().unwrap();
+                    let uid = io_config.borrow().get_elemnt_by_id(*i).borrow().get_uid();
+
+                    // Elements in use are deleted from the confirmation dialog
+                    if get_io_element_usages(uid).len() > 0 {
+                        Edit::None
+                    } else {
+                        Edit::Discrete
+                    }
+                },
+                _ => Edit::Discrete
+            }
+        },
+        IOConfigMessage::DeleteElementCanceled |
+        IOConfigMessage::ReassignElementSelected(_) |
+        IOConfigMessage::OpenImport |
+        IOConfigMessage::OpenBulk |
+        IOConfigMessage::BulkMessage(_) |
+        IOConfigMessage::ImportMessage(_) |
+        IOConfigMessage::OpenHardware |
+        IOConfigMessage::CloseHardware |
+        IOConfigMessage::OpenAssign |
+        IOConfigMessage::AssignMessage(_) => Edit::None,
+        IOConfigMessage::HardwareMessage(HwLayoutMessage::ModuleMessage(i, message)) => {
+            match message {
+                HwModuleMessage::RackChanged(_) => Edit::Text(format!("hardware/{}/rack", i)),
+                HwModuleMessage::SlotChanged(_) => Edit::Text(format!("hardware/{}/slot", i)),
+                HwModuleMessage::ChannelsChanged(_) => {
+                    Edit::Text(format!("hardware/{}/channels", i))
+                },
+                _ => Edit::Discrete
+            }
+        },
+        _ => Edit::Discrete
+    }
+}
+
+fn get_subprogram_edit(message: &SubprogramConfigMessage) -> Edit {
+    match message {
+        SubprogramConfigMessage::SubprogramMessage(i, message) => {
+            match message {
+                SubprogramMessage::SubprogramEdit => Edit::None,
+                SubprogramMessage::SubprogramDescrptionChanged(_) => {
+                    Edit::Text(format!("subprogram/{}/name", i))
+                },
+                SubprogramMessage::SubprogramStepMessage(j, message) => {
+                    match message {
+                        SubprogramStepMessage::PickConditions(_) => Edit::None,
+                        SubprogramStepMessage::DescriptionChanged(_) => {
+                            Edit::Text(format!("subprogram/{}/step/{}/description", i, j))
+                        },
+                        _ => Edit::Discrete
+                    }
+                },
+                _ => Edit::Discrete
+            }
+        },
+        _ => Edit::Discrete
+    }
+}
+
+fn get_conditions_edit(message: &CondtionsConfigMessage) -> Edit {
+    match message {
+        CondtionsConfigMessage::ConditionsConfigElementMessage(i, message) => {
+            match message {
+                ConditionsConfigElementMessage::PickConditions(_) => Edit::None,
+                ConditionsConfigElementMessage::DescriptionChanged(_) => {
+                    Edit::Text(format!("condition/{}/description", i))
+                },
+                _ => Edit::Discrete
+            }
+        },
+        _ => Edit::Discrete
+    }
+}
@@ -2,35 +2,76 @@ use std::{rc::Rc, cell::RefCell};
 
 use iced::{
     button, Align, Button, Column, Element, Length, Text, Row, TextInput,
-    text_input, pick_list, PickList
+    text_input, pick_list, PickList, Checkbox, Scrollable, scrollable
 };
 
 use crate::configuration:: {
     language_pack_conastants::{
-        FIELD_NAME, FIELD_TYPE, FIELD_SIGNAL, FIELD_HW
+        FIELD_NAME, FIELD_TYPE, FIELD_SIGNAL, FIELD_HW, IO_ELEMENT_USAGES,
+        BUTTON_CANCEL, BUTTON_REMOVE_REFERENCES, BUTTON_REASSIGN_REFERENCES,
+        FIELD_CSV_FILE, BUTTON_LOAD, FIELD_REPLACE_IO, BUTTON_IMPORT, IMPORT_VALID_ROWS,
+        FIELD_RACK, FIELD_SLOT, FIELD_CHANNEL, FIELD_CHANNELS, FIELD_IEC_ADDRESS,
+        BUTTON_ADD_MODULE, BUTTON_BACK, HARDWARE_EMPTY, FIELD_KEEP_ADDRESSES, BUTTON_APPLY,
+        ASSIGN_NO_CHANGES, ASSIGN_CHANGED, ASSIGN_NO_FREE_CHANNEL, IMPORT_ISSUE_DUPLICATE_NAME,
+        FIELD_PATTERN, FIELD_FROM, FIELD_TO, FIELD_FIRST_ADDRESS, BUTTON_CREATE,
+        FIELD_KIND, FIELD_TIMER_MODE, FIELD_PRESET_MS, FIELD_PRESET, FIELD_COUNTER_MODE,
+        FIELD_PRESET_COUNT, FIELD_LOW, FIELD_HIGH, FIELD_HYSTERESIS, ANALOG_ISSUE_LIMITS
     },
-    style_config::DEFAULT_SPACING,
-    FrameTypes, GLOBAL_CONFIG, SignalTypes,
+    style_config::{self, DEFAULT_SPACING},
+    FrameTypes, GLOBAL_CONFIG, SignalTypes, ElementKind,
     delete_icon
 };
 
 use crate::configs:: {
-    IOElement, IOElementMessage
+    IOElement, IOElementMessage, IOConfigMessage, IOElementId, IOElementUsage,
+    IO_CONFIG
+};
+use crate::csvimport::{
+    IOImportMessage, ImportField, ImportColumn, ImportRow, ColumnMapping, CsvTable,
+    IMPORT_FIELDS_ALL, load_csv, map_rows
+};
+use crate::bulkcreate::{IOBulkMessage, BulkPattern, BulkRow};
+use crate::timer::{TimerMode, TIMER_MODES_ALL};
+use crate::counter::{CounterMode, COUNTER_MODES_ALL};
+use crate::hardware::{
+    HwField, HwModule, HwModuleMessage, HwLayoutMessage, HwAssignment, HwAssignMessage, HwIssue
 };
 
 #[derive(Debug)]
 pub struct IOElementView {
     name_input: text_input::State,
+    kind_list: pick_list::State<ElementKind>,
     type_list: pick_list::State<FrameTypes>,
     signal_list: pick_list::State<SignalTypes>,
-    hw_input: text_input::State,
+    hw_inputs: Vec<text_input::State>,
+    timer_mode_list: pick_list::State<TimerMode>,
+    preset_input: text_input::State,
+    counter_mode_list: pick_list::State<CounterMode>,
+    counter_preset_input: text_input::State,
+    low_input: text_input::State,
+    high_input: text_input::State,
+    hysteresis_input: text_input::State,
     delete_button: button::State,
     ioelemnt: Rc<RefCell<IOElement>>,
 }
 
+static ELEMENT_KINDS_ALL: &[ElementKind] = &[
+    ElementKind::Discrete,
+    ElementKind::Timer,
+    ElementKind::Counter,
+    ElementKind::Analog,
+];
+
 static FRAME_TYPES_ALL: &[FrameTypes] = &[
     FrameTypes::State,
     FrameTypes::Control,
+    FrameTypes::Flag,
+];
+
+// Bulk created elements take channels, internal flags have none
+static BULK_FRAME_TYPES: &[FrameTypes] = &[
+    FrameTypes::State,
+    FrameTypes::Control,
 ];
 
 static SIGNAL_TYPES_ALL: &[SignalTypes] = &[
@@ -38,13 +79,53 @@ static SIGNAL_TYPES_ALL: &[SignalTypes] = &[
     SignalTypes::Output,
 ];
 
+static HW_FIELDS_ALL: &[HwField] = &[
+    HwField::Rack,
+    HwField::Slot,
+    HwField::Channel,
+];
+
+fn get_hw_field_label(field: HwField) -> &'static str {
+    match field {
+        HwField::Rack => FIELD_RACK,
+        HwField::Slot => FIELD_SLOT,
+        HwField::Channel => FIELD_CHANNEL,
+    }
+}
+
+fn number_input<'a, Message: Clone>(
+    state: &'a mut text_input::State,
+    value: u16,
+    on_change: impl 'static + Fn(String) -> Message
+) -> TextInput<'a, Message> {
+    TextInput::new(state, "", value.to_string().as_str(), on_change)
+        .size(30).width(Length::Units(50)).style(style_config::TextInput)
+}
+
+fn limit_input<'a, Message: Clone>(
+    state: &'a mut text_input::State,
+    value: &str,
+    on_change: impl 'static + Fn(String) -> Message
+) -> TextInput<'a, Message> {
+    TextInput::new(state, "", value, on_change)
+        .size(30).width(Length::Units(70)).style(style_config::TextInput)
+}
+
 impl<'a> IOElementView {
     pub fn new(ioelemnt: Rc<RefCell<IOElement>>) -> Self {
         IOElementView {
             name_input: text_input::State::new(),
+            kind_list: pick_list::State::default(),
             type_list: pick_list::State::default(),
             signal_list: pick_list::State::default(),
-            hw_input: text_input::State::new(),
+            hw_inputs: HW_FIELDS_ALL.iter().map(|_| text_input::State::new()).collect(),
+            timer_mode_list: pick_list::State::default(),
+            preset_input: text_input::State::new(),
+            counter_mode_list: pick_list::State::default(),
+            counter_preset_input: text_input::State::new(),
+            low_input: text_input::State::new(),
+            high_input: text_input::State::new(),
+            hysteresis_input: text_input::State::new(),
             delete_button: button::State::new(),
             ioelemnt: ioelemnt.clone(),
         }
@@ -53,62 +134,934 @@ impl<'a> IOElementView {
     pub fn view(&'a mut self) -> Element<'a, IOElementMessage> {
         let (name, frame_type, signal_type, hw_address) =
             self.ioelemnt.borrow().get_data();
+        let (kind, timer, counter, analog) = {
+            let element = self.ioelemnt.borrow();
+
+            (element.get_kind(), element.get_timer(), element.get_counter(), element.get_analog())
+        };
 
         let name_input = TextInput::new(
             &mut self.name_input,
             "", &name.as_str(),
             IOElementMessage::NameInputChanged
-        ).size(30).width(Length::Units(140));
+        ).size(30).width(Length::Units(140)).style(style_config::TextInput);
+
+        let kind_list = PickList::new(
+            &mut self.kind_list,
+            ELEMENT_KINDS_ALL,
+            Some(kind),
+            IOElementMessage::KindSelected
+        ).style(style_config::PickList);
 
         let type_list = PickList::new(
             &mut self.type_list,
             FRAME_TYPES_ALL,
             Some(frame_type),
             IOElementMessage::FrameTypeSelected
-        );
+        ).style(style_config::PickList);
 
         let signal_list = PickList::new(
             &mut self.signal_list,
             SIGNAL_TYPES_ALL,
             Some(signal_type),
             IOElementMessage::SignalTypeSelected
-        );
-
-        let hw_input = TextInput::new(
-            &mut self.hw_input,
-            "", String::from(format!("{}", hw_address)).as_str(),
-            IOElementMessage::HwSelected
-        ).size(30).width(Length::Units(30));
+        ).style(style_config::PickList);
 
         let config = unsafe {
             &GLOBAL_CONFIG
-        }.as_ref().unwrap();
+        }.as_ref().unwrap().borrow();
+
+        let hw_inputs = HW_FIELDS_ALL.iter()
+            .zip(self.hw_inputs.iter_mut())
+            .fold(Row::new().spacing(5), |row, (field, state)| {
+                let field = *field;
+
+                row.push(Column::new()
+                    .push(Text::new(config.get_field(get_hw_field_label(field)).to_string()))
+                    .push(number_input(
+                        state, hw_address.get_field(field),
+                        move |value| IOElementMessage::HwFieldChanged(field, value)
+                    )))
+            });
+
+        let (iec_address, issues) = {
+            let io_config = unsafe {
+                &IO_CONFIG
+            }.as_ref().unwrap().borrow();
+            let element = self.ioelemnt.borrow();
+
+            (io_config.get_iec_address(&element), io_config.get_hw_issues(&element))
+        };
+        let issues = issues.iter()
+            .map(|issue| issue.to_string())
+            .collect::<Vec<String>>()
+            .join(", ");
 
         let delete_button = Button::new(
             &mut self.delete_button, delete_icon())
+            .style(style_config::Button::Icon)
             .on_press(IOElementMessage::DeleteElement);
 
-        Row::new()
+        let row = Row::new()
             .spacing(DEFAULT_SPACING)
             .push(Column::new()
                 .push(Text::new(config.get_field(FIELD_NAME).to_string()
                                                               .as_str()))
                 .push(name_input))
             .push(Column::new()
-                .push(Text::new(config.get_field(FIELD_TYPE).to_string()
-                                                              .as_str()))
-                .push(type_list))
+                .push(Text::new(config.get_field(FIELD_KIND).to_string()))
+                .push(kind_list));
+
+        // Internal flags, timers and counters have no signal wiring, the settings of the
+        // blocks take the place of the hardware columns. Analog bands keep the channel of
+        // their transmitter
+        let row = match kind {
+            ElementKind::Timer => row
+                .push(Column::new()
+                    .push(Text::new(config.get_field(FIELD_TIMER_MODE).to_string()))
+                    .push(PickList::new(
+                        &mut self.timer_mode_list,
+                        TIMER_MODES_ALL,
+                        Some(timer.mode),
+                        IOElementMessage::TimerModeSelected
+                    ).style(style_config::PickList)))
+                .push(Column::new()
+                    .push(Text::new(config.get_field(FIELD_PRESET_MS).to_string()))
+                    .push(TextInput::new(
+                        &mut self.preset_input,
+                        "", timer.preset_ms.to_string().as_str(),
+                        IOElementMessage::TimerPresetChanged
+                    ).size(30).width(Length::Units(100)).style(style_config::TextInput)))
+                .push(Column::new()
+                    .width(Length::Units(120))
+                    .push(Text::new(config.get_field(FIELD_PRESET).to_string()))
+                    .push(Text::new(timer.get_preset()).size(30))),
+            ElementKind::Counter => row
+                .push(Column::new()
+                    .push(Text::new(config.get_field(FIELD_COUNTER_MODE).to_string()))
+                    .push(PickList::new(
+                        &mut self.counter_mode_list,
+                        COUNTER_MODES_ALL,
+                        Some(counter.mode),
+                        IOElementMessage::CounterModeSelected
+                    ).style(style_config::PickList)))
+                .push(Column::new()
+                    .push(Text::new(config.get_field(FIELD_PRESET_COUNT).to_string()))
+                    .push(TextInput::new(
+                        &mut self.counter_preset_input,
+                        "", counter.preset.to_string().as_str(),
+                        IOElementMessage::CounterPresetChanged
+                    ).size(30).width(Length::Units(100)).style(style_config::TextInput))),
+            ElementKind::Analog => {
+                let issue = if analog.is_valid() {
+                    String::new()
+                } else {
+                    config.get_field(ANALOG_ISSUE_LIMITS).to_string()
+                };
+
+                row
+                    .push(Column::new()
+                        .push(Text::new(config.get_field(FIELD_HW).to_string()))
+                        .push(hw_inputs))
+                    .push(Column::new()
+                        .push(Text::new(config.get_field(FIELD_LOW).to_string()))
+                        .push(limit_input(
+                            &mut self.low_input, analog.low.as_str(),
+                            IOElementMessage::AnalogLowChanged
+                        )))
+                    .push(Column::new()
+                        .push(Text::new(config.get_field(FIELD_HIGH).to_string()))
+                        .push(limit_input(
+                            &mut self.high_input, analog.high.as_str(),
+                            IOElementMessage::AnalogHighChanged
+                        )))
+                    .push(Column::new()
+                        .push(Text::new(config.get_field(FIELD_HYSTERESIS).to_string()))
+                        .push(limit_input(
+                            &mut self.hysteresis_input, analog.hysteresis.as_str(),
+                            IOElementMessage::AnalogHysteresisChanged
+                        )))
+                    .push(Column::new()
+                        .width(Length::Units(120))
+                        .push(Text::new(analog.get_range()).size(20))
+                        .push(Text::new(issue).size(16).color([0.8, 0.4, 0.0]))
+                        .push(Text::new(issues).size(16).color([0.8, 0.4, 0.0])))
+            },
+            ElementKind::Discrete if FrameTypes::Flag == frame_type => row
+                .push(Column::new()
+                    .push(Text::new(config.get_field(FIELD_TYPE).to_string()))
+                    .push(type_list)),
+            ElementKind::Discrete => row
+                .push(Column::new()
+                    .push(Text::new(config.get_field(FIELD_TYPE).to_string()
+                                                                  .as_str()))
+                    .push(type_list))
+                .push(Column::new()
+                    .push(Text::new(config.get_field(FIELD_SIGNAL).to_string()
+                                                                  .as_str()))
+                    .push(signal_list))
+                .push(Column::new()
+                    .push(Text::new(config.get_field(FIELD_HW).to_string()
+                                                                  .as_str()))
+                    .push(hw_inputs))
+                .push(Column::new()
+                    .width(Length::Units(120))
+                    .push(Text::new(config.get_field(FIELD_IEC_ADDRESS).to_string()))
+                    .push(Text::new(iec_address.unwrap_or(String::from("-"))).size(30))
+                    .push(Text::new(issues).size(16).color([0.8, 0.4, 0.0]))),
+        };
+
+        row
+            .push(Column::new().width(Length::Units(40)).height(Length::Units(40)).align_items(Align::Center).push(delete_button))
+
+            .into()
+
+    }
+}
+
+#[derive(Debug)]
+pub struct IODeleteView {
+    cancel_button: button::State,
+    remove_button: button::State,
+    reassign_button: button::State,
+    reassign_list: pick_list::State<IOElementId>,
+    reassign_to: Option<IOElementId>,
+    element_id: usize,
+    ioelemnt: Rc<RefCell<IOElement>>,
+    usages: Vec<IOElementUsage>,
+}
+
+impl<'a> IODeleteView {
+    pub fn new(
+        element_id: usize,
+        ioelemnt: Rc<RefCell<IOElement>>,
+        usages: Vec<IOElementUsage>
+    ) -> Self {
+        IODeleteView {
+            cancel_button: button::State::new(),
+            remove_button: button::State::new(),
+            reassign_button: button::State::new(),
+            reassign_list: pick_list::State::default(),
+            reassign_to: None,
+            element_id: element_id,
+            ioelemnt: ioelemnt.clone(),
+            usages: usages,
+        }
+    }
+
+    pub fn update(&mut self, message: IOConfigMessage) {
+        match message {
+            IOConfigMessage::ReassignElementSelected(id) => {
+                self.reassign_to = Some(id)
+            },
+            _ => {}
+        }
+    }
+
+    fn get_reassign_list(&self) -> Vec<IOElementId> {
+        let (_, frame_type, ..) = self.ioelemnt.borrow().get_data();
+        let uid = self.ioelemnt.borrow().get_uid();
+        let io_config = unsafe {
+            &IO_CONFIG
+        }.as_ref().unwrap();
+
+        io_config.borrow().get_elements_by_frame_type(frame_type).iter()
+            .map(|element| IOElementId(element.borrow().get_uid()))
+            .filter(|id| id.0 != uid)
+            .collect()
+    }
+
+    pub fn view(&'a mut self) -> Element<'a, IOConfigMessage> {
+        let (name, ..) = self.ioelemnt.borrow().get_data();
+        let reassign_list = self.get_reassign_list();
+
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+
+        let usages = self.usages.iter().fold(
+            Column::new().spacing(10),
+            |column, usage| column.push(Text::new(usage.to_string()).size(25))
+        );
+
+        let reassign_list = PickList::new(
+            &mut self.reassign_list,
+            reassign_list,
+            self.reassign_to,
+            IOConfigMessage::ReassignElementSelected
+        ).style(style_config::PickList);
+
+        let mut reassign_button = Button::new(
+            &mut self.reassign_button,
+            Text::new(config.get_field(BUTTON_REASSIGN_REFERENCES).to_string())
+        ).style(style_config::Button::Primary);
+
+        if let Some(id) = self.reassign_to {
+            reassign_button = reassign_button
+                .on_press(IOConfigMessage::DeleteElementReassigned(self.element_id, id));
+        }
+
+        let remove_button = Button::new(
+            &mut self.remove_button,
+            Text::new(config.get_field(BUTTON_REMOVE_REFERENCES).to_string())
+        ).style(style_config::Button::Primary)
+            .on_press(IOConfigMessage::DeleteElementConfirmed(self.element_id));
+
+        let cancel_button = Button::new(
+            &mut self.cancel_button,
+            Text::new(config.get_field(BUTTON_CANCEL).to_string())
+        ).style(style_config::Button::Secondary)
+            .on_press(IOConfigMessage::DeleteElementCanceled);
+
+        Column::new()
+            .spacing(DEFAULT_SPACING)
+            .align_items(Align::Center)
+            .push(Text::new(format!(
+                "{} \"{}\":", config.get_field(IO_ELEMENT_USAGES).to_string(), name
+            )).size(30))
+            .push(usages)
+            .push(Row::new()
+                .spacing(DEFAULT_SPACING)
+                .align_items(Align::Center)
+                .push(reassign_list)
+                .push(reassign_button))
+            .push(Row::new()
+                .spacing(DEFAULT_SPACING)
+                .push(cancel_button)
+                .push(remove_button))
+
+            .into()
+    }
+}
+
+#[derive(Debug)]
+pub struct IOImportView {
+    path_input: text_input::State,
+    load_button: button::State,
+    cancel_button: button::State,
+    import_button: button::State,
+    replace_checkbox: bool,
+    column_lists: Vec<pick_list::State<ImportColumn>>,
+    scroll: scrollable::State,
+    path: String,
+    table: Option<CsvTable>,
+    error: Option<String>,
+    mapping: ColumnMapping,
+}
+
+fn get_field_label(field: ImportField) -> &'static str {
+    match field {
+        ImportField::Name => FIELD_NAME,
+        ImportField::FrameType => FIELD_TYPE,
+        ImportField::SignalType => FIELD_SIGNAL,
+        ImportField::HwAddress => FIELD_HW,
+    }
+}
+
+impl<'a> IOImportView {
+    pub fn new() -> Self {
+        IOImportView {
+            path_input: text_input::State::new(),
+            load_button: button::State::new(),
+            cancel_button: button::State::new(),
+            import_button: button::State::new(),
+            replace_checkbox: false,
+            column_lists: IMPORT_FIELDS_ALL.iter().map(|_| pick_list::State::default()).collect(),
+            scroll: scrollable::State::new(),
+            path: String::new(),
+            table: None,
+            error: None,
+            mapping: ColumnMapping::guess(&vec![]),
+        }
+    }
+
+    pub fn update(&mut self, message: IOImportMessage) {
+        match message {
+            IOImportMessage::PathChanged(path) => {
+                self.path = path
+            },
+            IOImportMessage::Load => {
+                match load_csv(self.path.as_str()) {
+                    Ok(table) => {
+                        self.mapping = ColumnMapping::guess(&table.headers);
+                        self.table = Some(table);
+                        self.error = None;
+                    },
+                    Err(error) => {
+                        self.table = None;
+                        self.error = Some(error.to_string());
+                    }
+                }
+            },
+            IOImportMessage::ColumnSelected(field, column) => {
+                self.mapping.set(field, column.index)
+            },
+            IOImportMessage::ReplaceToggled(replace) => {
+                self.replace_checkbox = replace
+            },
+            _ => ()
+        }
+    }
+
+    fn get_rows(&self) -> Vec<ImportRow> {
+        let existing_names = if self.replace_checkbox {
+            vec![]
+        } else {
+            unsafe {
+                &IO_CONFIG
+            }.as_ref().unwrap().borrow().get_all_elelments().iter()
+                .map(|element| element.borrow().get_data().0)
+                .collect()
+        };
+
+        self.table.as_ref()
+            .map_or(vec![], |table| map_rows(table, &self.mapping, &existing_names))
+    }
+
+    pub fn view(&'a mut self) -> Element<'a, IOConfigMessage> {
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+
+        let rows = self.get_rows();
+        let elements: Vec<IOElement> = rows.iter()
+            .filter(|row| row.is_valid())
+            .map(ImportRow::to_element)
+            .collect();
+        let columns = self.table.as_ref().map_or(vec![], CsvTable::get_columns);
+
+        let path_input = TextInput::new(
+            &mut self.path_input,
+            "", self.path.as_str(),
+            |path| IOConfigMessage::ImportMessage(IOImportMessage::PathChanged(path))
+        ).size(30).width(Length::Units(500)).style(style_config::TextInput)
+            .on_submit(IOConfigMessage::ImportMessage(IOImportMessage::Load));
+
+        let load_button = Button::new(
+            &mut self.load_button,
+            Text::new(config.get_field(BUTTON_LOAD).to_string())
+        ).style(style_config::Button::Secondary)
+            .on_press(IOConfigMessage::ImportMessage(IOImportMessage::Load));
+
+        let mapping = self.mapping;
+        let column_lists = IMPORT_FIELDS_ALL.iter()
+            .zip(self.column_lists.iter_mut())
+            .fold(Row::new().spacing(DEFAULT_SPACING), |row, (field, state)| {
+                let field = *field;
+                let selected = columns.iter()
+                    .find(|column| column.index == mapping.get(field))
+                    .cloned();
+
+                row.push(Column::new()
+                    .push(Text::new(config.get_field(get_field_label(field)).to_string()))
+                    .push(PickList::new(
+                        state,
+                        columns.clone(),
+                        selected,
+                        move |column| IOConfigMessage::ImportMessage(
+                            IOImportMessage::ColumnSelected(field, column)
+                        )
+                    ).style(style_config::PickList)))
+            });
+
+        let replace_checkbox = Checkbox::new(
+            self.replace_checkbox,
+            config.get_field(FIELD_REPLACE_IO).to_string(),
+            |replace| IOConfigMessage::ImportMessage(IOImportMessage::ReplaceToggled(replace))
+        ).style(style_config::Checkbox);
+
+        let preview = rows.iter().fold(
+            Column::new().spacing(10),
+            |column, row| {
+                let issues = row.issues.iter()
+                    .map(|issue| issue.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ");
+
+                column.push(Row::new()
+                    .spacing(DEFAULT_SPACING)
+                    .push(Text::new(row.line.to_string()).size(20).width(Length::Units(60)))
+                    .push(Text::new(row.name.clone()).size(20).width(Length::Units(200)))
+                    .push(Text::new(row.frame_type.to_string()).size(20).width(Length::Units(120)))
+                    .push(Text::new(row.signal_type.to_string()).size(20).width(Length::Units(120)))
+                    .push(Text::new(row.hw_address.to_string()).size(20).width(Length::Units(60)))
+                    .push(Text::new(issues).size(20).color([0.8, 0.4, 0.0])))
+            }
+        );
+
+        let mut import_button = Button::new(
+            &mut self.import_button,
+            Text::new(format!(
+                "{} ({}: {}/{})", config.get_field(BUTTON_IMPORT).to_string(),
+                config.get_field(IMPORT_VALID_ROWS).to_string(), elements.len(), rows.len()
+            ))
+        ).style(style_config::Button::Primary);
+
+        if !elements.is_empty() {
+            import_button = import_button
+                .on_press(IOConfigMessage::ImportElements(elements, self.replace_checkbox));
+        }
+
+        let cancel_button = Button::new(
+            &mut self.cancel_button,
+            Text::new(config.get_field(BUTTON_CANCEL).to_string())
+        ).style(style_config::Button::Secondary)
+            .on_press(IOConfigMessage::ImportMessage(IOImportMessage::Cancel));
+
+        let mut content = Column::new()
+            .spacing(DEFAULT_SPACING)
+            .align_items(Align::Center)
+            .push(Row::new()
+                .spacing(DEFAULT_SPACING)
+                .align_items(Align::Center)
+                .push(Text::new(config.get_field(FIELD_CSV_FILE).to_string()))
+                .push(path_input)
+                .push(load_button));
+
+        if let Some(error) = &self.error {
+            content = content.push(Text::new(error.clone()).color([0.8, 0.4, 0.0]));
+        }
+
+        if self.table.is_some() {
+            content = content
+                .push(column_lists)
+                .push(replace_checkbox)
+                .push(Scrollable::new(&mut self.scroll)
+                    .height(Length::Units(400))
+                    .push(preview));
+        }
+
+        content
+            .push(Row::new()
+                .spacing(DEFAULT_SPACING)
+                .push(cancel_button)
+                .push(import_button))
+            .into()
+    }
+}
+
+#[derive(Debug)]
+pub struct HwModuleView {
+    rack_input: text_input::State,
+    slot_input: text_input::State,
+    signal_list: pick_list::State<SignalTypes>,
+    channels_input: text_input::State,
+    delete_button: button::State,
+    module: HwModule,
+    duplicate: bool,
+}
+
+impl<'a> HwModuleView {
+    pub fn new(module: HwModule) -> Self {
+        HwModuleView {
+            rack_input: text_input::State::new(),
+            slot_input: text_input::State::new(),
+            signal_list: pick_list::State::default(),
+            channels_input: text_input::State::new(),
+            delete_button: button::State::new(),
+            module: module,
+            duplicate: false,
+        }
+    }
+
+    pub fn view(&'a mut self) -> Element<'a, HwModuleMessage> {
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+
+        let signal_list = PickList::new(
+            &mut self.signal_list,
+            SIGNAL_TYPES_ALL,
+            Some(self.module.signal_type),
+            HwModuleMessage::SignalTypeSelected
+        ).style(style_config::PickList);
+
+        let delete_button = Button::new(
+            &mut self.delete_button, delete_icon())
+            .style(style_config::Button::Icon)
+            .on_press(HwModuleMessage::DeleteModule);
+
+        let issue = if self.duplicate {
+            HwIssue::DuplicateModule.to_string()
+        } else {
+            String::new()
+        };
+
+        Row::new()
+            .spacing(DEFAULT_SPACING)
             .push(Column::new()
-                .push(Text::new(config.get_field(FIELD_SIGNAL).to_string()
-                                                              .as_str()))
+                .push(Text::new(config.get_field(FIELD_RACK).to_string()))
+                .push(number_input(
+                    &mut self.rack_input, self.module.rack as u16, HwModuleMessage::RackChanged
+                )))
+            .push(Column::new()
+                .push(Text::new(config.get_field(FIELD_SLOT).to_string()))
+                .push(number_input(
+                    &mut self.slot_input, self.module.slot as u16, HwModuleMessage::SlotChanged
+                )))
+            .push(Column::new()
+                .push(Text::new(config.get_field(FIELD_SIGNAL).to_string()))
                 .push(signal_list))
             .push(Column::new()
-                .push(Text::new(config.get_field(FIELD_HW).to_string()
-                                                              .as_str()))
-                .push(hw_input))
+                .push(Text::new(config.get_field(FIELD_CHANNELS).to_string()))
+                .push(number_input(
+                    &mut self.channels_input, self.module.channels,
+                    HwModuleMessage::ChannelsChanged
+                )))
             .push(Column::new().width(Length::Units(40)).height(Length::Units(40)).align_items(Align::Center).push(delete_button))
+            .push(Text::new(issue).size(16).color([0.8, 0.4, 0.0]))
+            .into()
+    }
+}
+
+// Module layout of the racks, addresses of the IO elements are checked against it
+#[derive(Debug)]
+pub struct HardwareView {
+    scroll: scrollable::State,
+    add_button: button::State,
+    back_button: button::State,
+    modules: Vec<HwModuleView>,
+}
+
+impl<'a> HardwareView {
+    pub fn new() -> Self {
+        let mut view = HardwareView {
+            scroll: scrollable::State::new(),
+            add_button: button::State::new(),
+            back_button: button::State::new(),
+            modules: vec![],
+        };
+
+        view.reload();
+        view
+    }
+
+    // The module views copy the model, so they are rebuilt after every change
+    pub fn reload(&mut self) {
+        let hardware = unsafe {
+            &IO_CONFIG
+        }.as_ref().unwrap().borrow().get_hardware();
+
+        self.modules.resize_with(hardware.get_modules().len(), || HwModuleView::new(HwModule::new()));
+
+        for (view, module) in self.modules.iter_mut().zip(hardware.get_modules()) {
+            view.duplicate = hardware.is_duplicate(module.rack, module.slot);
+            view.module = module;
+        }
+    }
+
+    pub fn view(&'a mut self) -> Element<'a, IOConfigMessage> {
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+
+        let modules: Element<_> = if self.modules.is_empty() {
+            Text::new(config.get_field(HARDWARE_EMPTY).to_string()).into()
+        } else {
+            self.modules.iter_mut()
+                .enumerate()
+                .fold(Column::new().spacing(DEFAULT_SPACING), |column, (i, module)| {
+                    column.push(module.view().map(move |message| {
+                        IOConfigMessage::HardwareMessage(HwLayoutMessage::ModuleMessage(i, message))
+                    }))
+                })
+                .into()
+        };
+
+        let add_button = Button::new(
+            &mut self.add_button,
+            Text::new(config.get_field(BUTTON_ADD_MODULE).to_string())
+        ).style(style_config::Button::Primary)
+            .on_press(IOConfigMessage::HardwareMessage(HwLayoutMessage::AddModule));
+
+        let back_button = Button::new(
+            &mut self.back_button,
+            Text::new(config.get_field(BUTTON_BACK).to_string())
+        ).style(style_config::Button::Secondary)
+            .on_press(IOConfigMessage::CloseHardware);
 
+        Scrollable::new(&mut self.scroll)
+            .spacing(DEFAULT_SPACING)
+            .align_items(Align::Center)
+            .push(modules)
+            .push(Row::new()
+                .spacing(DEFAULT_SPACING)
+                .push(back_button)
+                .push(add_button))
             .into()
+    }
+}
+
+// Preview of the automatic address assignment, kept as the report once applied
+#[derive(Debug)]
+pub struct HwAssignView {
+    scroll: scrollable::State,
+    apply_button: button::State,
+    back_button: button::State,
+    keep: bool,
+    report: Option<Vec<HwAssignment>>,
+}
+
+impl<'a> HwAssignView {
+    pub fn new() -> Self {
+        HwAssignView {
+            scroll: scrollable::State::new(),
+            apply_button: button::State::new(),
+            back_button: button::State::new(),
+            keep: true,
+            report: None,
+        }
+    }
+
+    pub fn update(&mut self, message: HwAssignMessage) {
+        match message {
+            HwAssignMessage::KeepToggled(keep) => {
+                self.keep = keep
+            },
+            _ => ()
+        }
+    }
+
+    pub fn set_report(&mut self, report: Vec<HwAssignment>) {
+        self.report = Some(report)
+    }
 
+    pub fn view(&'a mut self) -> Element<'a, IOConfigMessage> {
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+
+        let assignments = match &self.report {
+            Some(report) => report.clone(),
+            None => unsafe {
+                &IO_CONFIG
+            }.as_ref().unwrap().borrow().plan_hw_addresses(self.keep),
+        };
+
+        let mut content = Column::new()
+            .spacing(DEFAULT_SPACING)
+            .align_items(Align::Center);
+
+        if self.report.is_some() {
+            content = content.push(Text::new(config.get_field(ASSIGN_CHANGED).to_string()));
+        } else {
+            content = content.push(Checkbox::new(
+                self.keep,
+                config.get_field(FIELD_KEEP_ADDRESSES).to_string(),
+                |keep| IOConfigMessage::AssignMessage(HwAssignMessage::KeepToggled(keep))
+            ).style(style_config::Checkbox));
+        }
+
+        let changes: Element<_> = if assignments.is_empty() {
+            Text::new(config.get_field(ASSIGN_NO_CHANGES).to_string()).into()
+        } else {
+            assignments.iter()
+                .fold(Column::new().spacing(5), |column, assignment| {
+                    let text = Text::new(assignment.to_string()).size(20);
+
+                    column.push(match assignment.to {
+                        Some(_) => text,
+                        None => text.color([0.8, 0.4, 0.0]),
+                    })
+                })
+                .into()
+        };
+
+        let back_button = Button::new(
+            &mut self.back_button,
+            Text::new(config.get_field(BUTTON_BACK).to_string())
+        ).style(style_config::Button::Secondary)
+            .on_press(IOConfigMessage::AssignMessage(HwAssignMessage::Close));
+
+        let mut buttons = Row::new()
+            .spacing(DEFAULT_SPACING)
+            .push(back_button);
+
+        if self.report.is_none() {
+            let mut apply_button = Button::new(
+                &mut self.apply_button,
+                Text::new(config.get_field(BUTTON_APPLY).to_string())
+            ).style(style_config::Button::Primary);
+
+            if assignments.iter().any(|assignment| assignment.to.is_some()) {
+                apply_button = apply_button
+                    .on_press(IOConfigMessage::AssignHwAddresses(self.keep));
+            }
+
+            buttons = buttons.push(apply_button);
+        }
+
+        content
+            .push(Scrollable::new(&mut self.scroll)
+                .height(Length::Units(400))
+                .push(changes))
+            .push(buttons)
+            .into()
+    }
+}
+
+#[derive(Debug)]
+pub struct IOBulkView {
+    pattern_input: text_input::State,
+    from_input: text_input::State,
+    to_input: text_input::State,
+    type_list: pick_list::State<FrameTypes>,
+    signal_list: pick_list::State<SignalTypes>,
+    address_input: text_input::State,
+    scroll: scrollable::State,
+    cancel_button: button::State,
+    create_button: button::State,
+    pattern: BulkPattern,
+    // Expanded on every change of the pattern, not on every draw
+    rows: Result<Vec<BulkRow>, &'static str>,
+}
+
+impl<'a> IOBulkView {
+    pub fn new() -> Self {
+        IOBulkView {
+            pattern_input: text_input::State::focused(),
+            from_input: text_input::State::new(),
+            to_input: text_input::State::new(),
+            type_list: pick_list::State::default(),
+            signal_list: pick_list::State::default(),
+            address_input: text_input::State::new(),
+            scroll: scrollable::State::new(),
+            cancel_button: button::State::new(),
+            create_button: button::State::new(),
+            pattern: BulkPattern::new(),
+            rows: Ok(vec![]),
+        }
+    }
+
+    pub fn update(&mut self, message: IOBulkMessage) {
+        self.pattern.update(message);
+        self.rows = self.pattern.get_rows();
+    }
+
+    pub fn view(&'a mut self) -> Element<'a, IOConfigMessage> {
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+
+        let input = |state, value: &String, width, message: fn(String) -> IOBulkMessage| {
+            TextInput::new(
+                state, "", value.as_str(),
+                move |value| IOConfigMessage::BulkMessage(message(value))
+            ).size(30).width(Length::Units(width)).style(style_config::TextInput)
+        };
+
+        let fields = Row::new()
+            .spacing(DEFAULT_SPACING)
+            .push(Column::new()
+                .push(Text::new(config.get_field(FIELD_PATTERN).to_string()))
+                .push(input(
+                    &mut self.pattern_input, &self.pattern.pattern, 300,
+                    IOBulkMessage::PatternChanged
+                )))
+            .push(Column::new()
+                .push(Text::new(config.get_field(FIELD_FROM).to_string()))
+                .push(input(
+                    &mut self.from_input, &self.pattern.from, 70, IOBulkMessage::FromChanged
+                )))
+            .push(Column::new()
+                .push(Text::new(config.get_field(FIELD_TO).to_string()))
+                .push(input(
+                    &mut self.to_input, &self.pattern.to, 70, IOBulkMessage::ToChanged
+                )));
+
+        let types = Row::new()
+            .spacing(DEFAULT_SPACING)
+            .push(Column::new()
+                .push(Text::new(config.get_field(FIELD_TYPE).to_string()))
+                .push(PickList::new(
+                    &mut self.type_list,
+                    BULK_FRAME_TYPES,
+                    Some(self.pattern.frame_type),
+                    |frame_type| IOConfigMessage::BulkMessage(
+                        IOBulkMessage::FrameTypeSelected(frame_type)
+                    )
+                ).style(style_config::PickList)))
+            .push(Column::new()
+                .push(Text::new(config.get_field(FIELD_SIGNAL).to_string()))
+                .push(PickList::new(
+                    &mut self.signal_list,
+                    SIGNAL_TYPES_ALL,
+                    Some(self.pattern.signal_type),
+                    |signal_type| IOConfigMessage::BulkMessage(
+                        IOBulkMessage::SignalTypeSelected(signal_type)
+                    )
+                ).style(style_config::PickList)))
+            .push(Column::new()
+                .push(Text::new(config.get_field(FIELD_FIRST_ADDRESS).to_string()))
+                .push(input(
+                    &mut self.address_input, &self.pattern.first_address, 150,
+                    IOBulkMessage::FirstAddressChanged
+                )));
+
+        let (preview, elements): (Element<_>, Vec<IOElement>) = match &self.rows {
+            Ok(rows) => {
+                let preview = rows.iter().fold(
+                    Column::new().spacing(5),
+                    |column, row| {
+                        let mut issues = vec![];
+
+                        if row.duplicate {
+                            issues.push(config.get_field(IMPORT_ISSUE_DUPLICATE_NAME).to_string());
+                        }
+                        if row.hw_address.is_none() {
+                            issues.push(config.get_field(ASSIGN_NO_FREE_CHANNEL).to_string());
+                        }
+
+                        column.push(Row::new()
+                            .spacing(DEFAULT_SPACING)
+                            .push(Text::new(row.name.clone()).size(20).width(Length::Units(200)))
+                            .push(Text::new(row.hw_address.map_or(String::from("-"), |address| {
+                                address.to_string()
+                            })).size(20).width(Length::Units(100)))
+                            .push(Text::new(issues.join(", ")).size(20).color([0.8, 0.4, 0.0])))
+                    }
+                );
+                let elements = rows.iter()
+                    .filter(|row| row.is_valid())
+                    .map(|row| row.to_element(&self.pattern))
+                    .collect();
+
+                (preview.into(), elements)
+            },
+            Err(error) => (
+                Text::new(config.get_field(*error).to_string()).color([0.8, 0.4, 0.0]).into(),
+                vec![]
+            )
+        };
+
+        let cancel_button = Button::new(
+            &mut self.cancel_button,
+            Text::new(config.get_field(BUTTON_CANCEL).to_string())
+        ).style(style_config::Button::Secondary)
+            .on_press(IOConfigMessage::BulkMessage(IOBulkMessage::Cancel));
+
+        let mut create_button = Button::new(
+            &mut self.create_button,
+            Text::new(format!("{} ({})", config.get_field(BUTTON_CREATE).to_string(), elements.len()))
+        ).style(style_config::Button::Primary);
+
+        if !elements.is_empty() {
+            create_button = create_button
+                .on_press(IOConfigMessage::ImportElements(elements, false));
+        }
+
+        Column::new()
+            .spacing(DEFAULT_SPACING)
+            .align_items(Align::Center)
+            .push(fields)
+            .push(types)
+            .push(Scrollable::new(&mut self.scroll)
+                .height(Length::Units(400))
+                .push(preview))
+            .push(Row::new()
+                .spacing(DEFAULT_SPACING)
+                .push(cancel_button)
+                .push(create_button))
+            .into()
     }
 }
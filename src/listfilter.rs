@@ -0,0 +1,186 @@
+use std::{rc::Rc, cell::RefCell};
+
+use crate::configs::{IOElement, IOElementId, Subprogram, ConditionsConfigElement};
+use crate::configuration::{
+    language_pack_conastants::{
+        SORT_LIST_ORDER, FIELD_NAME, FIELD_TYPE, FIELD_SIGNAL, FIELD_ADDRESS, FIELD_DESCRIPTION
+    },
+    GLOBAL_CONFIG
+};
+use crate::tsv::{io_element_row, subprogram_row, condition_row};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortColumn {
+    ListOrder,
+    Name,
+    Description,
+    Type,
+    Signal,
+    Address,
+}
+
+pub static IO_SORT_COLUMNS: &[SortColumn] = &[
+    SortColumn::ListOrder,
+    SortColumn::Name,
+    SortColumn::Type,
+    SortColumn::Signal,
+    SortColumn::Address,
+];
+
+pub static SUBPROGRAM_SORT_COLUMNS: &[SortColumn] = &[
+    SortColumn::ListOrder,
+    SortColumn::Address,
+    SortColumn::Name,
+    SortColumn::Type,
+];
+
+pub static CONDITION_SORT_COLUMNS: &[SortColumn] = &[
+    SortColumn::ListOrder,
+    SortColumn::Description,
+    SortColumn::Address,
+];
+
+#[derive(Debug, Clone)]
+pub enum FilterMessage {
+    QueryChanged(String),
+    SortSelected(SortColumn),
+    // IOElementId(0) matches no element and turns the usages filter off
+    UsagesSelected(IOElementId),
+    Clear,
+}
+
+// Narrows and orders a list screen without touching the order of the model
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListFilter {
+    pub query: String,
+    pub sort: SortColumn,
+    pub usages_of: IOElementId,
+}
+
+impl std::fmt::Display for SortColumn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+
+        let field = match self {
+            SortColumn::ListOrder => SORT_LIST_ORDER,
+            SortColumn::Name => FIELD_NAME,
+            SortColumn::Description => FIELD_DESCRIPTION,
+            SortColumn::Type => FIELD_TYPE,
+            SortColumn::Signal => FIELD_SIGNAL,
+            SortColumn::Address => FIELD_ADDRESS,
+        };
+
+        write!(f, "{}", config.get_field(field).to_string())
+    }
+}
+
+impl ListFilter {
+    pub fn new() -> Self {
+        ListFilter {
+            query: String::new(),
+            sort: SortColumn::ListOrder,
+            usages_of: IOElementId(0),
+        }
+    }
+
+    pub fn update(&mut self, message: FilterMessage) {
+        match message {
+            FilterMessage::QueryChanged(query) => self.query = query,
+            FilterMessage::SortSelected(sort) => self.sort = sort,
+            FilterMessage::UsagesSelected(element) => self.usages_of = element,
+            FilterMessage::Clear => *self = ListFilter::new(),
+        }
+    }
+
+    // Every word of the query has to appear somewhere in the row
+    fn matches(&self, row: &str) -> bool {
+        let row = row.to_lowercase();
+
+        self.query.to_lowercase()
+            .split_whitespace()
+            .all(|word| row.contains(word))
+    }
+
+    fn uses_element(&self, uses: impl Fn(usize) -> bool) -> bool {
+        0 == self.usages_of.0 || uses(self.usages_of.0)
+    }
+
+    pub fn io_elements_order(&self, elements: &Vec<Rc<RefCell<IOElement>>>) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..elements.len())
+            .filter(|i| self.matches(io_element_row(&elements[*i].borrow()).as_str()))
+            .collect();
+
+        order.sort_by(|a, b| {
+            let (name_a, type_a, signal_a, address_a) = elements[*a].borrow().get_data();
+            let (name_b, type_b, signal_b, address_b) = elements[*b].borrow().get_data();
+
+            match self.sort {
+                SortColumn::Name => name_a.to_lowercase().cmp(&name_b.to_lowercase()),
+                SortColumn::Type => type_a.to_string().cmp(&type_b.to_string()),
+                SortColumn::Signal => signal_a.to_string().cmp(&signal_b.to_string()),
+                SortColumn::Address => address_a.cmp(&address_b),
+                _ => a.cmp(b),
+            }
+        });
+
+        order
+    }
+
+    pub fn subprograms_order(&self, subprograms: &Vec<Rc<RefCell<Subprogram>>>) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..subprograms.len())
+            .filter(|i| {
+                let subprogram = subprograms[*i].borrow();
+
+                self.matches(subprogram_row(&subprogram).as_str()) &&
+                    self.uses_element(|uid| subprogram.uses_io_element(uid))
+            })
+            .collect();
+
+        order.sort_by(|a, b| {
+            let (address_a, name_a, type_a, _) = subprograms[*a].borrow().get_data();
+            let (address_b, name_b, type_b, _) = subprograms[*b].borrow().get_data();
+
+            match self.sort {
+                SortColumn::Name => name_a.to_lowercase().cmp(&name_b.to_lowercase()),
+                SortColumn::Type => type_a.to_string().cmp(&type_b.to_string()),
+                SortColumn::Address => address_a.cmp(&address_b),
+                _ => a.cmp(b),
+            }
+        });
+
+        order
+    }
+
+    pub fn conditions_order(
+        &self, conditions: &Vec<Rc<RefCell<ConditionsConfigElement>>>
+    ) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..conditions.len())
+            .filter(|i| {
+                let condition = conditions[*i].borrow();
+
+                self.matches(condition_row(&condition).as_str()) &&
+                    self.uses_element(|uid| condition.uses_io_element(uid))
+            })
+            .collect();
+
+        order.sort_by(|a, b| {
+            let (condition_a, condition_b) = (conditions[*a].borrow(), conditions[*b].borrow());
+            let (description_a, ..) = condition_a.get_data();
+            let (description_b, ..) = condition_b.get_data();
+
+            match self.sort {
+                SortColumn::Description => {
+                    description_a.to_lowercase().cmp(&description_b.to_lowercase())
+                },
+                SortColumn::Address => {
+                    condition_a.get_transition_address().cmp(&condition_b.get_transition_address())
+                },
+                _ => a.cmp(b),
+            }
+        });
+
+        order
+    }
+}
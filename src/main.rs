@@ -1,19 +1,40 @@
 use std::{rc::Rc, cell::RefCell};
 
 use configs::{IOConfig, IO_CONFIG, SUBPROGRAMS_CONFIG, SubprogramConfig, SubprogramConfigStetes, CONDTIONS_CONFIG, CondtionsConfig, CondtionsConfigStetes, IOElement};
-use generator::generate_tables;
 use iced::{
     button, executor, Align, Application, Button, Clipboard, Column, Command,
-    Container, Element, Length, Settings, Text, scrollable, Row, Space
+    Container, Element, Length, Settings, Text, scrollable, Row,
+    Subscription, keyboard, pick_list, text_input, Color
 };
+use iced_native::{subscription, event, window, Event};
 
 mod configuration;
 use configuration:: {
-    Config, language_pack_conastants::{BUTTON_NEXT, BUTTON_BACK}, GLOBAL_CONFIG,
-    style_config::{self, FONT_SIZE, DEFAULT_PADDING}, FrameTypes, SignalTypes
+    Config, GLOBAL_CONFIG,
+    language_pack_conastants::{
+        BUTTON_BACK, BUTTON_SHORTCUTS, SHORTCUT_ADD_NEW, SHORTCUT_DELETE, SHORTCUT_SAVE_PROJECT,
+        SHORTCUT_MOVE_FOCUS, SHORTCUT_CYCLE_STATE, SHORTCUT_GENERATE, SHORTCUT_UNDO,
+        SHORTCUT_REDO, SHORTCUT_BACK, SHORTCUT_CHEAT_SHEET, SHORTCUT_COPY,
+        SHORTCUT_COPY_ALL, SHORTCUT_PASTE, SETTINGS_SAVE_FAILED, SETTINGS_EXIT_AGAIN
+    },
+    find_language_packs, check_language_pack, check_default_language_pack,
+    get_language_pack_dirs,
+    style_config::{self, DEFAULT_PADDING, DEFAULT_SPACING, NAVIGATION_WIDTH},
+    FrameTypes, SignalTypes
 };
 use subprogramview::SubprogramDescriptionEditView;
-use view::{PresetViewMessage, PresetViews};
+use view::{PresetViewMessage, PresetViews, Shortcut};
+use history::History;
+use matrixview::ConditionsMatrixView;
+use csvimport::{load_csv, map_rows, ColumnMapping, ImportField};
+use tsv::io_element_row;
+use filterview::FilterView;
+use previewview::PreviewView;
+use validationview::ValidationView;
+use simulationview::SimulationView;
+use project::load_project;
+use projectview::ProjectMessage;
+use listfilter::{IO_SORT_COLUMNS, SUBPROGRAM_SORT_COLUMNS, CONDITION_SORT_COLUMNS};
 
 mod view;
 mod ioconfigview;
@@ -21,11 +42,40 @@ mod subprogramview;
 mod conditionsview;
 mod configs;
 mod generator;
+mod history;
+mod matrixview;
+mod settingsview;
+mod tsv;
+mod csvimport;
+mod bulkcreate;
+mod listfilter;
+mod filterview;
+mod previewview;
+mod hardware;
+mod timer;
+mod counter;
+mod analog;
+mod draglist;
+mod project;
+mod projectview;
+mod encoding;
+mod validationview;
+mod simulationview;
 
 #[derive(Debug, Clone)]
 pub enum Message {
     BackPresset,
-    NextPresset,
+    SelectPresset(usize),
+    SelectBreadcrumb(usize),
+    Undo,
+    Redo,
+    Generate,
+    SaveProject,
+    ToggleCheatSheet,
+    Copy(bool),
+    Paste,
+    WindowResized(u32, u32),
+    Exit,
     PresetViewMessage(PresetViewMessage),
 }
 
@@ -34,8 +84,15 @@ pub struct Generator {
     active_preset: usize,
     scroll: scrollable::State,
     presets: Vec<PresetViews>,
-    next_preset: button::State,
     back_preset: button::State,
+    navigation: Vec<button::State>,
+    breadcrumbs: Vec<button::State>,
+    shortcuts_button: button::State,
+    cheat_sheet: bool,
+    history: History,
+    exit: bool,
+    // Settings that could not be saved on the first close attempt
+    exit_error: Option<String>,
 }
 
 impl Application for Generator {
@@ -52,21 +109,40 @@ impl Application for Generator {
                     PresetViews::EntryView {
                         create_new_button: button::State::new(),
                         load_table_button: button::State::new(),
+                        settings_button: button::State::new(),
+                        settings_view: None,
+                        project_view: None,
                     },
                     PresetViews::IOConfigView {
                         scroll: scrollable::State::new(),
                         create_new_button: button::State::new(),
-                        elements: vec![]
+                        elements: vec![],
+                        delete_view: None,
+                        import_button: button::State::new(),
+                        import_view: None,
+                        hardware_button: button::State::new(),
+                        hardware_view: None,
+                        assign_button: button::State::new(),
+                        assign_view: None,
+                        bulk_button: button::State::new(),
+                        bulk_view: None,
+                        filter: FilterView::new(IO_SORT_COLUMNS, false),
+                        focus: 0
                     },
                     PresetViews::SubprogramConfigView {
                         scroll: scrollable::State::new(),
                         create_new_button: button::State::new(),
+                        description_button: button::State::new(),
                         subprograms: vec![],
                         state: SubprogramConfigStetes::SubprogramConfigState,
                         subprogramsteps: vec![],
                         conditions: vec![],
                         conditions_type: FrameTypes::State,
-                        subrogramedit_view: None
+                        subrogramedit_view: None,
+                        matrix: ConditionsMatrixView::new(),
+                        filter: FilterView::new(SUBPROGRAM_SORT_COLUMNS, true),
+                        focus: 0,
+                        drag: draglist::State::new()
                     },
                     PresetViews::ConditionsConfigView {
                         scroll: scrollable::State::new(),
@@ -74,14 +150,38 @@ impl Application for Generator {
                         state: configs::CondtionsConfigStetes::CondtionsConfigState,
                         conditionsview: vec![],
                         frame_type: FrameTypes::State,
-                        ioconditionsview: vec![]
+                        ioconditionsview: vec![],
+                        matrix: ConditionsMatrixView::new(),
+                        filter: FilterView::new(CONDITION_SORT_COLUMNS, true),
+                        focus: 0,
+                        drag: draglist::State::new()
+                    },
+                    PresetViews::ValidationView {
+                        validation: ValidationView::new(),
+                    },
+                    PresetViews::SimulationView {
+                        simulation: SimulationView::new(),
+                    },
+                    PresetViews::PreviewView {
+                        preview: PreviewView::new(),
                     },
                     PresetViews::GenereteTableView {
                         generete_table: button::State::new(),
+                        document_language_list: pick_list::State::default(),
+                        project_input: text_input::State::new(),
+                        save_project: button::State::new(),
+                        result: None,
+                        project_result: None,
                     }
                 ],
-                next_preset: button::State::new(),
                 back_preset: button::State::new(),
+                navigation: vec![],
+                breadcrumbs: vec![],
+                shortcuts_button: button::State::new(),
+                cheat_sheet: false,
+                history: History::new(),
+                exit: false,
+                exit_error: None,
             },
             Command::none(),
         )
@@ -94,8 +194,22 @@ impl Application for Generator {
     fn update(
         &mut self,
         message: Message,
-        _clipboard: &mut Clipboard,
+        clipboard: &mut Clipboard,
     ) -> Command<Message> {
+        // The cheat sheet covers the lists, keys meant for them are dropped while it is open
+        if self.cheat_sheet {
+            match &message {
+                Message::PresetViewMessage(PresetViewMessage::Shortcut(_)) |
+                Message::Copy(_) | Message::Paste | Message::Undo | Message::Redo |
+                Message::Generate | Message::SaveProject => return Command::none(),
+                Message::BackPresset => {
+                    self.cheat_sheet = false;
+                    return Command::none()
+                },
+                _ => {}
+            }
+        }
+
         match message {
             Message::PresetViewMessage(preset_message) => {
                 match preset_message {
@@ -104,52 +218,111 @@ impl Application for Generator {
                             self.active_preset += 1
                         }
                     },
+                    // The opened project replaces the model, the edits before it can not be undone
+                    PresetViewMessage::ProjectMessage(ProjectMessage::Open(path)) => {
+                        match load_project(path.as_str()) {
+                            Ok(()) => {
+                                self.history = History::new();
+                                for preset in &mut self.presets {
+                                    preset.select_breadcrumb(0);
+                                    preset.reload();
+                                }
+                                self.active_preset = 1;
+                            },
+                            Err(error) => self.presets[self.active_preset].update(
+                                PresetViewMessage::ProjectMessage(
+                                    ProjectMessage::OpenFailed(error.to_string())
+                                )
+                            )
+                        }
+                    },
                     _ => {
+                        let preset_message = self.presets[self.active_preset]
+                            .resolve_message(preset_message);
+
+                        self.history.record(&preset_message);
                         self.presets[self.active_preset].update(preset_message)
                     }
                 }
             },
-            Message::NextPresset => {
-                match &mut self.presets[self.active_preset] {
-                    PresetViews::SubprogramConfigView {state, ..} => {
-                        match state {
-                            SubprogramConfigStetes::SubprogramEditState => {
-                                *state = SubprogramConfigStetes::SubprogramEditDescription;
-                            },
-                            SubprogramConfigStetes::SubprogramEditDescription => {
-                                *state = SubprogramConfigStetes::SubprogramConfigState
-                            },
-                            SubprogramConfigStetes::SubprogramStepConditonsPick => {
-                                *state = SubprogramConfigStetes::SubprogramEditState
-                            },
-                            SubprogramConfigStetes::SubprogramConfigState => {
-                                if (self.active_preset + 1) < self.presets.len() {
-                                    self.active_preset += 1
-                                }
-                            },
-                            _ => {
-                            }
-                        }
-                    },
-                    PresetViews::ConditionsConfigView {state, ..} => {
-                        match state {
-                            CondtionsConfigStetes::IOConditonsPick => {
-                                *state = CondtionsConfigStetes::CondtionsConfigState;
-                            },
-                            _ => {
-                                if (self.active_preset + 1) < self.presets.len() {
-                                    self.active_preset += 1
-                                }
-                            }
-                        }
+            Message::WindowResized(width, height) => {
+                unsafe {
+                    &GLOBAL_CONFIG
+                }.as_ref().unwrap().borrow_mut().set_window_size((width, height));
+            },
+            // A failed save keeps the window open once so the user sees why
+            Message::Exit => {
+                let config = unsafe {
+                    &GLOBAL_CONFIG
+                }.as_ref().unwrap().borrow();
+
+                match config.save_settings() {
+                    Err(error) if self.exit_error.is_none() => {
+                        self.exit_error = Some(format!(
+                            "{}: {}. {}", config.get_field(SETTINGS_SAVE_FAILED), error,
+                            config.get_field(SETTINGS_EXIT_AGAIN)
+                        ));
                     },
-                    _ => {
-                        if (self.active_preset + 1) < self.presets.len() {
-                            self.active_preset += 1
-                        }
+                    _ => self.exit = true
+                }
+            },
+            Message::Undo | Message::Redo => {
+                let changed = match message {
+                    Message::Undo => self.history.undo(),
+                    _ => self.history.redo(),
+                };
+
+                if changed {
+                    for preset in &mut self.presets {
+                        preset.reload();
                     }
                 }
             },
+            // Opens the generate screen so the result is shown next to its settings
+            Message::Generate => {
+                let generate_preset = self.presets.iter().position(|preset| {
+                    matches!(preset, PresetViews::GenereteTableView {..})
+                });
+
+                if let Some(preset) = generate_preset {
+                    self.active_preset = preset;
+                    self.presets[preset].update(PresetViewMessage::GenereteTable);
+                }
+            },
+            // The project file is picked on the generate screen, the result is shown there
+            Message::SaveProject => {
+                let generate_preset = self.presets.iter().position(|preset| {
+                    matches!(preset, PresetViews::GenereteTableView {..})
+                });
+
+                if let Some(preset) = generate_preset {
+                    self.active_preset = preset;
+                    self.presets[preset].update(PresetViewMessage::SaveProject);
+                }
+            },
+            Message::ToggleCheatSheet => {
+                self.cheat_sheet = !self.cheat_sheet;
+            },
+            Message::Copy(all) => {
+                if let Some(text) = self.presets[self.active_preset].get_tsv(all) {
+                    clipboard.write(text);
+                }
+            },
+            Message::Paste => {
+                if let Some(text) = clipboard.read() {
+                    return self.update(
+                        Message::PresetViewMessage(PresetViewMessage::Paste(text)), clipboard
+                    )
+                }
+            },
+            Message::SelectPresset(preset) => {
+                if preset < self.presets.len() {
+                    self.active_preset = preset
+                }
+            },
+            Message::SelectBreadcrumb(level) => {
+                self.presets[self.active_preset].select_breadcrumb(level)
+            },
             Message::BackPresset => {
                 match &mut self.presets[self.active_preset] {
                     PresetViews::SubprogramConfigView {state, ..} => {
@@ -164,7 +337,7 @@ impl Application for Generator {
                                 *state = SubprogramConfigStetes::SubprogramEditState
                             },
                             _ => {
-                                self.active_preset -= 1;
+                                self.active_preset = self.active_preset.saturating_sub(1);
                             }
                         }
                     },
@@ -174,13 +347,13 @@ impl Application for Generator {
                                 *state = CondtionsConfigStetes::CondtionsConfigState;
                             },
                             _ => {
-                                self.active_preset -= 1
+                                self.active_preset = self.active_preset.saturating_sub(1);
                             }
                         }
                     },
 
                     _ => {
-                        self.active_preset -= 1;
+                        self.active_preset = self.active_preset.saturating_sub(1);
                     }
                 }
             },
@@ -188,60 +361,369 @@ impl Application for Generator {
         Command::none()
     }
 
+    fn subscription(&self) -> Subscription<Message> {
+        subscription::events_with(|event, status| {
+            match event {
+                Event::Window(window::Event::Resized {width, height}) => {
+                    Some(Message::WindowResized(width, height))
+                },
+                Event::Window(window::Event::CloseRequested) => Some(Message::Exit),
+                Event::Keyboard(keyboard::Event::KeyPressed {key_code, modifiers})
+                    if modifiers.is_command_pressed() => {
+                    match key_code {
+                        keyboard::KeyCode::Z if modifiers.shift => Some(Message::Redo),
+                        keyboard::KeyCode::Z => Some(Message::Undo),
+                        keyboard::KeyCode::Y => Some(Message::Redo),
+                        keyboard::KeyCode::G => Some(Message::Generate),
+                        keyboard::KeyCode::S => Some(Message::SaveProject),
+                        // Text inputs handle the clipboard themselves while focused
+                        keyboard::KeyCode::C if status == event::Status::Ignored => {
+                            Some(Message::Copy(modifiers.shift))
+                        },
+                        keyboard::KeyCode::V if status == event::Status::Ignored => {
+                            Some(Message::Paste)
+                        },
+                        keyboard::KeyCode::N => Some(Message::PresetViewMessage(
+                            PresetViewMessage::Shortcut(Shortcut::AddNew)
+                        )),
+                        _ => None
+                    }
+                },
+                Event::Keyboard(keyboard::Event::KeyPressed {key_code: keyboard::KeyCode::F1, ..}) => {
+                    Some(Message::ToggleCheatSheet)
+                },
+                // Keys captured by a focused text input are not forwarded
+                Event::Keyboard(keyboard::Event::KeyPressed {key_code, ..})
+                    if status == event::Status::Ignored => {
+                    let shortcut = match key_code {
+                        keyboard::KeyCode::Up => Shortcut::MoveFocus(-1, 0),
+                        keyboard::KeyCode::Down => Shortcut::MoveFocus(1, 0),
+                        keyboard::KeyCode::Left => Shortcut::MoveFocus(0, -1),
+                        keyboard::KeyCode::Right => Shortcut::MoveFocus(0, 1),
+                        keyboard::KeyCode::Space => Shortcut::CycleFocused,
+                        keyboard::KeyCode::Delete => Shortcut::DeleteFocused,
+                        keyboard::KeyCode::Escape => return Some(Message::BackPresset),
+                        _ => return None
+                    };
+
+                    Some(Message::PresetViewMessage(PresetViewMessage::Shortcut(shortcut)))
+                },
+                _ => None
+            }
+        })
+    }
+
+    fn should_exit(&self) -> bool {
+        self.exit
+    }
+
+    fn scale_factor(&self) -> f64 {
+        let ui_scale = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow().get_ui_scale();
+
+        ui_scale.0 as f64 / 100.0
+    }
+
+    fn background_color(&self) -> Color {
+        style_config::palette().background
+    }
+
     fn view(&mut self) -> Element<Message> {
         let config = unsafe {
             &GLOBAL_CONFIG
-        }.as_ref().unwrap();
+        }.as_ref().unwrap().borrow();
         let active_preset = self.active_preset;
-        let mut content = Column::new();
-
-        content = content.push(Container::new(self.presets[active_preset]
-                                .view().map(Message::PresetViewMessage))
-                .width(Length::Fill));
-
-        let controls = Row::new()
-            .align_items(Align::Center)
-            .padding(DEFAULT_PADDING)
-            .push(Button::new(&mut self.back_preset,
-                              Text::new(config.get_field(BUTTON_BACK)
-                                        .to_string().as_str()).size(FONT_SIZE))
-                  .on_press(Message::BackPresset)
-                  .style(style_config::Button::Secondary))
-            .push(Space::with_width(Length::Fill))
-            .push(Button::new(&mut self.next_preset,
-                              Text::new(config.get_field(BUTTON_NEXT)
-                                        .to_string().as_str()).size(FONT_SIZE)) 
-                  .on_press(Message::NextPresset)
-                  .style(style_config::Button::Primary));
-
-        if self.active_preset > 0 {
-            content = content
-                .push(Space::with_height(Length::Fill))
-                .push(controls.align_items(Align::End));
+
+        let sections: Vec<(usize, &str)> = self.presets.iter().enumerate()
+            .filter_map(|(i, preset)| {
+                preset.get_navigation_field().map(|field| (i, field))
+            })
+            .collect();
+
+        let mut breadcrumbs = self.presets[active_preset].get_breadcrumbs();
+
+        if let Some(field) = self.presets[active_preset].get_navigation_field() {
+            breadcrumbs.insert(0, config.get_field(field).to_string());
+        }
+
+        if self.cheat_sheet {
+            return Container::new(cheat_sheet_view(&mut self.shortcuts_button))
+                .height(Length::Fill)
+                .width(Length::Fill)
+                .center_x()
+                .center_y()
+                .style(style_config::Root)
+                .into()
         }
 
-        Container::new(content)
+        let mut preset = self.presets[active_preset].view().map(Message::PresetViewMessage);
+
+        if let Some(error) = &self.exit_error {
+            preset = Column::new()
+                .width(Length::Fill)
+                .align_items(Align::Center)
+                .push(Text::new(error.as_str()).size(16).color([0.8, 0.4, 0.0]))
+                .push(preset)
+                .into();
+        }
+
+        if 0 == active_preset {
+            return Container::new(preset)
+                .height(Length::Fill)
+                .width(Length::Fill)
+                .center_y()
+                .style(style_config::Root)
+                .into()
+        }
+
+        self.navigation.resize_with(sections.len(), button::State::new);
+
+        let navigation = sections.into_iter().zip(self.navigation.iter_mut())
+            .fold(
+                Column::new()
+                    .spacing(DEFAULT_SPACING)
+                    .padding(DEFAULT_PADDING)
+                    .width(Length::Units(NAVIGATION_WIDTH)),
+                |column, ((i, field), state)| {
+                    column.push(Button::new(state,
+                                            Text::new(config.get_field(field).to_string()))
+                        .width(Length::Fill)
+                        .on_press(Message::SelectPresset(i))
+                        .style(if i == active_preset {
+                            style_config::Button::Primary
+                        } else {
+                            style_config::Button::Secondary
+                        }))
+                }
+            );
+
+        self.breadcrumbs.resize_with(breadcrumbs.len(), button::State::new);
+
+        let last = breadcrumbs.len().saturating_sub(1);
+        let path = breadcrumbs.into_iter().zip(self.breadcrumbs.iter_mut())
+            .enumerate()
+            .fold(
+                Row::new()
+                    .spacing(10)
+                    .align_items(Align::Center)
+                    .push(Button::new(&mut self.back_preset,
+                                      Text::new(config.get_field(BUTTON_BACK).to_string()))
+                          .on_press(Message::BackPresset)
+                          .style(style_config::Button::Secondary)),
+                |row, (level, (breadcrumb, state))| {
+                    let row = row.push(Text::new(">"));
+
+                    if level == last {
+                        row.push(Text::new(breadcrumb))
+                    } else {
+                        row.push(Button::new(state, Text::new(breadcrumb))
+                            .on_press(Message::SelectBreadcrumb(level))
+                            .style(style_config::Button::Secondary))
+                    }
+                }
+            );
+
+        let navigation = navigation
+            .push(Button::new(&mut self.shortcuts_button,
+                              Text::new(format!("{} (F1)", config.get_field(BUTTON_SHORTCUTS))))
+                  .width(Length::Fill)
+                  .on_press(Message::ToggleCheatSheet)
+                  .style(style_config::Button::Secondary));
+
+        let content = Column::new()
+            .width(Length::Fill)
+            .push(Container::new(path).padding(DEFAULT_PADDING))
+            .push(Container::new(preset).width(Length::Fill));
+
+        Container::new(Row::new()
+                .push(navigation)
+                .push(content))
             .height(Length::Fill)
             .width(Length::Fill)
-            .center_y()
+            .style(style_config::Root)
             .into()
     }
 }
 
+static SHORTCUTS: &[(&str, &str)] = &[
+    ("Ctrl+N", SHORTCUT_ADD_NEW),
+    ("Delete", SHORTCUT_DELETE),
+    ("Up/Down/Left/Right", SHORTCUT_MOVE_FOCUS),
+    ("Space", SHORTCUT_CYCLE_STATE),
+    ("Ctrl+C", SHORTCUT_COPY),
+    ("Ctrl+Shift+C", SHORTCUT_COPY_ALL),
+    ("Ctrl+V", SHORTCUT_PASTE),
+    ("Ctrl+G", SHORTCUT_GENERATE),
+    ("Ctrl+S", SHORTCUT_SAVE_PROJECT),
+    ("Ctrl+Z", SHORTCUT_UNDO),
+    ("Ctrl+Y, Ctrl+Shift+Z", SHORTCUT_REDO),
+    ("Escape", SHORTCUT_BACK),
+    ("F1", SHORTCUT_CHEAT_SHEET),
+];
+
+fn cheat_sheet_view<'a>(close_button: &'a mut button::State) -> Element<'a, Message> {
+    let config = unsafe {
+        &GLOBAL_CONFIG
+    }.as_ref().unwrap().borrow();
+
+    let shortcuts = SHORTCUTS.iter().fold(
+        Column::new().spacing(10),
+        |column, (keys, field)| {
+            column.push(Row::new()
+                .spacing(DEFAULT_SPACING)
+                .push(Text::new(*keys).width(Length::Units(NAVIGATION_WIDTH)))
+                .push(Text::new(config.get_field(field).to_string())))
+        }
+    );
+
+    Column::new()
+        .spacing(DEFAULT_SPACING)
+        .padding(DEFAULT_PADDING)
+        .align_items(Align::Center)
+        .push(Text::new(config.get_field(BUTTON_SHORTCUTS).to_string()).size(30))
+        .push(shortcuts)
+        .push(Button::new(close_button,
+                          Text::new(config.get_field(BUTTON_BACK).to_string()))
+              .on_press(Message::ToggleCheatSheet)
+              .style(style_config::Button::Secondary))
+        .into()
+}
+
 fn init() {
+    let mut config = Config::new();
+
+    config.load_settings();
+
     unsafe {
-        GLOBAL_CONFIG = Some(Rc::new(Config::new()));
+        GLOBAL_CONFIG = Some(Rc::new(RefCell::new(config)));
         IO_CONFIG = Some(Rc::new(RefCell::new(IOConfig::new())));
         SUBPROGRAMS_CONFIG = Some(Rc::new(RefCell::new(SubprogramConfig::new())));
         CONDTIONS_CONFIG = Some(Rc::new(RefCell::new(CondtionsConfig::new())));
     }
 }
 
+// Prints the issues of the given language packs, the installed ones if none given
+fn check_language_packs(paths: &[String]) -> bool {
+    let mut paths = paths.to_vec();
+    let mut reports = vec![];
+
+    if paths.is_empty() {
+        reports.push(check_default_language_pack());
+        for dir in get_language_pack_dirs() {
+            paths.push(dir.to_string_lossy().to_string());
+        }
+    }
+
+    for path in paths {
+        for language_pack in find_language_packs(path.as_str()) {
+            reports.push(check_language_pack(language_pack.to_string_lossy().as_ref()));
+        }
+    }
+
+    let mut clean = true;
+
+    for report in reports {
+        if report.is_clean() {
+            println!("{}: OK", report.file_path);
+        } else {
+            println!("{}", report);
+            clean = false;
+        }
+    }
+
+    clean
+}
+
+// Maps a CSV IO list like the import dialog, valid rows are printed as tab separated text
+fn import_io_list(args: &[String]) -> bool {
+    let path = match args.first() {
+        Some(path) => path,
+        None => {
+            eprintln!("Usage: import-io <file.csv> [name=<column>] [type=<column>] [signal=<column>] [hw=<column>]");
+            return false
+        }
+    };
+
+    let table = match load_csv(path.as_str()) {
+        Ok(table) => table,
+        Err(error) => {
+            eprintln!("{}: {}", path, error);
+            return false
+        }
+    };
+
+    let mut mapping = ColumnMapping::guess(&table.headers);
+
+    for arg in &args[1..] {
+        let (field, column) = arg.split_once('=').unwrap_or((arg.as_str(), ""));
+        let field = match field {
+            "name" => ImportField::Name,
+            "type" => ImportField::FrameType,
+            "signal" => ImportField::SignalType,
+            "hw" => ImportField::HwAddress,
+            _ => {
+                eprintln!("Unknown field: {}", field);
+                return false
+            }
+        };
+
+        match table.find_column(column) {
+            Some(i) => mapping.set(field, Some(i)),
+            None => {
+                eprintln!("Unknown column: {}", column);
+                return false
+            }
+        }
+    }
+
+    let mut clean = true;
+
+    for row in map_rows(&table, &mapping, &vec![]) {
+        if row.is_valid() {
+            println!("{}", io_element_row(&row.to_element()));
+        } else {
+            let issues = row.issues.iter()
+                .map(|issue| issue.to_string())
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            eprintln!("{}:{}: {}", path, row.line, issues);
+            clean = false;
+        }
+    }
+
+    clean
+}
+
 fn main() -> iced::Result {
+    let args: Vec<String> = std::env::args().collect();
+
+    match args.get(1).map(String::as_str) {
+        Some("check-languages") => {
+            std::process::exit(if check_language_packs(&args[2..]) {0} else {1});
+        },
+        Some("import-io") => {
+            init();
+            std::process::exit(if import_io_list(&args[2..]) {0} else {1});
+        },
+        _ => ()
+    }
+
     init();
 
+    let window_size = unsafe {
+        &GLOBAL_CONFIG
+    }.as_ref().unwrap().borrow().get_window_size();
+
     Generator::run(Settings {
         antialiasing: true,
+        // Closing is handled to store the settings first
+        exit_on_close_request: false,
+        window: iced::window::Settings {
+            size: window_size,
+            ..iced::window::Settings::default()
+        },
         ..Settings::default()
     })
 }
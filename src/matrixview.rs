@@ -0,0 +1,236 @@
+use std::{rc::Rc, cell::RefCell};
+
+use iced::{
+    button, scrollable, Align, Button, Column, Element, HorizontalAlignment,
+    Length, Row, Scrollable, Space, Text
+};
+
+use crate::configuration:: {
+    language_pack_conastants::{
+        BUTTON_MATRIX_VIEW, BUTTON_LIST_VIEW, TABLE_CONTENT_SENSOR_STATES,
+        TABLE_CONTENT_CONTROL_STATES
+    },
+    style_config::{
+        self, DEFAULT_PADDING, DEFAULT_SPACING, MATRIX_CELL_WIDTH,
+        SUBPRORAM_DESCRIPTION_WIDTH
+    },
+    FrameTypes, GLOBAL_CONFIG
+};
+use crate::configs::{IO_CONFIG, IOElement, IOElementCoditions, IOElementId};
+use crate::generator::get_conditions_state;
+
+const MATRIX_CELL_SPACING: u16 = 2;
+
+#[derive(Debug, Clone)]
+pub enum MatrixMessage {
+    ToggleMatrix,
+    CellPressed(usize, IOElementId, FrameTypes),
+    MoveCursor(isize, isize),
+    CycleCursor,
+}
+
+#[derive(Debug)]
+pub struct ConditionsMatrixView {
+    toggle_button: button::State,
+    scroll: scrollable::State,
+    cells: Vec<Vec<button::State>>,
+    cursor: (usize, usize),
+    rows_count: usize,
+    active: bool,
+}
+
+// Columns are ordered the same way as in the generated sheets, except for internal flags
+// which are tested in the state group and set or reset in the control group
+fn get_columns() -> (Vec<Rc<RefCell<IOElement>>>, Vec<Rc<RefCell<IOElement>>>) {
+    let io_config = unsafe {
+        &IO_CONFIG
+    }.as_ref().unwrap();
+
+    (
+        io_config.borrow().get_elements_by_frame_type(FrameTypes::State),
+        io_config.borrow().get_elements_by_frame_type(FrameTypes::Control)
+    )
+}
+
+fn group_width(columns: usize) -> Length {
+    Length::Units((MATRIX_CELL_WIDTH + MATRIX_CELL_SPACING) * columns as u16)
+}
+
+impl<'a> ConditionsMatrixView {
+    pub fn new() -> Self {
+        ConditionsMatrixView {
+            toggle_button: button::State::new(),
+            scroll: scrollable::State::new(),
+            cells: vec![],
+            cursor: (0, 0),
+            rows_count: 0,
+            active: false,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn update(&mut self, message: MatrixMessage) {
+        match message {
+            MatrixMessage::ToggleMatrix => {
+                self.active = !self.active
+            },
+            MatrixMessage::MoveCursor(rows, columns) => {
+                let (state_columns, control_columns) = get_columns();
+                let columns_count = state_columns.len() + control_columns.len();
+                let (row, column) = self.cursor;
+
+                self.cursor = (
+                    (row as isize + rows).min(self.rows_count as isize - 1).max(0) as usize,
+                    (column as isize + columns).min(columns_count as isize - 1).max(0) as usize
+                );
+            },
+            _ => {}
+        }
+    }
+
+    pub fn get_cursor_cell(&self) -> Option<(usize, IOElementId, FrameTypes)> {
+        let (mut columns, mut control_columns) = get_columns();
+        let state_count = columns.len();
+        columns.append(&mut control_columns);
+
+        if !self.active || self.cursor.0 >= self.rows_count {
+            return None
+        }
+
+        columns.get(self.cursor.1).map(|element| (
+            self.cursor.0,
+            IOElementId(element.borrow().get_uid()),
+            if self.cursor.1 < state_count {FrameTypes::State} else {FrameTypes::Control}
+        ))
+    }
+
+    pub fn toggle_view(&'a mut self) -> Element<'a, MatrixMessage> {
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+
+        let label = if self.active {BUTTON_LIST_VIEW} else {BUTTON_MATRIX_VIEW};
+
+        Button::new(
+            &mut self.toggle_button,
+            Text::new(config.get_field(label).to_string())
+        ).style(style_config::Button::Secondary)
+            .on_press(MatrixMessage::ToggleMatrix)
+            .into()
+    }
+
+    // Each row is a label with its state and control conditions
+    pub fn view(
+        &'a mut self,
+        rows: Vec<(String, Vec<Rc<RefCell<IOElementCoditions>>>, Vec<Rc<RefCell<IOElementCoditions>>>)>
+    ) -> Element<'a, MatrixMessage> {
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+
+        let (state_columns, control_columns) = get_columns();
+        let columns_count = state_columns.len() + control_columns.len();
+
+        self.rows_count = rows.len();
+        self.cursor = (
+            self.cursor.0.min(rows.len().max(1) - 1),
+            self.cursor.1.min(columns_count.max(1) - 1)
+        );
+        self.cells.resize_with(rows.len(), Vec::new);
+        for cells in self.cells.iter_mut() {
+            cells.resize_with(columns_count, button::State::new);
+        }
+
+        let groups = Row::new()
+            .push(Space::with_width(Length::Units(SUBPRORAM_DESCRIPTION_WIDTH)))
+            .push(Text::new(config.get_field(TABLE_CONTENT_SENSOR_STATES).to_string())
+                .size(20)
+                .width(group_width(state_columns.len())))
+            .push(Text::new(config.get_field(TABLE_CONTENT_CONTROL_STATES).to_string())
+                .size(20)
+                .width(group_width(control_columns.len())));
+
+        let names = state_columns.iter().chain(control_columns.iter()).fold(
+            Row::new()
+                .spacing(MATRIX_CELL_SPACING)
+                .push(Space::with_width(Length::Units(SUBPRORAM_DESCRIPTION_WIDTH))),
+            |row, element| {
+                let (name, ..) = element.borrow().get_data();
+
+                row.push(Text::new(name)
+                    .size(16)
+                    .width(Length::Units(MATRIX_CELL_WIDTH))
+                    .horizontal_alignment(HorizontalAlignment::Center))
+            }
+        );
+
+        let cursor = self.cursor;
+        let state_count = state_columns.len();
+        let columns: Vec<Rc<RefCell<IOElement>>> = state_columns.into_iter()
+            .chain(control_columns.into_iter())
+            .collect();
+
+        let body = self.cells.iter_mut().zip(rows.into_iter()).enumerate().fold(
+            Column::new().spacing(MATRIX_CELL_SPACING),
+            |column, (i, (cells, (label, states, controls)))| {
+                let row = cells.iter_mut().enumerate().fold(
+                    Row::new()
+                        .spacing(MATRIX_CELL_SPACING)
+                        .align_items(Align::Center)
+                        .push(Text::new(label)
+                            .size(20)
+                            .width(Length::Units(SUBPRORAM_DESCRIPTION_WIDTH))),
+                    |row, (j, cell)| {
+                        let element = &columns[j];
+                        let content = get_conditions_state(
+                            if j < state_count {&states} else {&controls},
+                            element
+                        );
+
+                        row.push(Button::new(
+                                cell,
+                                Text::new(content)
+                                    .size(20)
+                                    .horizontal_alignment(HorizontalAlignment::Center)
+                            )
+                            .width(Length::Units(MATRIX_CELL_WIDTH))
+                            .style(if (i, j) == cursor {
+                                style_config::Button::CellSelected
+                            } else {
+                                style_config::Button::Cell
+                            })
+                            .on_press(MatrixMessage::CellPressed(
+                                i,
+                                IOElementId(element.borrow().get_uid()),
+                                if j < state_count {FrameTypes::State} else {FrameTypes::Control}
+                            )))
+                    }
+                );
+
+                column.push(row)
+            }
+        );
+
+        let toggle_button = Button::new(
+            &mut self.toggle_button,
+            Text::new(config.get_field(BUTTON_LIST_VIEW).to_string())
+        ).style(style_config::Button::Secondary)
+            .on_press(MatrixMessage::ToggleMatrix);
+
+        Column::new()
+            .width(Length::Fill)
+            .align_items(Align::Center)
+            .push(toggle_button)
+            .push(Scrollable::new(&mut self.scroll)
+                .spacing(MATRIX_CELL_SPACING)
+                .padding(DEFAULT_PADDING)
+                .push(groups)
+                .push(names)
+                .push(Space::with_height(Length::Units(DEFAULT_SPACING)))
+                .push(body))
+            .into()
+    }
+}
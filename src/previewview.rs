@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+
+use iced::{
+    button, Align, Button, Column, Container, Element, Length, Text, Row, Space,
+    Scrollable, scrollable
+};
+
+use crate::configuration::{
+    language_pack_conastants::{
+        TABLE_SHEET_CONDITIONS, TABLE_SHEET_SUBPROGRAMS, TABLE_SHEET_TIMERS, TABLE_SHEET_COUNTERS,
+        TABLE_SHEET_ANALOG, TABLE_SHEET_FLAGS
+    },
+    style_config::{self, DEFAULT_PADDING, DEFAULT_SPACING},
+    GLOBAL_CONFIG
+};
+use crate::generator::{
+    build_conditions_sheet, build_subprograms_sheet, build_timers_sheet,
+    build_counters_sheet, build_analog_sheet, build_flags_sheet, CellFormat, Sheet
+};
+
+const PREVIEW_FONT_SIZE: u16 = 12;
+const CHAR_WIDTH: u16 = 7;
+const LINE_HEIGHT: u16 = 14;
+const CELL_PADDING: u16 = 3;
+const MIN_COLUMN_WIDTH: u16 = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreviewSheet {
+    Conditions,
+    Subprograms,
+    Timers,
+    Counters,
+    Analog,
+    Flags,
+}
+
+static PREVIEW_SHEETS_ALL: &[PreviewSheet] = &[
+    PreviewSheet::Conditions,
+    PreviewSheet::Subprograms,
+    PreviewSheet::Timers,
+    PreviewSheet::Counters,
+    PreviewSheet::Analog,
+    PreviewSheet::Flags,
+];
+
+#[derive(Debug, Clone)]
+pub enum PreviewMessage {
+    SheetSelected(PreviewSheet),
+}
+
+// Rotated header cells are shown as stacked letters
+fn get_cell_text(text: String, format: CellFormat) -> String {
+    match format {
+        CellFormat::Rotated => text.chars().map(String::from).collect::<Vec<String>>().join("\n"),
+        _ => text
+    }
+}
+
+fn get_text_size(text: &str) -> (u16, u16) {
+    let lines = text.lines().count().max(1) as u16;
+    let chars = text.lines().map(|line| line.chars().count()).max().unwrap_or(0) as u16;
+
+    (chars * CHAR_WIDTH + 2 * CELL_PADDING, lines * LINE_HEIGHT + 2 * CELL_PADDING)
+}
+
+// Column widths and row heights fitting the content, merged cells widen their last
+// column or row when the spanned ones are too small
+fn get_layout(sheet: &Sheet) -> (Vec<u16>, Vec<u16>) {
+    let (rows, cols) = sheet.get_size();
+    let mut widths = vec![MIN_COLUMN_WIDTH; cols as usize];
+    let mut heights = vec![LINE_HEIGHT + 2 * CELL_PADDING; rows as usize];
+
+    for cell in sheet.get_cells() {
+        if cell.last_row < cell.first_row || cell.last_col < cell.first_col {
+            continue;
+        }
+
+        let text = get_cell_text(cell.value.to_string(), cell.format);
+        let (width, height) = get_text_size(text.as_str());
+        let cols = cell.first_col as usize..=cell.last_col as usize;
+        let rows = cell.first_row as usize..=cell.last_row as usize;
+        let spanned_width: u16 = widths[cols.clone()].iter().sum();
+        let spanned_height: u16 = heights[rows.clone()].iter().sum();
+
+        if width > spanned_width {
+            widths[*cols.end()] += width - spanned_width;
+        }
+        if height > spanned_height {
+            heights[*rows.end()] += height - spanned_height;
+        }
+    }
+
+    (widths, heights)
+}
+
+fn sheet_view<'a, Message: 'a>(sheet: &Sheet) -> Element<'a, Message> {
+    let (rows, cols) = sheet.get_size();
+    let (widths, heights) = get_layout(sheet);
+
+    // Cell covering the first column of a range in each of its rows, later
+    // writes replace earlier ones as in the XLSX output
+    let mut covering: HashMap<(u32, u16), usize> = HashMap::new();
+    for (i, cell) in sheet.get_cells().iter().enumerate() {
+        if cell.last_row < cell.first_row || cell.last_col < cell.first_col {
+            continue;
+        }
+
+        for row in cell.first_row..=cell.last_row {
+            covering.insert((row, cell.first_col), i);
+        }
+    }
+
+    (0..rows).fold(Column::new(), |column, row| {
+        let height = heights[row as usize];
+        let mut cells = Row::new().height(Length::Units(height));
+        let mut col = 0;
+
+        while col < cols {
+            match covering.get(&(row, col)) {
+                Some(i) => {
+                    let cell = &sheet.get_cells()[*i];
+                    let width: u16 = widths[col as usize..=cell.last_col as usize].iter().sum();
+                    let text = if row == cell.first_row {
+                        get_cell_text(cell.value.to_string(), cell.format)
+                    } else {
+                        String::new()
+                    };
+
+                    cells = cells.push(Container::new(Text::new(text).size(PREVIEW_FONT_SIZE))
+                        .width(Length::Units(width))
+                        .height(Length::Units(height))
+                        .padding(CELL_PADDING)
+                        .center_x()
+                        .center_y()
+                        .style(style_config::SheetCell {
+                            header: cell.format != CellFormat::Default
+                        }));
+                    col = cell.last_col + 1;
+                },
+                None => {
+                    cells = cells.push(Space::new(
+                        Length::Units(widths[col as usize]), Length::Units(height)
+                    ));
+                    col += 1;
+                }
+            }
+        }
+
+        column.push(cells)
+    }).into()
+}
+
+// Read-only rendering of the generated sheets, rebuilt from the model on every draw
+#[derive(Debug)]
+pub struct PreviewView {
+    sheet: PreviewSheet,
+    sheet_buttons: Vec<button::State>,
+    scroll: scrollable::State,
+}
+
+impl<'a> PreviewView {
+    pub fn new() -> Self {
+        PreviewView {
+            sheet: PreviewSheet::Conditions,
+            sheet_buttons: PREVIEW_SHEETS_ALL.iter().map(|_| button::State::new()).collect(),
+            scroll: scrollable::State::new(),
+        }
+    }
+
+    pub fn update(&mut self, message: PreviewMessage) {
+        match message {
+            PreviewMessage::SheetSelected(sheet) => self.sheet = sheet
+        }
+    }
+
+    pub fn view(&'a mut self) -> Element<'a, PreviewMessage> {
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+
+        let active_sheet = self.sheet;
+        let sheet_buttons = PREVIEW_SHEETS_ALL.iter()
+            .zip(self.sheet_buttons.iter_mut())
+            .fold(Row::new().spacing(DEFAULT_SPACING), |row, (sheet, state)| {
+                let title = match sheet {
+                    PreviewSheet::Conditions => TABLE_SHEET_CONDITIONS,
+                    PreviewSheet::Subprograms => TABLE_SHEET_SUBPROGRAMS,
+                    PreviewSheet::Timers => TABLE_SHEET_TIMERS,
+                    PreviewSheet::Counters => TABLE_SHEET_COUNTERS,
+                    PreviewSheet::Analog => TABLE_SHEET_ANALOG,
+                    PreviewSheet::Flags => TABLE_SHEET_FLAGS,
+                };
+
+                row.push(Button::new(
+                    state, Text::new(config.get_document_field(title))
+                ).style(if *sheet == active_sheet {
+                    style_config::Button::Primary
+                } else {
+                    style_config::Button::Secondary
+                }).on_press(PreviewMessage::SheetSelected(*sheet)))
+            });
+
+        let sheet = match self.sheet {
+            PreviewSheet::Conditions => build_conditions_sheet(),
+            PreviewSheet::Subprograms => build_subprograms_sheet(),
+            PreviewSheet::Timers => build_timers_sheet(),
+            PreviewSheet::Counters => build_counters_sheet(),
+            PreviewSheet::Analog => build_analog_sheet(),
+            PreviewSheet::Flags => build_flags_sheet(),
+        };
+
+        Column::new()
+            .width(Length::Fill)
+            .align_items(Align::Center)
+            .spacing(DEFAULT_SPACING)
+            .push(sheet_buttons)
+            .push(Scrollable::new(&mut self.scroll)
+                .width(Length::Fill)
+                .padding(DEFAULT_PADDING)
+                .push(sheet_view(&sheet)))
+            .into()
+    }
+}
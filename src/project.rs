@@ -0,0 +1,452 @@
+use std::{fs, rc::Rc, cell::RefCell};
+
+use json::{self, JsonValue};
+
+use crate::configs::{
+    IOConfig, IOConfigMessage, IOElement, IOElementMessage, IOElementId, IOElementCoditions,
+    IOElementCoditionsMessage, SubprogramConfig, SubprogramConfigMessage, SubprogramMessage,
+    SubprogramStepMessage, CondtionsConfig, CondtionsConfigMessage,
+    ConditionsConfigElementMessage, TransitionTarget, IO_CONFIG, SUBPROGRAMS_CONFIG,
+    CONDTIONS_CONFIG
+};
+use crate::configuration::{
+    FrameTypes, SignalTypes, ElementKind, Operators, SubprogramTypes, IOElementStates,
+    GLOBAL_CONFIG
+};
+use crate::hardware::{HwLayoutMessage, HwModuleMessage};
+use crate::timer::TIMER_MODES_ALL;
+use crate::counter::COUNTER_MODES_ALL;
+
+static FRAME_TYPES_ALL: &[FrameTypes] = &[
+    FrameTypes::State,
+    FrameTypes::Control,
+    FrameTypes::Flag,
+];
+
+static SIGNAL_TYPES_ALL: &[SignalTypes] = &[
+    SignalTypes::Input,
+    SignalTypes::Output,
+];
+
+static ELEMENT_KINDS_ALL: &[ElementKind] = &[
+    ElementKind::Discrete,
+    ElementKind::Timer,
+    ElementKind::Counter,
+    ElementKind::Analog,
+];
+
+static OPERATORS_ALL: &[Operators] = &[
+    Operators::AND,
+    Operators::OR,
+];
+
+static SUBPROGRAM_TYPES_ALL: &[SubprogramTypes] = &[
+    SubprogramTypes::Dflt,
+    SubprogramTypes::Critical,
+    SubprogramTypes::Blocked,
+];
+
+static IO_STATES_ALL: &[IOElementStates] = &[
+    IOElementStates::Active,
+    IOElementStates::Inactive,
+    IOElementStates::Any,
+];
+
+// Values are stored by identifier so a project opens with any UI language
+fn to_name<T: std::fmt::Debug>(value: T) -> JsonValue {
+    format!("{:?}", value).into()
+}
+
+fn get_text(value: &JsonValue) -> String {
+    value.as_str().unwrap_or_default().to_string()
+}
+
+fn from_name<T: Copy + std::fmt::Debug>(values: &[T], value: &JsonValue) -> Option<T> {
+    let value = value.as_str()?;
+
+    values.iter().copied().find(|item| format!("{:?}", item) == value)
+}
+
+// IO elements are referenced by their position in the IO list
+fn io_conditions_to_json(
+    conditions: Vec<Rc<RefCell<IOElementCoditions>>>, elements: &Vec<Rc<RefCell<IOElement>>>
+) -> JsonValue {
+    conditions.iter()
+        .map(|condition| {
+            let condition = condition.borrow();
+            let (_, state, frame_type) = condition.get_data();
+            let element = condition.get_element_uid().and_then(|uid| {
+                elements.iter().position(|element| element.borrow().get_uid() == uid)
+            });
+
+            json::object! {
+                element: element,
+                state: to_name(state),
+                frame_type: to_name(frame_type)
+            }
+        })
+        .collect::<Vec<JsonValue>>()
+        .into()
+}
+
+fn io_conditions_from_json(
+    conditions: &JsonValue, elements: &Vec<Rc<RefCell<IOElement>>>
+) -> Vec<Rc<RefCell<IOElementCoditions>>> {
+    conditions.members()
+        .filter_map(|condition| {
+            let frame_type = from_name(FRAME_TYPES_ALL, &condition["frame_type"])?;
+            let mut io_condition = IOElementCoditions::new(frame_type);
+
+            if let Some(element) = condition["element"].as_usize()
+                .and_then(|i| elements.get(i)) {
+                io_condition.update(IOElementCoditionsMessage::IOElementSelected(
+                    IOElementId(element.borrow().get_uid())
+                ));
+            }
+            if let Some(state) = from_name(IO_STATES_ALL, &condition["state"]) {
+                io_condition.update(IOElementCoditionsMessage::StateChanged(state));
+            }
+
+            Some(Rc::new(RefCell::new(io_condition)))
+        })
+        .collect()
+}
+
+fn io_config_to_json(io_config: &IOConfig) -> (JsonValue, JsonValue) {
+    let hardware: Vec<JsonValue> = io_config.get_hardware().get_modules().iter()
+        .map(|module| json::object! {
+            rack: module.rack,
+            slot: module.slot,
+            signal_type: to_name(module.signal_type),
+            channels: module.channels
+        })
+        .collect();
+
+    let elements: Vec<JsonValue> = io_config.get_all_elelments().iter()
+        .map(|element| {
+            let element = element.borrow();
+            let (name, frame_type, signal_type, hw_address) = element.get_data();
+            let (timer, counter, analog) =
+                (element.get_timer(), element.get_counter(), element.get_analog());
+
+            json::object! {
+                name: name,
+                kind: to_name(element.get_kind()),
+                frame_type: to_name(frame_type),
+                signal_type: to_name(signal_type),
+                hw_address: hw_address.to_string(),
+                timer: {
+                    mode: to_name(timer.mode),
+                    preset_ms: timer.preset_ms
+                },
+                counter: {
+                    mode: to_name(counter.mode),
+                    preset: counter.preset
+                },
+                analog: {
+                    low: analog.low,
+                    high: analog.high,
+                    hysteresis: analog.hysteresis
+                }
+            }
+        })
+        .collect();
+
+    (hardware.into(), elements.into())
+}
+
+fn io_config_from_json(project: &JsonValue) -> IOConfig {
+    let mut io_config = IOConfig::new();
+
+    for (i, module) in project["hardware"].members().enumerate() {
+        io_config.update(IOConfigMessage::HardwareMessage(HwLayoutMessage::AddModule));
+
+        let mut update = |message: HwModuleMessage| io_config.update(
+            IOConfigMessage::HardwareMessage(HwLayoutMessage::ModuleMessage(i, message))
+        );
+
+        update(HwModuleMessage::RackChanged(module["rack"].to_string()));
+        update(HwModuleMessage::SlotChanged(module["slot"].to_string()));
+        update(HwModuleMessage::ChannelsChanged(module["channels"].to_string()));
+        if let Some(signal_type) = from_name(SIGNAL_TYPES_ALL, &module["signal_type"]) {
+            update(HwModuleMessage::SignalTypeSelected(signal_type));
+        }
+    }
+
+    for element in project["io"].members() {
+        let mut io_element = IOElement::new();
+        let mut update = |message: IOElementMessage| io_element.update(message);
+
+        update(IOElementMessage::NameInputChanged(get_text(&element["name"])));
+        if let Some(kind) = from_name(ELEMENT_KINDS_ALL, &element["kind"]) {
+            update(IOElementMessage::KindSelected(kind));
+        }
+        if let Some(frame_type) = from_name(FRAME_TYPES_ALL, &element["frame_type"]) {
+            update(IOElementMessage::FrameTypeSelected(frame_type));
+        }
+        if let Some(signal_type) = from_name(SIGNAL_TYPES_ALL, &element["signal_type"]) {
+            update(IOElementMessage::SignalTypeSelected(signal_type));
+        }
+        update(IOElementMessage::HwSelected(get_text(&element["hw_address"])));
+        if let Some(mode) = from_name(TIMER_MODES_ALL, &element["timer"]["mode"]) {
+            update(IOElementMessage::TimerModeSelected(mode));
+        }
+        if let Some(preset) = element["timer"]["preset_ms"].as_u32() {
+            update(IOElementMessage::TimerPresetChanged(preset.to_string()));
+        }
+        if let Some(mode) = from_name(COUNTER_MODES_ALL, &element["counter"]["mode"]) {
+            update(IOElementMessage::CounterModeSelected(mode));
+        }
+        if let Some(preset) = element["counter"]["preset"].as_u32() {
+            update(IOElementMessage::CounterPresetChanged(preset.to_string()));
+        }
+        update(IOElementMessage::AnalogLowChanged(get_text(&element["analog"]["low"])));
+        update(IOElementMessage::AnalogHighChanged(get_text(&element["analog"]["high"])));
+        update(IOElementMessage::AnalogHysteresisChanged(
+            get_text(&element["analog"]["hysteresis"])
+        ));
+
+        io_config.add_new_element(io_element);
+    }
+
+    io_config
+}
+
+fn subprograms_to_json(
+    subprograms_config: &SubprogramConfig, elements: &Vec<Rc<RefCell<IOElement>>>
+) -> JsonValue {
+    subprograms_config.get_subprograms().iter()
+        .map(|subprogram| {
+            let (_, name, subprogram_type, steps) = subprogram.borrow().get_data();
+            let steps: Vec<JsonValue> = steps.iter()
+                .map(|step| {
+                    let (_, operator, states, controls, description) = step.borrow().get_data();
+
+                    json::object! {
+                        description: description,
+                        operator: to_name(operator),
+                        states: io_conditions_to_json(states, elements),
+                        controls: io_conditions_to_json(controls, elements)
+                    }
+                })
+                .collect();
+
+            json::object! {
+                name: name,
+                subprogram_type: to_name(subprogram_type),
+                steps: steps
+            }
+        })
+        .collect::<Vec<JsonValue>>()
+        .into()
+}
+
+// Condition elements are looked up in the IO config already in place
+fn subprograms_from_json(
+    project: &JsonValue, elements: &Vec<Rc<RefCell<IOElement>>>
+) -> SubprogramConfig {
+    let mut subprograms_config = SubprogramConfig::new();
+
+    for subprogram in project["subprograms"].members() {
+        subprograms_config.update(SubprogramConfigMessage::AddNewSubprogram);
+
+        let subprogram_ref = subprograms_config.get_last_subprogram();
+        let update = |message: SubprogramMessage| subprogram_ref.borrow_mut().update(message);
+
+        update(SubprogramMessage::SubprogramDescrptionChanged(get_text(&subprogram["name"])));
+        if let Some(subprogram_type) =
+            from_name(SUBPROGRAM_TYPES_ALL, &subprogram["subprogram_type"]) {
+            update(SubprogramMessage::SubprogramTypeSelected(subprogram_type));
+        }
+
+        for (i, step) in subprogram["steps"].members().enumerate() {
+            update(SubprogramMessage::AddNewSubprogramStep);
+            update(SubprogramMessage::SubprogramStepMessage(
+                i, SubprogramStepMessage::DescriptionChanged(get_text(&step["description"]))
+            ));
+            if let Some(operator) = from_name(OPERATORS_ALL, &step["operator"]) {
+                update(SubprogramMessage::SubprogramStepMessage(
+                    i, SubprogramStepMessage::OperatorSelected(operator)
+                ));
+            }
+
+            let subprogram_step = subprogram_ref.borrow().get_last_step();
+            let conditions = io_conditions_from_json(&step["states"], elements).into_iter()
+                .chain(io_conditions_from_json(&step["controls"], elements));
+
+            for condition in conditions {
+                subprogram_step.borrow_mut().add_new_conditon(condition);
+            }
+        }
+    }
+
+    subprograms_config.update_addresses();
+
+    subprograms_config
+}
+
+// Transition targets are referenced by the position of the subprogram and its step
+fn conditions_to_json(
+    conditions_config: &CondtionsConfig, subprograms_config: &SubprogramConfig,
+    elements: &Vec<Rc<RefCell<IOElement>>>
+) -> JsonValue {
+    let subprograms = subprograms_config.get_subprograms();
+
+    conditions_config.get_conditions().iter()
+        .map(|condition| {
+            let (description, states, controls, blocked, critical, transition) =
+                condition.borrow().get_data();
+            let transition = transition.and_then(|target| {
+                let (subprogram_uid, step_uid) = target.get_data();
+                let subprogram = subprograms.iter()
+                    .position(|subprogram| subprogram.borrow().get_uid() == subprogram_uid)?;
+                let step = match step_uid {
+                    Some(step_uid) => Some(
+                        subprograms[subprogram].borrow().get_data().3.iter()
+                            .position(|step| step.borrow().get_uid() == step_uid)?
+                    ),
+                    None => None
+                };
+
+                Some(json::object! {
+                    subprogram: subprogram,
+                    step: step
+                })
+            });
+
+            json::object! {
+                description: description,
+                states: io_conditions_to_json(states, elements),
+                controls: io_conditions_to_json(controls, elements),
+                critical: critical,
+                blocked: blocked,
+                transition: transition
+            }
+        })
+        .collect::<Vec<JsonValue>>()
+        .into()
+}
+
+fn conditions_from_json(
+    project: &JsonValue, subprograms_config: &SubprogramConfig,
+    elements: &Vec<Rc<RefCell<IOElement>>>
+) -> CondtionsConfig {
+    let mut conditions_config = CondtionsConfig::new();
+    let subprograms = subprograms_config.get_subprograms();
+
+    for condition in project["conditions"].members() {
+        conditions_config.update(CondtionsConfigMessage::AddNewConditons);
+
+        let conditions_element = conditions_config.get_last_condtions();
+        let update = |message: ConditionsConfigElementMessage| {
+            conditions_element.borrow_mut().update(message)
+        };
+
+        update(ConditionsConfigElementMessage::DescriptionChanged(
+            get_text(&condition["description"])
+        ));
+        update(ConditionsConfigElementMessage::CriticalPicked(
+            condition["critical"].as_bool().unwrap_or(false)
+        ));
+        update(ConditionsConfigElementMessage::BlockedPicked(
+            condition["blocked"].as_bool().unwrap_or(false)
+        ));
+
+        let transition = &condition["transition"];
+
+        if let Some(subprogram) = transition["subprogram"].as_usize()
+            .and_then(|i| subprograms.get(i)) {
+            let subprogram = subprogram.borrow();
+            let step = transition["step"].as_usize()
+                .and_then(|i| subprogram.get_data().3.get(i).map(|step| step.borrow().get_uid()));
+
+            update(ConditionsConfigElementMessage::TransitionTargetSelected(
+                TransitionTarget::new(subprogram.get_uid(), step)
+            ));
+        }
+
+        let io_conditions = io_conditions_from_json(&condition["states"], elements).into_iter()
+            .chain(io_conditions_from_json(&condition["controls"], elements));
+
+        for io_condition in io_conditions {
+            conditions_element.borrow_mut().add_new_conditon(io_condition);
+        }
+    }
+
+    conditions_config
+}
+
+// The document language and the bilingual headers are kept with the project, the
+// remaining settings belong to the user
+pub fn save_project(path: &str) -> std::io::Result<()> {
+    let config = unsafe {
+        &GLOBAL_CONFIG
+    }.as_ref().unwrap().borrow();
+
+    let io_config = unsafe {
+        &IO_CONFIG
+    }.as_ref().unwrap().borrow();
+
+    let subprograms_config = unsafe {
+        &SUBPROGRAMS_CONFIG
+    }.as_ref().unwrap().borrow();
+
+    let conditions_config = unsafe {
+        &CONDTIONS_CONFIG
+    }.as_ref().unwrap().borrow();
+
+    let elements = io_config.get_all_elelments();
+    let (hardware, io) = io_config_to_json(&io_config);
+
+    let project = json::object! {
+        document_language: config.get_document_language_pack(),
+        bilingual_headers: config.is_bilingual_headers(),
+        hardware: hardware,
+        io: io,
+        subprograms: subprograms_to_json(&subprograms_config, &elements),
+        conditions: conditions_to_json(&conditions_config, &subprograms_config, &elements)
+    };
+
+    fs::write(path, project.pretty(4))
+}
+
+// Replaces the model, the views are to be reloaded afterwards
+pub fn load_project(path: &str) -> std::io::Result<()> {
+    let content = fs::read_to_string(path)?;
+    let project = json::parse(content.as_str()).map_err(|error| {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, error.to_string())
+    })?;
+
+    let io_config = io_config_from_json(&project);
+    let elements = io_config.get_all_elelments();
+
+    *unsafe {
+        &IO_CONFIG
+    }.as_ref().unwrap().borrow_mut() = io_config;
+
+    let subprograms_config = subprograms_from_json(&project, &elements);
+    let conditions_config = conditions_from_json(&project, &subprograms_config, &elements);
+
+    *unsafe {
+        &SUBPROGRAMS_CONFIG
+    }.as_ref().unwrap().borrow_mut() = subprograms_config;
+
+    *unsafe {
+        &CONDTIONS_CONFIG
+    }.as_ref().unwrap().borrow_mut() = conditions_config;
+
+    let mut config = unsafe {
+        &GLOBAL_CONFIG
+    }.as_ref().unwrap().borrow_mut();
+
+    // A document language missing on this machine falls back to the UI language
+    config.reset_document_language_pack();
+    if let Some(language) = project["document_language"].as_str() {
+        config.set_document_language_pack(language);
+    }
+    config.set_bilingual_headers(project["bilingual_headers"].as_bool().unwrap_or(false));
+    config.set_project_path(path.to_string());
+    config.add_recent_project(path.to_string());
+
+    Ok(())
+}
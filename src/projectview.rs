@@ -0,0 +1,129 @@
+use iced::{
+    button, Align, Button, Column, Element, Length, Text, Row, TextInput, text_input
+};
+
+use crate::configuration:: {
+    language_pack_conastants::{
+        FIELD_PROJECT_FILE, RECENT_PROJECTS, BUTTON_LOAD, BUTTON_BACK, PROJECT_OPEN_FAILED
+    },
+    style_config::{self, DEFAULT_SPACING},
+    GLOBAL_CONFIG
+};
+
+#[derive(Debug, Clone)]
+pub enum ProjectMessage {
+    PathChanged(String),
+    // Handled by the application, the project replaces the whole model
+    Open(String),
+    OpenFailed(String),
+    Close,
+}
+
+#[derive(Debug)]
+pub struct ProjectOpenView {
+    path_input: text_input::State,
+    path: String,
+    open_button: button::State,
+    recent_buttons: Vec<button::State>,
+    close_button: button::State,
+    error: Option<String>,
+}
+
+impl<'a> ProjectOpenView {
+    pub fn new() -> Self {
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+
+        ProjectOpenView {
+            path_input: text_input::State::new(),
+            path: config.get_project_path(),
+            open_button: button::State::new(),
+            recent_buttons: vec![],
+            close_button: button::State::new(),
+            error: None,
+        }
+    }
+
+    pub fn update(&mut self, message: ProjectMessage) {
+        match message {
+            ProjectMessage::PathChanged(path) => {
+                self.path = path;
+            },
+            ProjectMessage::OpenFailed(error) => {
+                let config = unsafe {
+                    &GLOBAL_CONFIG
+                }.as_ref().unwrap().borrow();
+
+                self.error = Some(format!(
+                    "{}: {}", config.get_field(PROJECT_OPEN_FAILED), error
+                ));
+            },
+            _ => {}
+        }
+    }
+
+    pub fn view(&'a mut self) -> Element<'a, ProjectMessage> {
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+
+        let path_input = TextInput::new(
+            &mut self.path_input,
+            "", self.path.as_str(), ProjectMessage::PathChanged
+        ).size(30).width(Length::Units(500)).style(style_config::TextInput)
+            .on_submit(ProjectMessage::Open(self.path.clone()));
+
+        let open_button = Button::new(
+            &mut self.open_button,
+            Text::new(config.get_field(BUTTON_LOAD).to_string())
+        ).style(style_config::Button::Primary)
+            .on_press(ProjectMessage::Open(self.path.clone()));
+
+        let recent_projects = config.get_recent_projects();
+        let has_recent = !recent_projects.is_empty();
+
+        self.recent_buttons.resize_with(recent_projects.len(), button::State::new);
+
+        let recent = recent_projects.into_iter().zip(self.recent_buttons.iter_mut()).fold(
+            Column::new()
+                .spacing(10)
+                .align_items(Align::Center)
+                .push(Text::new(config.get_field(RECENT_PROJECTS).to_string())),
+            |column, (path, state)| {
+                column.push(Button::new(state, Text::new(path.as_str()).size(20))
+                    .style(style_config::Button::Secondary)
+                    .on_press(ProjectMessage::Open(path)))
+            }
+        );
+
+        let close_button = Button::new(
+            &mut self.close_button,
+            Text::new(config.get_field(BUTTON_BACK).to_string())
+        ).style(style_config::Button::Secondary)
+            .on_press(ProjectMessage::Close);
+
+        let mut content = Column::new()
+            .spacing(DEFAULT_SPACING)
+            .align_items(Align::Center)
+            .push(Row::new()
+                .spacing(DEFAULT_SPACING)
+                .align_items(Align::Center)
+                .push(Text::new(config.get_field(FIELD_PROJECT_FILE).to_string()))
+                .push(path_input)
+                .push(open_button));
+
+        if has_recent {
+            content = content.push(recent);
+        }
+
+        content = content.push(close_button);
+
+        match &self.error {
+            Some(error) => content
+                .push(Text::new(error.as_str()).size(16).color([0.8, 0.4, 0.0]))
+                .into(),
+            None => content.into()
+        }
+    }
+}
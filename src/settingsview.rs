@@ -0,0 +1,175 @@
+use iced::{
+    button, Align, Button, Column, Element, Length, Text, Row, TextInput,
+    text_input, pick_list, PickList
+};
+
+use crate::configuration:: {
+    language_pack_conastants::{
+        FIELD_LANGUAGE, FIELD_OUTPUT_DIR, FIELD_THEME, FIELD_UI_SCALE, FIELD_ENCODING, BUTTON_BACK, SETTINGS_SAVE_FAILED
+    },
+    style_config::{self, Theme, DEFAULT_SPACING, SUBPRORAM_DESCRIPTION_WIDTH, THEMES_ALL},
+    GLOBAL_CONFIG, UiScale, UI_SCALES_ALL
+};
+use crate::encoding::{Encoding, ENCODINGS_ALL};
+
+#[derive(Debug, Clone)]
+pub enum SettingsMessage {
+    LanguageSelected(String),
+    DocumentLanguageSelected(String),
+    BilingualHeadersToggled(bool),
+    OutputDirChanged(String),
+    ThemeSelected(Theme),
+    UiScaleSelected(UiScale),
+    EncodingSelected(Encoding),
+    Close,
+}
+
+// Applies the change to the global config, the settings file is written when the
+// settings screen closes or on exit. The document language is saved with the project
+pub fn update_settings(message: SettingsMessage) {
+    let mut config = unsafe {
+        &GLOBAL_CONFIG
+    }.as_ref().unwrap().borrow_mut();
+
+    match message {
+        SettingsMessage::LanguageSelected(language) => {
+            config.set_active_language_pack(language.as_str())
+        },
+        SettingsMessage::DocumentLanguageSelected(language) => {
+            config.set_document_language_pack(language.as_str())
+        },
+        SettingsMessage::BilingualHeadersToggled(bilingual) => {
+            config.set_bilingual_headers(bilingual)
+        },
+        SettingsMessage::OutputDirChanged(dir) => {
+            config.set_output_dir(dir)
+        },
+        SettingsMessage::ThemeSelected(theme) => {
+            config.set_theme(theme)
+        },
+        SettingsMessage::UiScaleSelected(scale) => {
+            config.set_ui_scale(scale)
+        },
+        SettingsMessage::EncodingSelected(encoding) => {
+            config.set_encoding(encoding)
+        },
+        SettingsMessage::Close => {}
+    }
+}
+
+#[derive(Debug)]
+pub struct SettingsView {
+    language_list: pick_list::State<String>,
+    output_dir_input: text_input::State,
+    theme_list: pick_list::State<Theme>,
+    ui_scale_list: pick_list::State<UiScale>,
+    encoding_list: pick_list::State<Encoding>,
+    close_button: button::State,
+    save_error: Option<String>,
+}
+
+impl<'a> SettingsView {
+    pub fn new() -> Self {
+        SettingsView {
+            language_list: pick_list::State::default(),
+            output_dir_input: text_input::State::new(),
+            theme_list: pick_list::State::default(),
+            ui_scale_list: pick_list::State::default(),
+            encoding_list: pick_list::State::default(),
+            close_button: button::State::new(),
+            save_error: None,
+        }
+    }
+
+    // Writes the settings file, on failure the screen stays open with the error
+    pub fn save(&mut self) -> bool {
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+
+        self.save_error = config.save_settings().err().map(|error| format!(
+            "{}: {}", config.get_field(SETTINGS_SAVE_FAILED), error
+        ));
+
+        self.save_error.is_none()
+    }
+
+    pub fn view(&'a mut self) -> Element<'a, SettingsMessage> {
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+
+        let language_list = PickList::new(
+            &mut self.language_list,
+            config.get_language_packs(),
+            Some(config.get_active_language_pack()),
+            SettingsMessage::LanguageSelected
+        ).style(style_config::PickList);
+
+        let output_dir_input = TextInput::new(
+            &mut self.output_dir_input,
+            "", config.get_output_dir().as_str(), SettingsMessage::OutputDirChanged
+        ).size(30).width(Length::Units(SUBPRORAM_DESCRIPTION_WIDTH)).style(style_config::TextInput);
+
+        let theme_list = PickList::new(
+            &mut self.theme_list,
+            THEMES_ALL,
+            Some(config.get_theme()),
+            SettingsMessage::ThemeSelected
+        ).style(style_config::PickList);
+
+        let ui_scale_list = PickList::new(
+            &mut self.ui_scale_list,
+            UI_SCALES_ALL,
+            Some(config.get_ui_scale()),
+            SettingsMessage::UiScaleSelected
+        ).style(style_config::PickList);
+
+        let encoding_list = PickList::new(
+            &mut self.encoding_list,
+            ENCODINGS_ALL,
+            Some(config.get_encoding()),
+            SettingsMessage::EncodingSelected
+        ).style(style_config::PickList);
+
+        let close_button = Button::new(
+            &mut self.close_button,
+            Text::new(config.get_field(BUTTON_BACK).to_string())
+        ).style(style_config::Button::Secondary)
+            .on_press(SettingsMessage::Close);
+
+        let content = Column::new()
+            .spacing(DEFAULT_SPACING)
+            .align_items(Align::Center)
+            .push(Row::new()
+                .spacing(DEFAULT_SPACING)
+                .align_items(Align::Center)
+                .push(Text::new(config.get_field(FIELD_LANGUAGE).to_string()))
+                .push(language_list))
+            .push(Row::new()
+                .spacing(DEFAULT_SPACING)
+                .align_items(Align::Center)
+                .push(Text::new(config.get_field(FIELD_OUTPUT_DIR).to_string()))
+                .push(output_dir_input))
+            .push(Row::new()
+                .spacing(DEFAULT_SPACING)
+                .align_items(Align::Center)
+                .push(Text::new(config.get_field(FIELD_THEME).to_string()))
+                .push(theme_list)
+                .push(Text::new(config.get_field(FIELD_UI_SCALE).to_string()))
+                .push(ui_scale_list))
+            .push(Row::new()
+                .spacing(DEFAULT_SPACING)
+                .align_items(Align::Center)
+                .push(Text::new(config.get_field(FIELD_ENCODING).to_string()))
+                .push(encoding_list))
+            .push(close_button);
+
+        match &self.save_error {
+            Some(error) => content
+                .push(Text::new(error.as_str()).size(16).color([0.8, 0.4, 0.0]))
+                .into(),
+            None => content.into()
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use std::{collections::HashSet, rc::Rc, cell::RefCell};
+
+use iced::{
+    button, Align, Button, Checkbox, Column, Element, Length, Text, Row, Scrollable, scrollable
+};
+
+use crate::configuration::{
+    language_pack_conastants::{
+        SUBPROGRAM_STEP, SIMULATION_IO_STATES, SIMULATION_STEPS, SIMULATION_CONDITIONS,
+        SIMULATION_NONE, BUTTON_RESET
+    },
+    style_config::{self, DEFAULT_PADDING, DEFAULT_SPACING},
+    GLOBAL_CONFIG, IOElementStates, Operators
+};
+use crate::configs::{
+    IOElementCoditions, IO_CONFIG, SUBPROGRAMS_CONFIG, CONDTIONS_CONFIG
+};
+
+#[derive(Debug, Clone)]
+pub enum SimulationMessage {
+    ElementToggled(usize, bool),
+    Reset,
+}
+
+// Conditions without a picked element do not take part
+fn condition_holds(condition: &IOElementCoditions, active: &HashSet<usize>) -> bool {
+    let (_, state, _) = condition.get_data();
+
+    match (condition.get_element_uid(), state) {
+        (Some(uid), IOElementStates::Active) => active.contains(&uid),
+        (Some(uid), IOElementStates::Inactive) => !active.contains(&uid),
+        _ => true
+    }
+}
+
+// A step or row without state conditions is never satisfied
+fn conditions_hold(
+    conditions: &[Rc<RefCell<IOElementCoditions>>], operator: Operators, active: &HashSet<usize>
+) -> bool {
+    let mut results = conditions.iter()
+        .map(|condition| condition_holds(&condition.borrow(), active));
+
+    match operator {
+        _ if conditions.is_empty() => false,
+        Operators::AND => results.all(|holds| holds),
+        Operators::OR => results.any(|holds| holds),
+    }
+}
+
+fn controls_text(controls: &[Rc<RefCell<IOElementCoditions>>]) -> String {
+    controls.iter()
+        .filter_map(|control| {
+            let (element, state, _) = control.borrow().get_data();
+
+            element.map(|element| format!("{}: {}", element.borrow().get_data().0, state))
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn result_view<'a, Message: 'a>(title: String, results: Vec<(String, String)>) -> Column<'a, Message> {
+    let config = unsafe {
+        &GLOBAL_CONFIG
+    }.as_ref().unwrap().borrow();
+
+    let column = Column::new()
+        .spacing(5)
+        .width(Length::FillPortion(1))
+        .push(Text::new(title));
+
+    if results.is_empty() {
+        return column.push(Text::new(config.get_field(SIMULATION_NONE).to_string()).size(16))
+    }
+
+    results.into_iter().fold(column, |column, (result, controls)| {
+        column
+            .push(Text::new(result).size(16))
+            .push(Text::new(controls).size(14))
+    })
+}
+
+// Dry run of the model: the IO states are set by hand and the steps and conditions rows
+// they satisfy are listed with the controls they would set
+#[derive(Debug)]
+pub struct SimulationView {
+    active: HashSet<usize>,
+    reset_button: button::State,
+    scroll: scrollable::State,
+}
+
+impl<'a> SimulationView {
+    pub fn new() -> Self {
+        SimulationView {
+            active: HashSet::new(),
+            reset_button: button::State::new(),
+            scroll: scrollable::State::new(),
+        }
+    }
+
+    pub fn update(&mut self, message: SimulationMessage) {
+        match message {
+            SimulationMessage::ElementToggled(uid, true) => {
+                self.active.insert(uid);
+            },
+            SimulationMessage::ElementToggled(uid, false) => {
+                self.active.remove(&uid);
+            },
+            SimulationMessage::Reset => self.active.clear(),
+        }
+    }
+
+    pub fn view(&'a mut self) -> Element<'a, SimulationMessage> {
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+        let elements = unsafe {
+            &IO_CONFIG
+        }.as_ref().unwrap().borrow().get_all_elelments();
+        let subprograms = unsafe {
+            &SUBPROGRAMS_CONFIG
+        }.as_ref().unwrap().borrow().get_subprograms();
+        let conditions = unsafe {
+            &CONDTIONS_CONFIG
+        }.as_ref().unwrap().borrow().get_conditions();
+
+        let io_states = elements.iter().fold(
+            Column::new()
+                .spacing(5)
+                .width(Length::FillPortion(1))
+                .push(Text::new(config.get_field(SIMULATION_IO_STATES).to_string())),
+            |column, element| {
+                let uid = element.borrow().get_uid();
+                let (name, ..) = element.borrow().get_data();
+
+                column.push(Checkbox::new(
+                    self.active.contains(&uid),
+                    name.as_str(),
+                    move |active| SimulationMessage::ElementToggled(uid, active)
+                ).style(style_config::Checkbox))
+            }
+        );
+
+        let mut steps: Vec<(String, String)> = vec![];
+
+        for subprogram in subprograms {
+            let (address, name, _, subprogram_steps) = subprogram.borrow().get_data();
+
+            for step in subprogram_steps {
+                let (id, operator, states, controls, description) = step.borrow().get_data();
+
+                if conditions_hold(&states, operator, &self.active) {
+                    steps.push((
+                        format!(
+                            "{}. {} / {} {}: {}", address, name,
+                            config.get_field(SUBPROGRAM_STEP).to_string(), id, description
+                        ),
+                        controls_text(&controls)
+                    ));
+                }
+            }
+        }
+
+        let rows: Vec<(String, String)> = conditions.iter()
+            .filter_map(|condition| {
+                let (description, states, controls, _, _, transition) =
+                    condition.borrow().get_data();
+
+                if !conditions_hold(&states, Operators::AND, &self.active) {
+                    return None
+                }
+
+                Some((
+                    match transition {
+                        Some(transition) => format!("{} -> {}", description, transition),
+                        None => description
+                    },
+                    controls_text(&controls)
+                ))
+            })
+            .collect();
+
+        let reset_button = Button::new(
+            &mut self.reset_button,
+            Text::new(config.get_field(BUTTON_RESET).to_string())
+        ).style(style_config::Button::Secondary)
+            .on_press(SimulationMessage::Reset);
+
+        Column::new()
+            .width(Length::Fill)
+            .align_items(Align::Center)
+            .spacing(DEFAULT_SPACING)
+            .push(reset_button)
+            .push(Scrollable::new(&mut self.scroll)
+                .width(Length::Fill)
+                .padding(DEFAULT_PADDING)
+                .push(Row::new()
+                    .spacing(DEFAULT_SPACING)
+                    .push(io_states)
+                    .push(result_view(config.get_field(SIMULATION_STEPS).to_string(), steps))
+                    .push(result_view(
+                        config.get_field(SIMULATION_CONDITIONS).to_string(), rows
+                    ))))
+            .into()
+    }
+}
@@ -6,10 +6,11 @@ use iced::{
 
 use crate::{configuration:: {
     language_pack_conastants::{
-        FIELD_NAME,  FIELD_ADDRESS, BUTTON_EDIT_STATES_SUBPROGRAM_STEP, SUBPROGRAM_STEP, OPERATOR, BUTTON_EDIT_CONTROLS_SUBPROGRAM_STEP, FIELD_SIGNAL, FIELD_TYPE_STATE, FIELD_DESCRIPTION, FIELD_TYPE
+        FIELD_NAME,  FIELD_ADDRESS, BUTTON_EDIT_STATES_SUBPROGRAM_STEP, SUBPROGRAM_STEP, OPERATOR, BUTTON_EDIT_CONTROLS_SUBPROGRAM_STEP, FIELD_SIGNAL, FIELD_TYPE_STATE, FIELD_DESCRIPTION, FIELD_TYPE,
+        BUTTON_MOVE_UP, BUTTON_MOVE_DOWN, BUTTON_DUPLICATE
     },
-    style_config::{DEFAULT_SPACING, SUBPRORAM_DESCRIPTION_WIDTH}, GLOBAL_CONFIG, delete_icon, edit_icon, Operators, IOElementStates, FrameTypes, SubprogramTypes
-}, configs::{SubprogramStep, SubprogramStepMessage, IOElementCoditions, IOElementCoditionsMessage, IO_CONFIG, IOElement}};
+    style_config::{self, DEFAULT_SPACING, SUBPRORAM_DESCRIPTION_WIDTH}, GLOBAL_CONFIG, delete_icon, edit_icon, Operators, IOElementStates, FrameTypes, SubprogramTypes
+}, configs::{SubprogramStep, SubprogramStepMessage, IOElementCoditions, IOElementCoditionsMessage, IO_CONFIG, IOElement, IOElementId, MoveDirection}};
 
 use crate::configs::{
     SubprogramMessage, Subprogram
@@ -36,6 +37,9 @@ static SUBPROGRAM_TYPES_ALL: &[SubprogramTypes] = &[
 pub struct SubprogramView {
     delete_button: button::State,
     edit_button: button::State,
+    move_up_button: button::State,
+    move_down_button: button::State,
+    duplicate_button: button::State,
     subprogram: Rc<RefCell<Subprogram>>,
 }
 
@@ -44,6 +48,9 @@ impl<'a> SubprogramView {
         SubprogramView {
             delete_button: button::State::new(),
             edit_button: button::State::new(),
+            move_up_button: button::State::new(),
+            move_down_button: button::State::new(),
+            duplicate_button: button::State::new(),
             subprogram: subprogram.clone(),
         }
     }
@@ -58,14 +65,34 @@ impl<'a> SubprogramView {
             .width(Length::Units(SUBPRORAM_DESCRIPTION_WIDTH));
 
         let edit_button = Button::new(&mut self.edit_button, edit_icon())
+            .style(style_config::Button::Icon)
             .on_press(SubprogramMessage::SubprogramEdit);
 
         let delete_button = Button::new(&mut self.delete_button, delete_icon())
+            .style(style_config::Button::Icon)
             .on_press(SubprogramMessage::SubprogramDelete);
 
         let config = unsafe {
             &GLOBAL_CONFIG
-        }.as_ref().unwrap();
+        }.as_ref().unwrap().borrow();
+
+        let move_up_button = Button::new(
+            &mut self.move_up_button,
+            Text::new(config.get_field(BUTTON_MOVE_UP).to_string())
+        ).style(style_config::Button::Icon)
+            .on_press(SubprogramMessage::SubprogramMove(MoveDirection::Up));
+
+        let move_down_button = Button::new(
+            &mut self.move_down_button,
+            Text::new(config.get_field(BUTTON_MOVE_DOWN).to_string())
+        ).style(style_config::Button::Icon)
+            .on_press(SubprogramMessage::SubprogramMove(MoveDirection::Down));
+
+        let duplicate_button = Button::new(
+            &mut self.duplicate_button,
+            Text::new(config.get_field(BUTTON_DUPLICATE).to_string())
+        ).style(style_config::Button::Icon)
+            .on_press(SubprogramMessage::SubprogramDuplicate);
 
         Row::new()
             .spacing(DEFAULT_SPACING)
@@ -78,6 +105,9 @@ impl<'a> SubprogramView {
                                                               .as_str()))
                 .push(description_label))
             .push(edit_button)
+            .push(move_up_button)
+            .push(move_down_button)
+            .push(duplicate_button)
             .push(delete_button)
 
             .into()
@@ -89,6 +119,9 @@ pub struct SubprogramStepView {
     state_edit_button: button::State,
     control_edit_button: button::State,
     delete_button: button::State,
+    move_up_button: button::State,
+    move_down_button: button::State,
+    duplicate_button: button::State,
     operator_list: pick_list::State<Operators>,
     subprogramstep: Rc<RefCell<SubprogramStep>>,
     description_input: text_input::State,
@@ -100,6 +133,9 @@ impl<'a> SubprogramStepView {
             delete_button: button::State::new(),
             state_edit_button: button::State::new(),
             control_edit_button: button::State::new(),
+            move_up_button: button::State::new(),
+            move_down_button: button::State::new(),
+            duplicate_button: button::State::new(),
             operator_list: pick_list::State::default(),
             subprogramstep: subprogramstep.clone(),
             description_input: text_input::State::new()
@@ -112,7 +148,7 @@ impl<'a> SubprogramStepView {
         let description_input = TextInput::new(
             &mut self.description_input,
             "", &desription, SubprogramStepMessage::DescriptionChanged
-        ).size(30).width(Length::Units(SUBPRORAM_DESCRIPTION_WIDTH));
+        ).size(30).width(Length::Units(SUBPRORAM_DESCRIPTION_WIDTH)).style(style_config::TextInput);
 
         let step_label = Text::new(String::from(format!("{}", step)),
         ).size(30).width(Length::Units(140));
@@ -122,23 +158,44 @@ impl<'a> SubprogramStepView {
             OPERATORS_ALL,
             Some(operator),
             SubprogramStepMessage::OperatorSelected
-        );
+        ).style(style_config::PickList);
 
         let config = unsafe {
             &GLOBAL_CONFIG
-        }.as_ref().unwrap();
+        }.as_ref().unwrap().borrow();
 
         let edit_state_button = Button::new(
             &mut self.state_edit_button,
             Text::new(config.get_field(BUTTON_EDIT_STATES_SUBPROGRAM_STEP).to_string())
-        ).on_press(SubprogramStepMessage::PickConditions(FrameTypes::State));
+        ).style(style_config::Button::Icon)
+            .on_press(SubprogramStepMessage::PickConditions(FrameTypes::State));
 
         let edit_control_button = Button::new(
             &mut self.control_edit_button,
             Text::new(config.get_field(BUTTON_EDIT_CONTROLS_SUBPROGRAM_STEP).to_string())
-        ).on_press(SubprogramStepMessage::PickConditions(FrameTypes::Control));
+        ).style(style_config::Button::Icon)
+            .on_press(SubprogramStepMessage::PickConditions(FrameTypes::Control));
+
+        let move_up_button = Button::new(
+            &mut self.move_up_button,
+            Text::new(config.get_field(BUTTON_MOVE_UP).to_string())
+        ).style(style_config::Button::Icon)
+            .on_press(SubprogramStepMessage::MoveStep(MoveDirection::Up));
+
+        let move_down_button = Button::new(
+            &mut self.move_down_button,
+            Text::new(config.get_field(BUTTON_MOVE_DOWN).to_string())
+        ).style(style_config::Button::Icon)
+            .on_press(SubprogramStepMessage::MoveStep(MoveDirection::Down));
+
+        let duplicate_button = Button::new(
+            &mut self.duplicate_button,
+            Text::new(config.get_field(BUTTON_DUPLICATE).to_string())
+        ).style(style_config::Button::Icon)
+            .on_press(SubprogramStepMessage::DuplicateStep);
 
         let delete_button = Button::new(&mut self.delete_button, delete_icon())
+            .style(style_config::Button::Icon)
             .on_press(SubprogramStepMessage::DeleteStep);
 
         Row::new()
@@ -157,6 +214,9 @@ impl<'a> SubprogramStepView {
                 .push(operator_list))
             .push(edit_state_button)
             .push(edit_control_button)
+            .push(move_up_button)
+            .push(move_down_button)
+            .push(duplicate_button)
             .push(delete_button)
 
             .into()
@@ -165,11 +225,10 @@ impl<'a> SubprogramStepView {
 
 #[derive(Debug)]
 pub struct SubprogramIOConditionsView {
-    io_element_list: pick_list::State<String>,
+    io_element_list: pick_list::State<IOElementId>,
     state_list: pick_list::State<IOElementStates>,
     delete_button: button::State,
     io_condition: Rc<RefCell<IOElementCoditions>>,
-    io_list: Vec<String>,
 }
 
 impl<'a> SubprogramIOConditionsView {
@@ -179,19 +238,17 @@ impl<'a> SubprogramIOConditionsView {
             state_list: pick_list::State::default(),
             delete_button: button::State::new(),
             io_condition: io_condition.clone(),
-            io_list: vec![],
         }
     }
 
-    fn get_names_list(io_list: &'a Vec<Rc<RefCell<IOElement>>>) -> Vec<String> {
-        let mut name_list: Vec<String> = vec![];
+    fn get_ids_list(io_list: &'a Vec<Rc<RefCell<IOElement>>>) -> Vec<IOElementId> {
+        let mut id_list: Vec<IOElementId> = vec![];
 
         for io_element in io_list {
-            let (name, ..) = io_element.borrow().get_data();
-            name_list.push(name.clone());
+            id_list.push(IOElementId(io_element.borrow().get_uid()));
         }
 
-        name_list
+        id_list
     }
 
     pub fn view(&'a mut self) -> Element<'a, IOElementCoditionsMessage> {
@@ -201,31 +258,32 @@ impl<'a> SubprogramIOConditionsView {
         }.as_ref().unwrap();
         let io_list = io_config.borrow().get_elements_by_frame_type(frame_type);
 
-        let (name, ..) = if io_element != None {
-            io_element.unwrap().borrow().get_data()
+        let uid = if io_element != None {
+            io_element.unwrap().borrow().get_uid()
         } else {
-            io_list.first().unwrap().borrow().get_data()
+            io_list.first().unwrap().borrow().get_uid()
         };
 
         let io_element_list = PickList::new(
             &mut self.io_element_list,
-            Self::get_names_list(&io_list),
-            Some(name),
+            Self::get_ids_list(&io_list),
+            Some(IOElementId(uid)),
             IOElementCoditionsMessage::IOElementSelected
-        );
+        ).style(style_config::PickList);
 
         let state_list = PickList::new(
             &mut self.state_list,
             IO_STATES_ALL,
             Some(state),
             IOElementCoditionsMessage::StateChanged
-        );
+        ).style(style_config::PickList);
 
         let config = unsafe {
             &GLOBAL_CONFIG
-        }.as_ref().unwrap();
+        }.as_ref().unwrap().borrow();
 
         let delete_button = Button::new(&mut self.delete_button, delete_icon())
+            .style(style_config::Button::Icon)
             .on_press(IOElementCoditionsMessage::DeleteElement(frame_type));
 
         Row::new()
@@ -272,7 +330,7 @@ impl<'a> SubprogramDescriptionEditView {
             SUBPROGRAM_TYPES_ALL,
             Some(subprogram_type),
             SubprogramMessage::SubprogramTypeSelected
-        );
+        ).style(style_config::PickList);
 
         let description_input = TextInput::new(
             &mut self.description_input,
@@ -280,11 +338,11 @@ impl<'a> SubprogramDescriptionEditView {
             &desription,
             SubprogramMessage::SubprogramDescrptionChanged
         ).size(30)
-            .width(Length::Units(SUBPRORAM_DESCRIPTION_WIDTH));
+            .width(Length::Units(SUBPRORAM_DESCRIPTION_WIDTH)).style(style_config::TextInput);
 
         let config = unsafe {
             &GLOBAL_CONFIG
-        }.as_ref().unwrap();
+        }.as_ref().unwrap().borrow();
 
         Row::new()
             .spacing(DEFAULT_SPACING)
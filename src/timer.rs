@@ -0,0 +1,142 @@
+// IEC 61131-3 timer function blocks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerMode {
+    TON,
+    TOF,
+    TP,
+}
+
+pub static TIMER_MODES_ALL: &[TimerMode] = &[
+    TimerMode::TON,
+    TimerMode::TOF,
+    TimerMode::TP,
+];
+
+impl std::fmt::Display for TimerMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+// Steps start the timer through IN, conditions check the done bit Q
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timer {
+    pub mode: TimerMode,
+    pub preset_ms: u32,
+}
+
+impl Timer {
+    pub fn new() -> Self {
+        Timer {
+            mode: TimerMode::TON,
+            preset_ms: 1000,
+        }
+    }
+
+    pub fn get_preset(&self) -> String {
+        format_duration(self.preset_ms)
+    }
+}
+
+static DURATION_UNITS: &[(&str, u32)] = &[
+    ("d", 86_400_000),
+    ("h", 3_600_000),
+    ("m", 60_000),
+    ("s", 1000),
+    ("ms", 1),
+];
+
+// IEC time literal, e.g. T#1m30s
+pub fn format_duration(ms: u32) -> String {
+    if 0 == ms {
+        return String::from("T#0ms");
+    }
+
+    let mut rest = ms;
+    let parts: String = DURATION_UNITS.iter()
+        .filter_map(|(unit, size)| {
+            let value = rest / size;
+            rest %= size;
+
+            if value > 0 { Some(format!("{}{}", value, unit)) } else { None }
+        })
+        .collect();
+
+    format!("T#{}", parts)
+}
+
+// Accepts IEC time literals with or without the T# prefix, a bare number is in milliseconds
+pub fn parse_duration(text: &str) -> Option<u32> {
+    let text = text.trim().to_lowercase();
+    let text = text.strip_prefix("time#")
+        .or_else(|| text.strip_prefix("t#"))
+        .unwrap_or(text.as_str())
+        .replace('_', "");
+
+    if text.is_empty() {
+        return None;
+    }
+    if let Ok(ms) = text.parse::<u32>() {
+        return Some(ms);
+    }
+
+    let mut total: u32 = 0;
+    let mut rest = text.as_str();
+
+    while !rest.is_empty() {
+        let number_len = rest.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+        let value = rest[..number_len].parse::<f64>().ok()?;
+        rest = &rest[number_len..];
+
+        let unit_len = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        let (_, size) = DURATION_UNITS.iter().find(|(unit, _)| *unit == &rest[..unit_len])?;
+        rest = &rest[unit_len..];
+
+        // The cast saturates, values beyond the range have to be rejected before it
+        let ms = (value * *size as f64).round();
+        if ms > u32::MAX as f64 {
+            return None;
+        }
+
+        total = total.checked_add(ms as u32)?;
+    }
+
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_duration_reads_iec_literals() {
+        assert_eq!(parse_duration("T#1m30s"), Some(90_000));
+        assert_eq!(parse_duration("time#1h_2m"), Some(3_720_000));
+        assert_eq!(parse_duration("t#1.5s"), Some(1500));
+        assert_eq!(parse_duration("250ms"), Some(250));
+        assert_eq!(parse_duration(" 1500 "), Some(1500));
+    }
+
+    #[test]
+    fn parse_duration_rejects_invalid_text() {
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("T#"), None);
+        assert_eq!(parse_duration("T#5x"), None);
+        assert_eq!(parse_duration("T#s"), None);
+    }
+
+    #[test]
+    fn parse_duration_rejects_overflow() {
+        assert_eq!(parse_duration("4294967296"), None);
+        assert_eq!(parse_duration("T#50d"), None);
+        assert_eq!(parse_duration("T#49d17h2m47s295ms"), Some(u32::MAX));
+        assert_eq!(parse_duration("T#49d17h2m47s296ms"), None);
+    }
+
+    #[test]
+    fn format_duration_round_trips() {
+        assert_eq!(format_duration(90_000), "T#1m30s");
+        assert_eq!(format_duration(0), "T#0ms");
+        assert_eq!(parse_duration(format_duration(u32::MAX).as_str()), Some(u32::MAX));
+    }
+}
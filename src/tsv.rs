@@ -0,0 +1,209 @@
+use std::{rc::Rc, cell::RefCell};
+
+use crate::configs::{
+    IOElement, IOElementMessage, IOElementCoditions, Subprogram, SubprogramStep,
+    ConditionsConfigElement, IO_CONFIG
+};
+use crate::configuration::{FrameTypes, SignalTypes, ElementKind};
+use crate::hardware::HwAddress;
+use crate::timer::{TIMER_MODES_ALL, parse_duration};
+use crate::counter::COUNTER_MODES_ALL;
+
+static FRAME_TYPES_ALL: &[FrameTypes] = &[
+    FrameTypes::State,
+    FrameTypes::Control,
+    FrameTypes::Flag,
+];
+
+static SIGNAL_TYPES_ALL: &[SignalTypes] = &[
+    SignalTypes::Input,
+    SignalTypes::Output,
+];
+
+// Accepts the name of the active language pack as well as the identifier
+fn parse_value<T: Copy + std::fmt::Display + std::fmt::Debug>(
+    values: &[T], value: &str
+) -> Option<T> {
+    let value = value.trim();
+
+    values.iter().copied().find(|item| {
+        item.to_string().eq_ignore_ascii_case(value) ||
+            format!("{:?}", item).eq_ignore_ascii_case(value)
+    })
+}
+
+pub fn parse_frame_type(value: &str) -> Option<FrameTypes> {
+    parse_value(FRAME_TYPES_ALL, value)
+}
+
+pub fn parse_signal_type(value: &str) -> Option<SignalTypes> {
+    parse_value(SIGNAL_TYPES_ALL, value)
+}
+
+fn conditions_to_text(conditions: &Vec<Rc<RefCell<IOElementCoditions>>>) -> String {
+    conditions.iter()
+        .filter_map(|condition| {
+            let (element, state, _) = condition.borrow().get_data();
+
+            element.map(|element| {
+                let (name, ..) = element.borrow().get_data();
+                format!("{}={}", name, state)
+            })
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+// Rows of name, type, signal and HW address, e.g. pasted from a spreadsheet.
+// Timer and counter rows carry the block type in place of the type and the preset in place
+// of the address, analog band rows the kind and the limits after the address
+pub fn parse_io_elements(text: &str) -> Vec<IOElement> {
+    let mut elements = vec![];
+
+    for (i, line) in text.lines().enumerate() {
+        let columns: Vec<&str> = line.split('\t').collect();
+        let name = columns[0].trim();
+        let frame_type = columns.get(1).and_then(|value| parse_frame_type(value));
+        let timer_mode = columns.get(1).and_then(|value| parse_value(TIMER_MODES_ALL, value));
+        let counter_mode = columns.get(1)
+            .and_then(|value| parse_value(COUNTER_MODES_ALL, value));
+        let is_analog = columns.get(1)
+            .and_then(|value| parse_value(&[ElementKind::Analog], value))
+            .is_some();
+        let signal_type = columns.get(2).and_then(|value| parse_signal_type(value));
+        let hw_address = columns.get(3).map(|value| value.trim().to_string());
+
+        // Column titles copied along with the rows
+        let is_header = 0 == i && columns.len() > 1 && frame_type.is_none() &&
+            timer_mode.is_none() && counter_mode.is_none() && !is_analog &&
+            hw_address.as_ref().map_or(true, |hw| HwAddress::parse(hw).is_none());
+
+        if name.is_empty() || is_header {
+            continue;
+        }
+
+        let mut element = IOElement::new();
+
+        element.update(IOElementMessage::NameInputChanged(name.to_string()));
+        if let Some(timer_mode) = timer_mode {
+            element.update(IOElementMessage::KindSelected(ElementKind::Timer));
+            element.update(IOElementMessage::TimerModeSelected(timer_mode));
+            if let Some(preset) = hw_address.and_then(|preset| parse_duration(preset.as_str())) {
+                element.update(IOElementMessage::TimerPresetChanged(preset.to_string()));
+            }
+
+            elements.push(element);
+            continue;
+        }
+        if let Some(counter_mode) = counter_mode {
+            element.update(IOElementMessage::KindSelected(ElementKind::Counter));
+            element.update(IOElementMessage::CounterModeSelected(counter_mode));
+            if let Some(preset) = hw_address.and_then(|preset| preset.parse::<u32>().ok()) {
+                element.update(IOElementMessage::CounterPresetChanged(preset.to_string()));
+            }
+
+            elements.push(element);
+            continue;
+        }
+        if is_analog {
+            element.update(IOElementMessage::KindSelected(ElementKind::Analog));
+            if let Some(hw_address) = hw_address {
+                element.update(IOElementMessage::HwSelected(hw_address));
+            }
+
+            let limit = |i: usize| {
+                columns.get(i).map_or(String::new(), |value| value.trim().to_string())
+            };
+            element.update(IOElementMessage::AnalogLowChanged(limit(5)));
+            element.update(IOElementMessage::AnalogHighChanged(limit(6)));
+            element.update(IOElementMessage::AnalogHysteresisChanged(limit(7)));
+
+            elements.push(element);
+            continue;
+        }
+        if let Some(frame_type) = frame_type {
+            element.update(IOElementMessage::FrameTypeSelected(frame_type));
+        }
+        if let Some(signal_type) = signal_type {
+            element.update(IOElementMessage::SignalTypeSelected(signal_type));
+        }
+        if let Some(hw_address) = hw_address {
+            element.update(IOElementMessage::HwSelected(hw_address));
+        }
+
+        elements.push(element);
+    }
+
+    elements
+}
+
+// The direct address is derived from the hardware layout and ignored when pasted back
+pub fn io_element_row(element: &IOElement) -> String {
+    let (name, frame_type, signal_type, hw_address) = element.get_data();
+
+    match element.get_kind() {
+        ElementKind::Timer => {
+            let timer = element.get_timer();
+
+            return format!("{}\t{}\t\t{}\t", name, timer.mode, timer.get_preset());
+        },
+        ElementKind::Counter => {
+            let counter = element.get_counter();
+
+            return format!("{}\t{}\t\t{}\t", name, counter.mode, counter.preset);
+        },
+        ElementKind::Analog => {
+            let analog = element.get_analog();
+
+            return format!(
+                "{}\t{}\t\t{}\t\t{}\t{}\t{}",
+                name, ElementKind::Analog, hw_address, analog.low, analog.high, analog.hysteresis
+            );
+        },
+        ElementKind::Discrete if FrameTypes::Flag == frame_type => {
+            return format!("{}\t{}\t\t\t", name, frame_type);
+        },
+        ElementKind::Discrete => ()
+    }
+
+    let iec_address = unsafe {
+        &IO_CONFIG
+    }.as_ref().unwrap().borrow().get_iec_address(element);
+
+    format!(
+        "{}\t{}\t{}\t{}\t{}",
+        name, frame_type, signal_type, hw_address, iec_address.unwrap_or_default()
+    )
+}
+
+pub fn subprogram_row(subprogram: &Subprogram) -> String {
+    let (address, name, subprogram_type, _) = subprogram.get_data();
+
+    format!("{}\t{}\t{}", address, name, subprogram_type)
+}
+
+pub fn subprogram_step_row(step: &SubprogramStep) -> String {
+    let (id, operator, states, controls, description) = step.get_data();
+
+    format!(
+        "{}\t{}\t{}\t{}\t{}",
+        id, description, operator, conditions_to_text(&states), conditions_to_text(&controls)
+    )
+}
+
+pub fn condition_row(condition: &ConditionsConfigElement) -> String {
+    let (description, states, controls, blocked, critical, transition) = condition.get_data();
+
+    format!(
+        "{}\t{}\t{}\t{}\t{}\t{}",
+        description, conditions_to_text(&states), conditions_to_text(&controls),
+        critical, blocked, transition.map_or(String::new(), |target| target.to_string())
+    )
+}
+
+pub fn io_condition_row(condition: &IOElementCoditions) -> String {
+    let (element, state, _) = condition.get_data();
+    let name = element.map_or(String::new(), |element| element.borrow().get_data().0);
+
+    format!("{}\t{}", name, state)
+}
@@ -0,0 +1,192 @@
+use std::{rc::Rc, cell::RefCell};
+
+use iced::{
+    Align, Column, Element, Length, Text, Row, Scrollable, scrollable
+};
+
+use crate::configuration::{
+    language_pack_conastants::{
+        NAVIGATION_IO_CONFIGURATION, NAVIGATION_SUBPROGRAMS, NAVIGATION_CONDITIONS,
+        IMPORT_ISSUE_EMPTY_NAME, IMPORT_ISSUE_DUPLICATE_NAME, ANALOG_ISSUE_LIMITS,
+        SUBPROGRAM_STEPS_EMPTY, SUBPROGRAM_STEP, GENERATE_UNRESOLVED_TRANSITION,
+        VALIDATION_EMPTY_CONDITION, VALIDATION_NO_ISSUES
+    },
+    style_config::{DEFAULT_PADDING, DEFAULT_SPACING},
+    ElementKind, GLOBAL_CONFIG
+};
+use crate::configs::{
+    IOElementCoditions, IO_CONFIG, SUBPROGRAMS_CONFIG, CONDTIONS_CONFIG
+};
+
+const SUBJECT_WIDTH: u16 = 300;
+
+#[derive(Debug, Clone)]
+pub struct ValidationIssue {
+    // Navigation field of the screen the issue is fixed on
+    pub section: &'static str,
+    pub subject: String,
+    pub issue: String,
+}
+
+fn has_empty_condition(conditions: &[Rc<RefCell<IOElementCoditions>>]) -> bool {
+    conditions.iter().any(|condition| condition.borrow().get_element_uid().is_none())
+}
+
+// Unnamed rows are referred to by their position
+fn get_subject(name: String, i: usize) -> String {
+    match name.is_empty() {
+        true => format!("#{}", i + 1),
+        false => name
+    }
+}
+
+// Problems that stop the generation or leave gaps in the tables, collected from the
+// whole model
+pub fn get_validation_issues() -> Vec<ValidationIssue> {
+    let config = unsafe {
+        &GLOBAL_CONFIG
+    }.as_ref().unwrap().borrow();
+    let ioconfig = unsafe {
+        &IO_CONFIG
+    }.as_ref().unwrap().borrow();
+
+    let mut issues: Vec<ValidationIssue> = vec![];
+    let elements = ioconfig.get_all_elelments();
+    let names: Vec<String> = elements.iter()
+        .map(|element| element.borrow().get_data().0)
+        .collect();
+
+    for (i, element) in elements.iter().enumerate() {
+        let element = element.borrow();
+        let (name, ..) = element.get_data();
+        let mut element_issues: Vec<String> = vec![];
+
+        if name.is_empty() {
+            element_issues.push(config.get_field(IMPORT_ISSUE_EMPTY_NAME).to_string());
+        } else if names.iter().filter(|other| **other == name).count() > 1 {
+            element_issues.push(config.get_field(IMPORT_ISSUE_DUPLICATE_NAME).to_string());
+        }
+
+        element_issues.extend(ioconfig.get_hw_issues(&element).iter().map(|issue| issue.to_string()));
+
+        if ElementKind::Analog == element.get_kind() && !element.get_analog().is_valid() {
+            element_issues.push(config.get_field(ANALOG_ISSUE_LIMITS).to_string());
+        }
+
+        let subject = get_subject(name, i);
+
+        issues.extend(element_issues.into_iter().map(|issue| ValidationIssue {
+            section: NAVIGATION_IO_CONFIGURATION,
+            subject: subject.clone(),
+            issue: issue,
+        }));
+    }
+
+    let subprograms = unsafe {
+        &SUBPROGRAMS_CONFIG
+    }.as_ref().unwrap().borrow().get_subprograms();
+
+    for (i, subprogram) in subprograms.iter().enumerate() {
+        let (address, name, _, steps) = subprogram.borrow().get_data();
+        let subject = format!("{}. {}", address, get_subject(name, i));
+
+        if steps.is_empty() {
+            issues.push(ValidationIssue {
+                section: NAVIGATION_SUBPROGRAMS,
+                subject: subject.clone(),
+                issue: config.get_field(SUBPROGRAM_STEPS_EMPTY).to_string(),
+            });
+        }
+
+        for step in steps {
+            let (id, _, states, controls, _) = step.borrow().get_data();
+
+            if has_empty_condition(&states) || has_empty_condition(&controls) {
+                issues.push(ValidationIssue {
+                    section: NAVIGATION_SUBPROGRAMS,
+                    subject: format!(
+                        "{} / {} {}", subject, config.get_field(SUBPROGRAM_STEP).to_string(), id
+                    ),
+                    issue: config.get_field(VALIDATION_EMPTY_CONDITION).to_string(),
+                });
+            }
+        }
+    }
+
+    let conditions = unsafe {
+        &CONDTIONS_CONFIG
+    }.as_ref().unwrap().borrow().get_conditions();
+
+    for (i, condition) in conditions.iter().enumerate() {
+        let condition = condition.borrow();
+        let (description, states, controls, ..) = condition.get_data();
+        let subject = get_subject(description, i);
+
+        if has_empty_condition(&states) || has_empty_condition(&controls) {
+            issues.push(ValidationIssue {
+                section: NAVIGATION_CONDITIONS,
+                subject: subject.clone(),
+                issue: config.get_field(VALIDATION_EMPTY_CONDITION).to_string(),
+            });
+        }
+
+        if condition.has_unresolved_transition() {
+            issues.push(ValidationIssue {
+                section: NAVIGATION_CONDITIONS,
+                subject: subject,
+                issue: config.get_field(GENERATE_UNRESOLVED_TRANSITION).to_string(),
+            });
+        }
+    }
+
+    issues
+}
+
+// Read-only list of the issues, rebuilt from the model on every draw as the preview
+#[derive(Debug)]
+pub struct ValidationView {
+    scroll: scrollable::State,
+}
+
+impl<'a> ValidationView {
+    pub fn new() -> Self {
+        ValidationView {
+            scroll: scrollable::State::new(),
+        }
+    }
+
+    pub fn view<Message: 'a>(&'a mut self) -> Element<'a, Message> {
+        let issues = get_validation_issues();
+
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+
+        if issues.is_empty() {
+            return Column::new()
+                .width(Length::Fill)
+                .align_items(Align::Center)
+                .push(Text::new(config.get_field(VALIDATION_NO_ISSUES).to_string()))
+                .into()
+        }
+
+        let rows = issues.into_iter().fold(
+            Column::new().spacing(5),
+            |column, issue| {
+                column.push(Row::new()
+                    .spacing(DEFAULT_SPACING)
+                    .push(Text::new(config.get_field(issue.section).to_string())
+                        .size(16)
+                        .width(Length::Units(SUBJECT_WIDTH / 2)))
+                    .push(Text::new(issue.subject).size(16).width(Length::Units(SUBJECT_WIDTH)))
+                    .push(Text::new(issue.issue).size(16).color([0.8, 0.4, 0.0])))
+            }
+        );
+
+        Scrollable::new(&mut self.scroll)
+            .width(Length::Fill)
+            .padding(DEFAULT_PADDING)
+            .push(rows)
+            .into()
+    }
+}
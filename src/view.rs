@@ -1,29 +1,61 @@
 use iced::{
     button, Align, Button, Column, Container, Element, HorizontalAlignment,
-    Length, Text, Scrollable, scrollable, Space, Row
+    Length, Text, Scrollable, scrollable, Space, Row, pick_list, PickList, Checkbox,
+    TextInput, text_input
 };
 
 use crate::{configuration:: {
     language_pack_conastants::{
-        CREATE_NEW, LOAD_TABLE, BUTTON_ADD_NEW, IOCONFIG_EMPTY, BUTTON_GENERATE_TABLE, BUTTON_BACK, SUBPROGRAM_CONFIG_EMPTY, SUBPROGRAM_STEPS_EMPTY, CONDITIONS_CONFIG_EMPTY
+        CREATE_NEW, LOAD_TABLE, FIELD_DOCUMENT_LANGUAGE, LANGUAGE_PACK_ISSUES,
+        FIELD_BILINGUAL_HEADERS, BUTTON_SETTINGS, FIELD_DESCRIPTION, SUBPROGRAM_STEP,
+        NAVIGATION_IO_CONFIGURATION, NAVIGATION_SUBPROGRAMS, NAVIGATION_CONDITIONS,
+        NAVIGATION_GENERATE, NAVIGATION_PREVIEW, NAVIGATION_VALIDATION, NAVIGATION_SIMULATION, BUTTON_EDIT_DESCRIPTION, BUTTON_IMPORT_CSV, BUTTON_HARDWARE, BUTTON_ASSIGN_ADDRESSES, BUTTON_BULK_CREATE, BUTTON_ADD_NEW, IOCONFIG_EMPTY, BUTTON_GENERATE_TABLE, GENERATE_DONE, GENERATE_FAILED, FIELD_PROJECT_FILE, BUTTON_SAVE_PROJECT, PROJECT_SAVED, PROJECT_SAVE_FAILED, BUTTON_BACK, SUBPROGRAM_CONFIG_EMPTY, SUBPROGRAM_STEPS_EMPTY, CONDITIONS_CONFIG_EMPTY
     },
     style_config::{DEFAULT_PADDING, DEFAULT_SPACING, FONT_SIZE, self},
-    GLOBAL_CONFIG, FrameTypes
+    GLOBAL_CONFIG, FrameTypes, IOElementStates
 }, configs::{CONDTIONS_CONFIG, CondtionsConfigStetes}, generator::generate_tables};
 
-use crate::ioconfigview::{IOElementView};
+use crate::ioconfigview::{
+    IOElementView, IODeleteView, IOImportView, HardwareView, HwAssignView, IOBulkView
+};
+use crate::csvimport::IOImportMessage;
+use crate::hardware::HwAssignMessage;
+use crate::bulkcreate::IOBulkMessage;
+use crate::filterview::FilterView;
+use crate::listfilter::FilterMessage;
+use crate::previewview::{PreviewView, PreviewMessage};
+use crate::validationview::ValidationView;
+use crate::simulationview::{SimulationView, SimulationMessage};
 use crate::configs::{
-    SubprogramConfigMessage, SUBPROGRAMS_CONFIG,
+    SubprogramConfigMessage, SUBPROGRAMS_CONFIG, get_io_element_usages,
     SubprogramConfigStetes, IOConfigMessage, IO_CONFIG, IOElementMessage,
     SubprogramStepMessage, SubprogramMessage,
     IOElementCoditionsMessage, CondtionsConfigMessage,
-    ConditionsConfigElementMessage
+    ConditionsConfigElementMessage, move_element, move_element_to, IOElementCoditions
 };
+use crate::draglist::{self, DragList};
+use std::{rc::Rc, cell::RefCell};
 use crate::subprogramview::{
     SubprogramIOConditionsView, SubprogramDescriptionEditView,
     SubprogramView, SubprogramStepView
 };
 use crate::conditionsview::ConditonsElementView;
+use crate::matrixview::{ConditionsMatrixView, MatrixMessage};
+use crate::settingsview::{SettingsView, SettingsMessage, update_settings};
+use crate::projectview::{ProjectOpenView, ProjectMessage};
+use crate::project::save_project;
+use crate::tsv::{
+    io_element_row, subprogram_row, subprogram_step_row, condition_row, io_condition_row
+};
+
+// Keyboard actions on the focused row of the active list
+#[derive(Debug, Clone, Copy)]
+pub enum Shortcut {
+    AddNew,
+    DeleteFocused,
+    MoveFocus(isize, isize),
+    CycleFocused,
+}
 
 #[derive(Debug, Clone)]
 pub enum PresetViewMessage {
@@ -32,6 +64,19 @@ pub enum PresetViewMessage {
     IOConfigMessage(IOConfigMessage),
     SubprogramConfigMessage(SubprogramConfigMessage),
     CondtionsConfigMessage(CondtionsConfigMessage),
+    MatrixMessage(MatrixMessage),
+    Shortcut(Shortcut),
+    Paste(String),
+    FilterMessage(FilterMessage),
+    PreviewMessage(PreviewMessage),
+    SimulationMessage(SimulationMessage),
+    EditSubprogramDescription,
+    OpenSettings,
+    SettingsMessage(SettingsMessage),
+    OpenProject,
+    ProjectMessage(ProjectMessage),
+    ProjectPathChanged(String),
+    SaveProject,
     GenereteTable
 }
 
@@ -40,21 +85,40 @@ pub enum PresetViews {
     EntryView {
         create_new_button: button::State,
         load_table_button: button::State,
+        settings_button: button::State,
+        settings_view: Option<SettingsView>,
+        project_view: Option<ProjectOpenView>,
     },
     IOConfigView {
         scroll: scrollable::State,
         create_new_button: button::State,
         elements: Vec<IOElementView>,
+        delete_view: Option<IODeleteView>,
+        import_button: button::State,
+        import_view: Option<IOImportView>,
+        hardware_button: button::State,
+        hardware_view: Option<HardwareView>,
+        assign_button: button::State,
+        assign_view: Option<HwAssignView>,
+        bulk_button: button::State,
+        bulk_view: Option<IOBulkView>,
+        filter: FilterView,
+        focus: usize,
     },
     SubprogramConfigView {
         scroll: scrollable::State,
         create_new_button: button::State,
+        description_button: button::State,
         subprograms: Vec<SubprogramView>,
         state: SubprogramConfigStetes,
         subprogramsteps: Vec<SubprogramStepView>,
         conditions: Vec<SubprogramIOConditionsView>,
         conditions_type: FrameTypes,
-        subrogramedit_view: Option<SubprogramDescriptionEditView>
+        subrogramedit_view: Option<SubprogramDescriptionEditView>,
+        matrix: ConditionsMatrixView,
+        filter: FilterView,
+        focus: usize,
+        drag: draglist::State,
     },
     ConditionsConfigView {
         scroll: scrollable::State,
@@ -62,13 +126,129 @@ pub enum PresetViews {
         conditionsview: Vec<ConditonsElementView>,
         ioconditionsview: Vec<SubprogramIOConditionsView>,
         state: CondtionsConfigStetes,
-        frame_type: FrameTypes
+        frame_type: FrameTypes,
+        matrix: ConditionsMatrixView,
+        filter: FilterView,
+        focus: usize,
+        drag: draglist::State,
+    },
+    ValidationView {
+        validation: ValidationView,
+    },
+    SimulationView {
+        simulation: SimulationView,
+    },
+    PreviewView {
+        preview: PreviewView,
     },
     GenereteTableView {
         generete_table: button::State,
+        document_language_list: pick_list::State<String>,
+        project_input: text_input::State,
+        save_project: button::State,
+        // Path of the written table or the reason it was not written
+        result: Option<Result<String, String>>,
+        // Same for the project file
+        project_result: Option<Result<String, String>>,
+    }
+}
+
+fn subprogram_matrix_message(subprogram_id: usize, message: MatrixMessage) -> PresetViewMessage {
+    match message {
+        MatrixMessage::CellPressed(i, id, frame_type) => {
+            PresetViewMessage::SubprogramConfigMessage(
+                SubprogramConfigMessage::SubprogramMessage(
+                    subprogram_id,
+                    SubprogramMessage::SubprogramStepMessage(
+                        i, SubprogramStepMessage::CycleIOElementState(id, frame_type)
+                    )
+                )
+            )
+        },
+        _ => PresetViewMessage::MatrixMessage(message)
+    }
+}
+
+fn conditions_matrix_message(message: MatrixMessage) -> PresetViewMessage {
+    match message {
+        MatrixMessage::CellPressed(i, id, frame_type) => {
+            PresetViewMessage::CondtionsConfigMessage(
+                CondtionsConfigMessage::ConditionsConfigElementMessage(
+                    i, ConditionsConfigElementMessage::CycleIOElementState(id, frame_type)
+                )
+            )
+        },
+        _ => PresetViewMessage::MatrixMessage(message)
     }
 }
 
+fn subprogram_message(subprogram_id: usize, message: SubprogramMessage) -> PresetViewMessage {
+    PresetViewMessage::SubprogramConfigMessage(
+        SubprogramConfigMessage::SubprogramMessage(subprogram_id, message)
+    )
+}
+
+fn condition_message(condition_id: usize, message: ConditionsConfigElementMessage) -> PresetViewMessage {
+    PresetViewMessage::CondtionsConfigMessage(
+        CondtionsConfigMessage::ConditionsConfigElementMessage(condition_id, message)
+    )
+}
+
+fn next_io_element_state(state: IOElementStates) -> IOElementStates {
+    match state {
+        IOElementStates::Active => IOElementStates::Inactive,
+        IOElementStates::Inactive => IOElementStates::Any,
+        IOElementStates::Any => IOElementStates::Active,
+    }
+}
+
+fn focusable_row<'a, M: 'a>(row: Element<'a, M>, focused: bool) -> Element<'a, M> {
+    Container::new(row)
+        .padding(5)
+        .style(style_config::ListRow {focused: focused})
+        .into()
+}
+
+// Rows in the order picked by the filter, messages keep the index of the row in the model.
+// The row being dragged is highlighted as the focused one
+fn ordered_rows<'a, T, M: 'a>(
+    rows: &'a mut Vec<T>,
+    order: Vec<usize>,
+    focus: usize,
+    grabbed: Option<usize>,
+    view: impl Fn(usize, &'a mut T) -> Element<'a, M>
+) -> Column<'a, M> {
+    let mut rows: Vec<Option<&'a mut T>> = rows.iter_mut().map(Some).collect();
+
+    order.into_iter().enumerate().fold(Column::new().spacing(20), |column, (position, i)| {
+        match rows.get_mut(i).and_then(Option::take) {
+            Some(row) => column.push(
+                focusable_row(view(i, row), i == focus || Some(position) == grabbed)
+            ),
+            None => column
+        }
+    })
+}
+
+// Drag positions are counted in the displayed order, the model is moved by its own indices
+fn draggable_rows<'a, T, M: 'a>(
+    drag: &'a mut draglist::State,
+    rows: &'a mut Vec<T>,
+    order: Vec<usize>,
+    focus: usize,
+    view: impl Fn(usize, &'a mut T) -> Element<'a, M>,
+    on_move: impl 'a + Fn(usize, usize) -> M
+) -> Element<'a, M> {
+    let grabbed = drag.get_grabbed();
+    let positions = order.clone();
+
+    DragList::new(
+        drag,
+        ordered_rows(rows, order, focus, grabbed, view),
+        move |from, to| on_move(positions[from], positions[to])
+    ).into()
+}
+
 fn empty_message<'a>(message: &str) -> Element<'a, IOConfigMessage> {
     Container::new(
         Text::new(message)
@@ -88,64 +268,718 @@ impl <'a> PresetViews {
         match self {
            PresetViews::EntryView {
                 create_new_button,
-                load_table_button
+                load_table_button,
+                settings_button,
+                settings_view,
+                project_view
             } => {
-                Self::entry_view(create_new_button, load_table_button)
+                match (settings_view, project_view) {
+                    (Some(settings_view), _) => {
+                        Column::new()
+                            .width(Length::Fill)
+                            .padding(DEFAULT_PADDING)
+                            .align_items(Align::Center)
+                            .push(settings_view.view().map(PresetViewMessage::SettingsMessage))
+                    },
+                    (None, Some(project_view)) => {
+                        Column::new()
+                            .width(Length::Fill)
+                            .padding(DEFAULT_PADDING)
+                            .align_items(Align::Center)
+                            .push(project_view.view().map(PresetViewMessage::ProjectMessage))
+                    },
+                    (None, None) => {
+                        Self::entry_view(create_new_button, load_table_button, settings_button)
+                    }
+                }
             },
             PresetViews::IOConfigView {
                 scroll,
                 create_new_button,
-                elements
+                elements,
+                delete_view,
+                import_button,
+                import_view,
+                hardware_button,
+                hardware_view,
+                assign_button,
+                assign_view,
+                bulk_button,
+                bulk_view,
+                filter,
+                focus
             } => {
-                Column::new()
+                let order = filter.get_filter().io_elements_order(
+                    &unsafe {
+                        &IO_CONFIG
+                    }.as_ref().unwrap().borrow().get_all_elelments()
+                );
+                let is_list = delete_view.is_none() && import_view.is_none() &&
+                    hardware_view.is_none() && assign_view.is_none() && bulk_view.is_none();
+                let mut column = Column::new().align_items(Align::Center);
+
+                if is_list {
+                    column = column.push(filter.view().map(PresetViewMessage::FilterMessage));
+                }
+
+                column
                     .push(Self::ioconfig_view(
                             scroll,
                             create_new_button,
-                            elements
+                            elements,
+                            delete_view,
+                            import_button,
+                            import_view,
+                            hardware_button,
+                            hardware_view,
+                            assign_button,
+                            assign_view,
+                            bulk_button,
+                            bulk_view,
+                            order,
+                            *focus
                           ).map(PresetViewMessage::IOConfigMessage))
             },
             PresetViews::SubprogramConfigView {
                 ..
             } => {
                 Column::new()
-                    .push(Self::subrogram_view(self)
-                        .map(PresetViewMessage::SubprogramConfigMessage))
+                    .push(Self::subrogram_view(self))
             },
             PresetViews::ConditionsConfigView {
                 ..
             } => {
                 Column::new()
-                    .push(Self::conditions_view(self)
-                        .map(PresetViewMessage::CondtionsConfigMessage))
+                    .push(Self::conditions_view(self))
+            },
+            PresetViews::ValidationView {validation} => {
+                Column::new()
+                    .push(validation.view())
+            },
+            PresetViews::SimulationView {simulation} => {
+                Column::new()
+                    .push(simulation.view().map(PresetViewMessage::SimulationMessage))
+            },
+            PresetViews::PreviewView {preview} => {
+                Column::new()
+                    .push(preview.view().map(PresetViewMessage::PreviewMessage))
             },
             PresetViews::GenereteTableView {
-                generete_table
+                generete_table,
+                document_language_list,
+                project_input,
+                save_project,
+                result,
+                project_result
             } => {
-                Self::generete_table_view(generete_table)
+                Self::generete_table_view(
+                    generete_table, document_language_list, project_input, save_project,
+                    result, project_result
+                )
             }
 
         }
         .into()
     }
 
+    // Language field of the preset in the navigation sidebar
+    pub fn get_navigation_field(&self) -> Option<&'static str> {
+        match self {
+            PresetViews::EntryView {..} => None,
+            PresetViews::IOConfigView {..} => Some(NAVIGATION_IO_CONFIGURATION),
+            PresetViews::SubprogramConfigView {..} => Some(NAVIGATION_SUBPROGRAMS),
+            PresetViews::ConditionsConfigView {..} => Some(NAVIGATION_CONDITIONS),
+            PresetViews::ValidationView {..} => Some(NAVIGATION_VALIDATION),
+            PresetViews::SimulationView {..} => Some(NAVIGATION_SIMULATION),
+            PresetViews::PreviewView {..} => Some(NAVIGATION_PREVIEW),
+            PresetViews::GenereteTableView {..} => Some(NAVIGATION_GENERATE),
+        }
+    }
+
+    // Path of the nested editing state below the preset itself
+    pub fn get_breadcrumbs(&self) -> Vec<String> {
+        let config = unsafe {
+            &GLOBAL_CONFIG
+        }.as_ref().unwrap().borrow();
+
+        match self {
+            PresetViews::SubprogramConfigView {state, conditions_type, ..} => {
+                if let SubprogramConfigStetes::SubprogramConfigState = state {
+                    return vec![]
+                }
+
+                let subrogramconfig = unsafe {
+                    &SUBPROGRAMS_CONFIG
+                }.as_ref().unwrap().borrow();
+                let subprogram = subrogramconfig.get_current_editable_subprogram();
+                let (address, name, ..) = subprogram.borrow().get_data();
+                let mut breadcrumbs = vec![format!("{}. {}", address, name)];
+
+                match state {
+                    SubprogramConfigStetes::SubprogramStepConditonsPick => {
+                        let (step_id, ..) = subprogram.borrow()
+                            .get_current_editable_step().borrow()
+                            .get_data();
+
+                        breadcrumbs.push(format!(
+                            "{} {}: {}", config.get_field(SUBPROGRAM_STEP).to_string(),
+                            step_id, conditions_type
+                        ));
+                    },
+                    SubprogramConfigStetes::SubprogramEditDescription => {
+                        breadcrumbs.push(config.get_field(FIELD_DESCRIPTION).to_string());
+                    },
+                    _ => ()
+                }
+
+                breadcrumbs
+            },
+            PresetViews::ConditionsConfigView {state, frame_type, ..} => {
+                match state {
+                    CondtionsConfigStetes::IOConditonsPick => {
+                        let (description, ..) = unsafe {
+                            &CONDTIONS_CONFIG
+                        }.as_ref().unwrap().borrow()
+                            .get_current_editable_subprogram().borrow()
+                            .get_data();
+
+                        vec![format!("{}: {}", description, frame_type)]
+                    },
+                    _ => vec![]
+                }
+            },
+            _ => vec![]
+        }
+    }
+
+    // Returns to the given level of the breadcrumbs, 0 is the preset itself
+    pub fn select_breadcrumb(&mut self, level: usize) {
+        match self {
+            PresetViews::SubprogramConfigView {state, ..} => {
+                *state = match level {
+                    0 => SubprogramConfigStetes::SubprogramConfigState,
+                    _ => SubprogramConfigStetes::SubprogramEditState
+                };
+            },
+            PresetViews::ConditionsConfigView {state, ..} => {
+                *state = CondtionsConfigStetes::CondtionsConfigState;
+            },
+            PresetViews::EntryView {settings_view, project_view, ..} => {
+                *settings_view = None;
+                *project_view = None;
+            },
+            _ => ()
+        }
+    }
+
+    // Rebuilds the cached widgets after the model was replaced, e.g. by undo
+    pub fn reload(&mut self) {
+        match self {
+            PresetViews::IOConfigView {elements, delete_view, hardware_view, ..} => {
+                let ioconfig = unsafe {
+                    &IO_CONFIG
+                }.as_ref().unwrap();
+
+                *elements = ioconfig.borrow().get_all_elelments().into_iter()
+                    .map(IOElementView::new)
+                    .collect();
+                *delete_view = None;
+                if let Some(hardware_view) = hardware_view {
+                    hardware_view.reload();
+                }
+            },
+            PresetViews::SubprogramConfigView {
+                subprograms, subprogramsteps, state, conditions,
+                subrogramedit_view, ..
+            } => {
+                let subrogramconfig = unsafe {
+                    &SUBPROGRAMS_CONFIG
+                }.as_ref().unwrap().borrow();
+
+                *subprograms = subrogramconfig.get_subprograms().into_iter()
+                    .map(SubprogramView::new)
+                    .collect();
+                subprogramsteps.clear();
+                conditions.clear();
+                *subrogramedit_view = None;
+
+                match state {
+                    SubprogramConfigStetes::SubprogramConfigState => (),
+                    _ => {
+                        if subrogramconfig.get_current_editable_id() >= subprograms.len() {
+                            *state = SubprogramConfigStetes::SubprogramConfigState;
+                        } else {
+                            let subprogram = subrogramconfig.get_current_editable_subprogram();
+
+                            if let SubprogramConfigStetes::SubprogramStepConditonsPick = state {
+                                if subprogram.borrow().get_current_editable_step_id() >=
+                                    subprogram.borrow().get_steps_count() {
+                                    *state = SubprogramConfigStetes::SubprogramEditState;
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            PresetViews::ConditionsConfigView {
+                conditionsview, ioconditionsview, state, ..
+            } => {
+                let conditionsconfig = unsafe {
+                    &CONDTIONS_CONFIG
+                }.as_ref().unwrap().borrow();
+
+                *conditionsview = conditionsconfig.get_conditions().into_iter()
+                    .map(ConditonsElementView::new)
+                    .collect();
+                ioconditionsview.clear();
+
+                if conditionsconfig.get_current_editable_id() >= conditionsview.len() {
+                    *state = CondtionsConfigStetes::CondtionsConfigState;
+                }
+            },
+            _ => ()
+        }
+    }
+
+    // Conditions listed in the condition pick states
+    fn get_picked_conditions(&self) -> Vec<Rc<RefCell<IOElementCoditions>>> {
+        match self {
+            PresetViews::SubprogramConfigView {
+                state: SubprogramConfigStetes::SubprogramStepConditonsPick,
+                conditions_type, ..
+            } => {
+                unsafe {
+                    &SUBPROGRAMS_CONFIG
+                }.as_ref().unwrap().borrow()
+                    .get_current_editable_subprogram().borrow()
+                    .get_current_editable_step().borrow()
+                    .get_conditions(*conditions_type)
+            },
+            PresetViews::ConditionsConfigView {
+                state: CondtionsConfigStetes::IOConditonsPick,
+                frame_type, ..
+            } => {
+                unsafe {
+                    &CONDTIONS_CONFIG
+                }.as_ref().unwrap().borrow()
+                    .get_current_editable_subprogram().borrow()
+                    .get_conditions(*frame_type)
+            },
+            _ => vec![]
+        }
+    }
+
+    fn get_rows_count(&self) -> usize {
+        match self {
+            PresetViews::IOConfigView {elements, ..} => elements.len(),
+            PresetViews::SubprogramConfigView {state, subprograms, ..} => {
+                match state {
+                    SubprogramConfigStetes::SubprogramConfigState => subprograms.len(),
+                    SubprogramConfigStetes::SubprogramEditState => {
+                        unsafe {
+                            &SUBPROGRAMS_CONFIG
+                        }.as_ref().unwrap().borrow()
+                            .get_current_editable_subprogram().borrow()
+                            .get_steps_count()
+                    },
+                    SubprogramConfigStetes::SubprogramStepConditonsPick => {
+                        self.get_picked_conditions().len()
+                    },
+                    _ => 0
+                }
+            },
+            PresetViews::ConditionsConfigView {state, conditionsview, ..} => {
+                match state {
+                    CondtionsConfigStetes::CondtionsConfigState => conditionsview.len(),
+                    _ => self.get_picked_conditions().len()
+                }
+            },
+            _ => 0
+        }
+    }
+
+    fn get_filter_mut(&mut self) -> Option<&mut FilterView> {
+        match self {
+            PresetViews::IOConfigView {filter, ..} |
+            PresetViews::SubprogramConfigView {filter, ..} |
+            PresetViews::ConditionsConfigView {filter, ..} => Some(filter),
+            _ => None
+        }
+    }
+
+    // Rows of the active list in display order, the top level lists follow the filter
+    fn get_visible_rows(&self) -> Vec<usize> {
+        match self {
+            PresetViews::IOConfigView {filter, ..} => {
+                filter.get_filter().io_elements_order(
+                    &unsafe {
+                        &IO_CONFIG
+                    }.as_ref().unwrap().borrow().get_all_elelments()
+                )
+            },
+            PresetViews::SubprogramConfigView {
+                state: SubprogramConfigStetes::SubprogramConfigState, filter, ..
+            } => {
+                filter.get_filter().subprograms_order(
+                    &unsafe {
+                        &SUBPROGRAMS_CONFIG
+                    }.as_ref().unwrap().borrow().get_subprograms()
+                )
+            },
+            PresetViews::ConditionsConfigView {
+                state: CondtionsConfigStetes::CondtionsConfigState, filter, ..
+            } => {
+                filter.get_filter().conditions_order(
+                    &unsafe {
+                        &CONDTIONS_CONFIG
+                    }.as_ref().unwrap().borrow().get_conditions()
+                )
+            },
+            _ => (0..self.get_rows_count()).collect()
+        }
+    }
+
+    fn get_focus_mut(&mut self) -> Option<&mut usize> {
+        match self {
+            PresetViews::IOConfigView {focus, ..} |
+            PresetViews::SubprogramConfigView {focus, ..} |
+            PresetViews::ConditionsConfigView {focus, ..} => Some(focus),
+            _ => None
+        }
+    }
+
+    // Turns a shortcut into the message of the matching button of the focused row,
+    // rows hidden by the filter are left alone
+    fn resolve_shortcut(&self, shortcut: Shortcut) -> Option<PresetViewMessage> {
+        let visible = self.get_visible_rows();
+
+        match self {
+            PresetViews::SubprogramConfigView {
+                matrix, state: SubprogramConfigStetes::SubprogramEditState, ..
+            } |
+            PresetViews::ConditionsConfigView {
+                matrix, state: CondtionsConfigStetes::CondtionsConfigState, ..
+            } if matrix.is_active() => {
+                match shortcut {
+                    Shortcut::MoveFocus(rows, columns) => Some(PresetViewMessage::MatrixMessage(
+                        MatrixMessage::MoveCursor(rows, columns)
+                    )),
+                    Shortcut::CycleFocused => Some(self.resolve_matrix_message(
+                        PresetViewMessage::MatrixMessage(MatrixMessage::CycleCursor)
+                    )),
+                    _ => None
+                }
+            },
+            PresetViews::IOConfigView {
+                focus, delete_view: None, import_view: None, hardware_view: None,
+                assign_view: None, bulk_view: None, ..
+            } => {
+                match shortcut {
+                    Shortcut::AddNew => Some(PresetViewMessage::IOConfigMessage(
+                        IOConfigMessage::AddNewElement
+                    )),
+                    Shortcut::DeleteFocused if visible.contains(focus) => Some(PresetViewMessage::IOConfigMessage(
+                        IOConfigMessage::IOElementMessage(*focus, IOElementMessage::DeleteElement)
+                    )),
+                    _ => None
+                }
+            },
+            PresetViews::SubprogramConfigView {state, focus, conditions_type, ..} => {
+                let subrogramconfig = unsafe {
+                    &SUBPROGRAMS_CONFIG
+                }.as_ref().unwrap().borrow();
+                let subprogram_id = subrogramconfig.get_current_editable_id();
+                let step_message = |message| {
+                    let step_id = subrogramconfig.get_current_editable_subprogram().borrow()
+                        .get_current_editable_step_id();
+
+                    subprogram_message(
+                        subprogram_id, SubprogramMessage::SubprogramStepMessage(step_id, message)
+                    )
+                };
+
+                match (state, shortcut) {
+                    (SubprogramConfigStetes::SubprogramConfigState, Shortcut::AddNew) => {
+                        Some(PresetViewMessage::SubprogramConfigMessage(
+                            SubprogramConfigMessage::AddNewSubprogram
+                        ))
+                    },
+                    (SubprogramConfigStetes::SubprogramConfigState, Shortcut::DeleteFocused) if visible.contains(focus) => {
+                        Some(subprogram_message(*focus, SubprogramMessage::SubprogramDelete))
+                    },
+                    (SubprogramConfigStetes::SubprogramEditState, Shortcut::AddNew) => {
+                        Some(subprogram_message(subprogram_id, SubprogramMessage::AddNewSubprogramStep))
+                    },
+                    (SubprogramConfigStetes::SubprogramEditState, Shortcut::DeleteFocused) if visible.contains(focus) => {
+                        Some(subprogram_message(subprogram_id, SubprogramMessage::SubprogramStepMessage(
+                            *focus, SubprogramStepMessage::DeleteStep
+                        )))
+                    },
+                    (SubprogramConfigStetes::SubprogramStepConditonsPick, Shortcut::AddNew) => {
+                        Some(step_message(SubprogramStepMessage::AddCondition(*conditions_type)))
+                    },
+                    (SubprogramConfigStetes::SubprogramStepConditonsPick, Shortcut::DeleteFocused) if visible.contains(focus) => {
+                        Some(step_message(SubprogramStepMessage::IOElementCoditionsMessage(
+                            *focus, IOElementCoditionsMessage::DeleteElement(*conditions_type)
+                        )))
+                    },
+                    (SubprogramConfigStetes::SubprogramStepConditonsPick, Shortcut::CycleFocused) if visible.contains(focus) => {
+                        let (_, state, _) = self.get_picked_conditions()[*focus].borrow().get_data();
+
+                        Some(step_message(SubprogramStepMessage::IOElementCoditionsMessage(
+                            *focus, IOElementCoditionsMessage::StateChanged(next_io_element_state(state))
+                        )))
+                    },
+                    _ => None
+                }
+            },
+            PresetViews::ConditionsConfigView {state, focus, frame_type, ..} => {
+                let condition_id = unsafe {
+                    &CONDTIONS_CONFIG
+                }.as_ref().unwrap().borrow().get_current_editable_id();
+
+                match (state, shortcut) {
+                    (CondtionsConfigStetes::CondtionsConfigState, Shortcut::AddNew) => {
+                        Some(PresetViewMessage::CondtionsConfigMessage(
+                            CondtionsConfigMessage::AddNewConditons
+                        ))
+                    },
+                    (CondtionsConfigStetes::CondtionsConfigState, Shortcut::DeleteFocused) if visible.contains(focus) => {
+                        Some(condition_message(*focus, ConditionsConfigElementMessage::DeleteCondition))
+                    },
+                    (CondtionsConfigStetes::IOConditonsPick, Shortcut::AddNew) => {
+                        Some(condition_message(
+                            condition_id, ConditionsConfigElementMessage::AddCondition(*frame_type)
+                        ))
+                    },
+                    (CondtionsConfigStetes::IOConditonsPick, Shortcut::DeleteFocused) if visible.contains(focus) => {
+                        Some(condition_message(condition_id, ConditionsConfigElementMessage::IOElementCoditionsMessage(
+                            *focus, IOElementCoditionsMessage::DeleteElement(*frame_type)
+                        )))
+                    },
+                    (CondtionsConfigStetes::IOConditonsPick, Shortcut::CycleFocused) if visible.contains(focus) => {
+                        let (_, state, _) = self.get_picked_conditions()[*focus].borrow().get_data();
+
+                        Some(condition_message(condition_id, ConditionsConfigElementMessage::IOElementCoditionsMessage(
+                            *focus, IOElementCoditionsMessage::StateChanged(next_io_element_state(state))
+                        )))
+                    },
+                    _ => None
+                }
+            },
+            _ => None
+        }
+    }
+
+    // Rows of the active list as tab separated text, the focused one only unless all
+    pub fn get_tsv(&self, all: bool) -> Option<String> {
+        let rows: Vec<String> = match self {
+            PresetViews::IOConfigView {..} => {
+                unsafe {
+                    &IO_CONFIG
+                }.as_ref().unwrap().borrow().get_all_elelments().iter()
+                    .map(|element| io_element_row(&element.borrow()))
+                    .collect()
+            },
+            PresetViews::SubprogramConfigView {state, ..} => {
+                let subrogramconfig = unsafe {
+                    &SUBPROGRAMS_CONFIG
+                }.as_ref().unwrap().borrow();
+
+                match state {
+                    SubprogramConfigStetes::SubprogramConfigState => {
+                        subrogramconfig.get_subprograms().iter()
+                            .map(|subprogram| subprogram_row(&subprogram.borrow()))
+                            .collect()
+                    },
+                    SubprogramConfigStetes::SubprogramEditState => {
+                        let (.., steps) = subrogramconfig.get_current_editable_subprogram()
+                            .borrow().get_data();
+
+                        steps.iter()
+                            .map(|step| subprogram_step_row(&step.borrow()))
+                            .collect()
+                    },
+                    _ => self.get_picked_conditions().iter()
+                        .map(|condition| io_condition_row(&condition.borrow()))
+                        .collect()
+                }
+            },
+            PresetViews::ConditionsConfigView {state, ..} => {
+                match state {
+                    CondtionsConfigStetes::CondtionsConfigState => {
+                        unsafe {
+                            &CONDTIONS_CONFIG
+                        }.as_ref().unwrap().borrow().get_conditions().iter()
+                            .map(|condition| condition_row(&condition.borrow()))
+                            .collect()
+                    },
+                    _ => self.get_picked_conditions().iter()
+                        .map(|condition| io_condition_row(&condition.borrow()))
+                        .collect()
+                }
+            },
+            _ => vec![]
+        };
+
+        if all {
+            return Some(rows.join("\n")).filter(|text| !text.is_empty())
+        }
+
+        match self {
+            PresetViews::IOConfigView {focus, ..} |
+            PresetViews::SubprogramConfigView {focus, ..} |
+            PresetViews::ConditionsConfigView {focus, ..} => {
+                if self.get_visible_rows().contains(focus) {
+                    rows.get(*focus).cloned()
+                } else {
+                    None
+                }
+            },
+            _ => None
+        }
+    }
+
+    // Turns keyboard and clipboard actions into model messages
+    pub fn resolve_message(&self, message: PresetViewMessage) -> PresetViewMessage {
+        match (self, message) {
+            (_, PresetViewMessage::Shortcut(shortcut)) => {
+                self.resolve_shortcut(shortcut)
+                    .unwrap_or(PresetViewMessage::Shortcut(shortcut))
+            },
+            (
+                PresetViews::IOConfigView {
+                    delete_view: None, import_view: None, hardware_view: None,
+                    assign_view: None, bulk_view: None, ..
+                },
+                PresetViewMessage::Paste(text)
+            ) => {
+                PresetViewMessage::IOConfigMessage(IOConfigMessage::PasteElements(text))
+            },
+            (_, message) => self.resolve_matrix_message(message)
+        }
+    }
+
+    fn resolve_matrix_message(&self, message: PresetViewMessage) -> PresetViewMessage {
+
+        match (self, &message) {
+            (
+                PresetViews::SubprogramConfigView {
+                    matrix, state: SubprogramConfigStetes::SubprogramEditState, ..
+                },
+                PresetViewMessage::MatrixMessage(MatrixMessage::CycleCursor)
+            ) => {
+                let subprogram_id = unsafe {
+                    &SUBPROGRAMS_CONFIG
+                }.as_ref().unwrap().borrow().get_current_editable_id();
+
+                match matrix.get_cursor_cell() {
+                    Some((i, id, frame_type)) => subprogram_matrix_message(
+                        subprogram_id, MatrixMessage::CellPressed(i, id, frame_type)
+                    ),
+                    None => message
+                }
+            },
+            (
+                PresetViews::ConditionsConfigView {
+                    matrix, state: CondtionsConfigStetes::CondtionsConfigState, ..
+                },
+                PresetViewMessage::MatrixMessage(MatrixMessage::CycleCursor)
+            ) => {
+                match matrix.get_cursor_cell() {
+                    Some((i, id, frame_type)) => conditions_matrix_message(
+                        MatrixMessage::CellPressed(i, id, frame_type)
+                    ),
+                    None => message
+                }
+            },
+            _ => message
+        }
+    }
+
     pub fn update(&mut self, message: PresetViewMessage) {
+        if let PresetViewMessage::Shortcut(Shortcut::MoveFocus(rows, _)) = message {
+            let order = self.get_visible_rows();
+
+            if let Some(focus) = self.get_focus_mut() {
+                let position = match order.iter().position(|row| *row == *focus) {
+                    Some(position) => (position as isize + rows)
+                        .min(order.len() as isize - 1).max(0) as usize,
+                    None => 0
+                };
+
+                if let Some(row) = order.get(position) {
+                    *focus = *row;
+                }
+            }
+            return;
+        }
+
+        if let PresetViewMessage::FilterMessage(message) = message {
+            if let Some(filter) = self.get_filter_mut() {
+                filter.update(message);
+            }
+
+            // The focused row may be filtered out, the first visible one takes over
+            let visible = self.get_visible_rows();
+
+            if let (Some(focus), Some(first)) = (self.get_focus_mut(), visible.first()) {
+                if !visible.contains(focus) {
+                    *focus = *first;
+                }
+            }
+            return;
+        }
+
         match self {
-            PresetViews::EntryView {..} => Self::entry_view_update(message),
-            PresetViews::IOConfigView {elements, ..} => Self::ioconfig_view_update(elements, message),
+            PresetViews::EntryView {settings_view, project_view, ..} => {
+                Self::entry_view_update(settings_view, project_view, message)
+            },
+            PresetViews::IOConfigView {
+                elements, delete_view, import_view, hardware_view, assign_view, bulk_view, ..
+            } => Self::ioconfig_view_update(
+                elements, delete_view, import_view, hardware_view, assign_view, bulk_view, message
+            ),
             PresetViews::SubprogramConfigView {..} => Self::subprogram_view_update(self, message),
             PresetViews::ConditionsConfigView {..} => Self::conditions_view_update(self, message),
-            PresetViews::GenereteTableView {..} => Self::generete_table_view_update(message)
+            PresetViews::ValidationView {..} => {},
+            PresetViews::SimulationView {simulation} => {
+                if let PresetViewMessage::SimulationMessage(message) = message {
+                    simulation.update(message)
+                }
+            },
+            PresetViews::PreviewView {preview} => {
+                if let PresetViewMessage::PreviewMessage(message) = message {
+                    preview.update(message)
+                }
+            },
+            PresetViews::GenereteTableView {result, project_result, ..} => {
+                Self::generete_table_view_update(result, project_result, message)
+            }
         }
     }
 
     fn entry_view(
         create_new_button: &'a mut button::State,
-        load_table_button: &'a mut button::State
+        load_table_button: &'a mut button::State,
+        settings_button: &'a mut button::State
     ) -> Column<'a, PresetViewMessage> {
         let config = unsafe {
             &GLOBAL_CONFIG
-        }.as_ref().unwrap();
-        Column::new()
+        }.as_ref().unwrap().borrow();
+
+        let reports = config.get_language_pack_reports();
+        let issues = reports.iter().fold(
+            Column::new()
+                .spacing(5)
+                .push(Text::new(config.get_field(LANGUAGE_PACK_ISSUES).to_string())
+                    .color([0.8, 0.4, 0.0])),
+            |column, report| {
+                column.push(Text::new(report.to_string()).size(16).color([0.8, 0.4, 0.0]))
+            }
+        );
+
+        let content = Column::new()
             .width(Length::Fill)
             .height(Length::Fill)
             .spacing(DEFAULT_SPACING)
@@ -162,20 +996,94 @@ impl <'a> PresetViews {
                                         .to_string().as_str())
                               .size(FONT_SIZE))
                 .style(style_config::Button::Primary)
-                .on_press(PresetViewMessage::NextPresset))
+                .on_press(PresetViewMessage::OpenProject))
+            .push(Button::new(settings_button,
+                              Text::new(config.get_field(BUTTON_SETTINGS)
+                                        .to_string().as_str()))
+                .style(style_config::Button::Secondary)
+                .on_press(PresetViewMessage::OpenSettings));
+
+        if reports.is_empty() {
+            content
+        } else {
+            content.push(issues)
+        }
     }
 
-    fn entry_view_update(message: PresetViewMessage) {
-        //TODO
+    fn entry_view_update(
+        settings_view: &'a mut Option<SettingsView>,
+        project_view: &'a mut Option<ProjectOpenView>,
+        message: PresetViewMessage
+    ) {
+        match message {
+            PresetViewMessage::OpenProject => {
+                *project_view = Some(ProjectOpenView::new());
+            },
+            PresetViewMessage::ProjectMessage(ProjectMessage::Close) => {
+                *project_view = None;
+            },
+            PresetViewMessage::ProjectMessage(project_message) => {
+                if let Some(project_view) = project_view {
+                    project_view.update(project_message);
+                }
+            },
+            PresetViewMessage::OpenSettings => {
+                *settings_view = Some(SettingsView::new());
+            },
+            PresetViewMessage::SettingsMessage(SettingsMessage::Close) => {
+                if settings_view.as_mut().map_or(true, SettingsView::save) {
+                    *settings_view = None;
+                }
+            },
+            PresetViewMessage::SettingsMessage(settings_message) => {
+                update_settings(settings_message);
+            },
+            _ => {}
+        }
     }
 
     fn generete_table_view(
         generate_button: &'a mut button::State,
+        document_language_list: &'a mut pick_list::State<String>,
+        project_input: &'a mut text_input::State,
+        save_project: &'a mut button::State,
+        result: &Option<Result<String, String>>,
+        project_result: &Option<Result<String, String>>
     ) -> Column<'a, PresetViewMessage> {
         let config = unsafe {
             &GLOBAL_CONFIG
-        }.as_ref().unwrap();
-        Column::new()
+        }.as_ref().unwrap().borrow();
+
+        let document_language_list = PickList::new(
+            document_language_list,
+            config.get_language_packs(),
+            Some(config.get_document_language_pack()),
+            |language| PresetViewMessage::SettingsMessage(
+                SettingsMessage::DocumentLanguageSelected(language)
+            )
+        ).style(style_config::PickList);
+
+        let bilingual_check = Checkbox::new(
+            config.is_bilingual_headers(),
+            config.get_field(FIELD_BILINGUAL_HEADERS).to_string().as_str(),
+            |bilingual| PresetViewMessage::SettingsMessage(
+                SettingsMessage::BilingualHeadersToggled(bilingual)
+            )
+        ).style(style_config::Checkbox);
+
+        let project_input = TextInput::new(
+            project_input,
+            "", config.get_project_path().as_str(), PresetViewMessage::ProjectPathChanged
+        ).size(30).width(Length::Units(500)).style(style_config::TextInput)
+            .on_submit(PresetViewMessage::SaveProject);
+
+        let save_project = Button::new(
+            save_project,
+            Text::new(config.get_field(BUTTON_SAVE_PROJECT).to_string())
+        ).style(style_config::Button::Secondary)
+            .on_press(PresetViewMessage::SaveProject);
+
+        let mut content = Column::new()
             .width(Length::Fill)
             .height(Length::Fill)
             .spacing(DEFAULT_SPACING)
@@ -187,12 +1095,72 @@ impl <'a> PresetViews {
                               .size(FONT_SIZE))
                 .style(style_config::Button::Primary)
                 .on_press(PresetViewMessage::GenereteTable))
+            .push(Row::new()
+                .spacing(DEFAULT_SPACING)
+                .align_items(Align::Center)
+                .push(Text::new(config.get_field(FIELD_DOCUMENT_LANGUAGE).to_string()))
+                .push(document_language_list)
+                .push(bilingual_check));
+
+        content = match result {
+            Some(Ok(path)) => content.push(Text::new(format!(
+                "{} {}", config.get_field(GENERATE_DONE), path
+            ))),
+            Some(Err(error)) => content.push(Text::new(format!(
+                "{}: {}", config.get_field(GENERATE_FAILED), error
+            )).color([0.8, 0.4, 0.0])),
+            None => content
+        };
+
+        content = content.push(Row::new()
+            .spacing(DEFAULT_SPACING)
+            .align_items(Align::Center)
+            .push(Text::new(config.get_field(FIELD_PROJECT_FILE).to_string()))
+            .push(project_input)
+            .push(save_project));
+
+        match project_result {
+            Some(Ok(path)) => content.push(Text::new(format!(
+                "{} {}", config.get_field(PROJECT_SAVED), path
+            ))),
+            Some(Err(error)) => content.push(Text::new(format!(
+                "{}: {}", config.get_field(PROJECT_SAVE_FAILED), error
+            )).color([0.8, 0.4, 0.0])),
+            None => content
+        }
     }
 
-    fn generete_table_view_update(message: PresetViewMessage) {
+    fn generete_table_view_update(
+        result: &mut Option<Result<String, String>>,
+        project_result: &mut Option<Result<String, String>>,
+        message: PresetViewMessage
+    ) {
         match message {
             PresetViewMessage::GenereteTable => {
-                generate_tables();
+                *result = Some(generate_tables().map_err(|error| error.to_string()));
+            },
+            PresetViewMessage::ProjectPathChanged(path) => {
+                unsafe {
+                    &GLOBAL_CONFIG
+                }.as_ref().unwrap().borrow_mut().set_project_path(path);
+            },
+            PresetViewMessage::SaveProject => {
+                let path = unsafe {
+                    &GLOBAL_CONFIG
+                }.as_ref().unwrap().borrow().get_project_path();
+
+                *project_result = Some(save_project(path.as_str())
+                    .map(|_| path)
+                    .map_err(|error| error.to_string()));
+
+                if let Some(Ok(path)) = project_result {
+                    unsafe {
+                        &GLOBAL_CONFIG
+                    }.as_ref().unwrap().borrow_mut().add_recent_project(path.clone());
+                }
+            },
+            PresetViewMessage::SettingsMessage(settings_message) => {
+                update_settings(settings_message);
             },
             _ => {}
         }
@@ -201,11 +1169,67 @@ impl <'a> PresetViews {
     fn ioconfig_view(
         scroll: &'a mut scrollable::State,
         create_new_button: &'a mut button::State,
-        elements: &'a mut Vec<IOElementView>
+        elements: &'a mut Vec<IOElementView>,
+        delete_view: &'a mut Option<IODeleteView>,
+        import_button: &'a mut button::State,
+        import_view: &'a mut Option<IOImportView>,
+        hardware_button: &'a mut button::State,
+        hardware_view: &'a mut Option<HardwareView>,
+        assign_button: &'a mut button::State,
+        assign_view: &'a mut Option<HwAssignView>,
+        bulk_button: &'a mut button::State,
+        bulk_view: &'a mut Option<IOBulkView>,
+        order: Vec<usize>,
+        focus: usize
     ) -> Element<'a, IOConfigMessage> {
+        if let Some(delete_view) = delete_view {
+            return Column::new()
+                .width(Length::Fill)
+                .align_items(Align::Center)
+                .padding(DEFAULT_PADDING)
+                .push(delete_view.view())
+            .into()
+        }
+
+        if let Some(import_view) = import_view {
+            return Column::new()
+                .width(Length::Fill)
+                .align_items(Align::Center)
+                .padding(DEFAULT_PADDING)
+                .push(import_view.view())
+            .into()
+        }
+
+        if let Some(hardware_view) = hardware_view {
+            return Column::new()
+                .width(Length::Fill)
+                .align_items(Align::Center)
+                .padding(DEFAULT_PADDING)
+                .push(hardware_view.view())
+            .into()
+        }
+
+        if let Some(assign_view) = assign_view {
+            return Column::new()
+                .width(Length::Fill)
+                .align_items(Align::Center)
+                .padding(DEFAULT_PADDING)
+                .push(assign_view.view())
+            .into()
+        }
+
+        if let Some(bulk_view) = bulk_view {
+            return Column::new()
+                .width(Length::Fill)
+                .align_items(Align::Center)
+                .padding(DEFAULT_PADDING)
+                .push(bulk_view.view())
+            .into()
+        }
+
         let config = unsafe {
             &GLOBAL_CONFIG
-        }.as_ref().unwrap();
+        }.as_ref().unwrap().borrow();
         let add_new = Column::new()
                   .align_items(Align::Center)
                   .width(Length::Fill)
@@ -214,17 +1238,30 @@ impl <'a> PresetViews {
                                         .to_string().as_str())
                               .size(FONT_SIZE))
                         .style(style_config::Button::Primary)
-                        .on_press(IOConfigMessage::AddNewElement));
+                        .on_press(IOConfigMessage::AddNewElement))
+                  .push(Button::new(import_button,
+                              Text::new(config.get_field(BUTTON_IMPORT_CSV).to_string()))
+                        .style(style_config::Button::Secondary)
+                        .on_press(IOConfigMessage::OpenImport))
+                  .push(Button::new(bulk_button,
+                              Text::new(config.get_field(BUTTON_BULK_CREATE).to_string()))
+                        .style(style_config::Button::Secondary)
+                        .on_press(IOConfigMessage::OpenBulk))
+                  .push(Button::new(hardware_button,
+                              Text::new(config.get_field(BUTTON_HARDWARE).to_string()))
+                        .style(style_config::Button::Secondary)
+                        .on_press(IOConfigMessage::OpenHardware))
+                  .push(Button::new(assign_button,
+                              Text::new(config.get_field(BUTTON_ASSIGN_ADDRESSES).to_string()))
+                        .style(style_config::Button::Secondary)
+                        .on_press(IOConfigMessage::OpenAssign));
 
         let elements_view: Element<_> = if elements.len() > 0 {
-                elements
-                    .iter_mut()
-                    .enumerate()
-                    .fold(Column::new().spacing(20), |column, (i, element)| {
-                        column.push(element.view().map(move |message| {
-                            IOConfigMessage::IOElementMessage(i, message)
-                        }))
+                ordered_rows(elements, order, focus, None, |i, element| {
+                    element.view().map(move |message| {
+                        IOConfigMessage::IOElementMessage(i, message)
                     })
+                })
                 .into()
         } else {
             empty_message(config.get_field(IOCONFIG_EMPTY).to_string().as_str())
@@ -251,6 +1288,11 @@ impl <'a> PresetViews {
 
     fn ioconfig_view_update(
         elements: &'a mut Vec<IOElementView>,
+        delete_view: &'a mut Option<IODeleteView>,
+        import_view: &'a mut Option<IOImportView>,
+        hardware_view: &'a mut Option<HardwareView>,
+        assign_view: &'a mut Option<HwAssignView>,
+        bulk_view: &'a mut Option<IOBulkView>,
         message: PresetViewMessage
     ) {
         match message {
@@ -259,6 +1301,81 @@ impl <'a> PresetViews {
                     &IO_CONFIG
                 }.as_ref().unwrap();
 
+                match ioconfig_message {
+                    IOConfigMessage::IOElementMessage(i, IOElementMessage::DeleteElement) => {
+                        let element = ioconfig.borrow().get_elemnt_by_id(i);
+                        let usages = get_io_element_usages(element.borrow().get_uid());
+
+                        if usages.len() > 0 {
+                            *delete_view = Some(IODeleteView::new(i, element, usages));
+                            return;
+                        }
+                    },
+                    IOConfigMessage::ReassignElementSelected(_) => {
+                        if let Some(delete_view) = delete_view {
+                            delete_view.update(ioconfig_message);
+                        }
+                        return;
+                    },
+                    IOConfigMessage::OpenImport => {
+                        *import_view = Some(IOImportView::new());
+                        return;
+                    },
+                    IOConfigMessage::OpenHardware => {
+                        *hardware_view = Some(HardwareView::new());
+                        return;
+                    },
+                    IOConfigMessage::CloseHardware => {
+                        *hardware_view = None;
+                        return;
+                    },
+                    IOConfigMessage::OpenBulk => {
+                        *bulk_view = Some(IOBulkView::new());
+                        return;
+                    },
+                    IOConfigMessage::BulkMessage(IOBulkMessage::Cancel) => {
+                        *bulk_view = None;
+                        return;
+                    },
+                    IOConfigMessage::BulkMessage(bulk_message) => {
+                        if let Some(bulk_view) = bulk_view {
+                            bulk_view.update(bulk_message);
+                        }
+                        return;
+                    },
+                    IOConfigMessage::OpenAssign => {
+                        *assign_view = Some(HwAssignView::new());
+                        return;
+                    },
+                    IOConfigMessage::AssignMessage(HwAssignMessage::Close) => {
+                        *assign_view = None;
+                        return;
+                    },
+                    IOConfigMessage::AssignMessage(assign_message) => {
+                        if let Some(assign_view) = assign_view {
+                            assign_view.update(assign_message);
+                        }
+                        return;
+                    },
+                    // The report shows the addresses before they are overwritten
+                    IOConfigMessage::AssignHwAddresses(keep) => {
+                        if let Some(assign_view) = assign_view {
+                            assign_view.set_report(ioconfig.borrow().plan_hw_addresses(keep));
+                        }
+                    },
+                    IOConfigMessage::ImportMessage(IOImportMessage::Cancel) => {
+                        *import_view = None;
+                        return;
+                    },
+                    IOConfigMessage::ImportMessage(import_message) => {
+                        if let Some(import_view) = import_view {
+                            import_view.update(import_message);
+                        }
+                        return;
+                    },
+                    _ => ()
+                }
+
                 ioconfig.borrow_mut().update(ioconfig_message.clone());
 
                 match ioconfig_message {
@@ -271,12 +1388,34 @@ impl <'a> PresetViews {
                             }
                         }
                     },
+                    IOConfigMessage::DeleteElementConfirmed(i) |
+                    IOConfigMessage::DeleteElementReassigned(i, _) => {
+                        elements.remove(i);
+                        *delete_view = None;
+                    },
+                    IOConfigMessage::DeleteElementCanceled => {
+                        *delete_view = None;
+                    },
                     IOConfigMessage::AddNewElement => {
                         elements.push(IOElementView::new(
                                         unsafe{&IO_CONFIG}.as_ref().unwrap()
                                         .borrow().get_last_element()
                                     ))
-                    }
+                    },
+                    IOConfigMessage::PasteElements(_) |
+                    IOConfigMessage::ImportElements(..) => {
+                        *elements = ioconfig.borrow().get_all_elelments().into_iter()
+                            .map(IOElementView::new)
+                            .collect();
+                        *import_view = None;
+                        *bulk_view = None;
+                    },
+                    IOConfigMessage::HardwareMessage(_) => {
+                        if let Some(hardware_view) = hardware_view {
+                            hardware_view.reload();
+                        }
+                    },
+                    _ => ()
                 }
             },
             _ => ()
@@ -285,23 +1424,40 @@ impl <'a> PresetViews {
 
     fn subrogram_view(
         view: &'a mut PresetViews
-    ) -> Element<'a, SubprogramConfigMessage> {
+    ) -> Element<'a, PresetViewMessage> {
         match view {
             PresetViews::SubprogramConfigView {
                 scroll,
                 create_new_button,
+                description_button,
                 subprograms,
                 subprogramsteps,
                 state,
                 conditions,
                 conditions_type,
-                subrogramedit_view
+                subrogramedit_view,
+                matrix,
+                filter,
+                focus,
+                drag
             } => {
                 match state {
                     SubprogramConfigStetes::SubprogramConfigState => {
                         subprogramsteps.clear();
 
-                        Self::subrogramconfig_view(scroll, create_new_button, subprograms)
+                        let order = filter.get_filter().subprograms_order(
+                            &unsafe {
+                                &SUBPROGRAMS_CONFIG
+                            }.as_ref().unwrap().borrow().get_subprograms()
+                        );
+
+                        Column::new()
+                            .align_items(Align::Center)
+                            .push(filter.view().map(PresetViewMessage::FilterMessage))
+                            .push(Self::subrogramconfig_view(
+                                    scroll, create_new_button, drag, subprograms, order, *focus
+                                  ).map(PresetViewMessage::SubprogramConfigMessage))
+                            .into()
                     },
                     SubprogramConfigStetes::SubprogramEditState => {
                         let subrogramconfig = unsafe {
@@ -311,20 +1467,52 @@ impl <'a> PresetViews {
 
                         conditions.clear();
 
-                        if 0 == subprogramsteps.len() {
-                            let (_, _, _, steps) = subrogramconfig.borrow()
-                                .get_current_editable_subprogram().borrow()
-                                .get_data();
+                        let (_, _, _, steps) = subrogramconfig.borrow()
+                            .get_current_editable_subprogram().borrow()
+                            .get_data();
+
+                        if matrix.is_active() {
+                            let rows = steps.iter().map(|step| {
+                                let (step_id, _, states, controls, description) =
+                                    step.borrow().get_data();
 
+                                (format!("{}. {}", step_id, description), states, controls)
+                            }).collect();
+
+                            return matrix.view(rows)
+                                .map(move |message| subprogram_matrix_message(id, message))
+                        }
+
+                        if 0 == subprogramsteps.len() {
                             for step in steps {
                                 subprogramsteps.push(SubprogramStepView::new(step))
                             }
                         }
 
-                        Self::subprogrameditor_view(scroll, create_new_button, subprogramsteps)
-                            .map(move |message| {
-                                SubprogramConfigMessage::SubprogramMessage(id, message)
-                            })
+                        let config = unsafe {
+                            &GLOBAL_CONFIG
+                        }.as_ref().unwrap().borrow();
+
+                        let description_button = Button::new(
+                            description_button,
+                            Text::new(config.get_field(BUTTON_EDIT_DESCRIPTION).to_string())
+                        ).style(style_config::Button::Secondary)
+                            .on_press(PresetViewMessage::EditSubprogramDescription);
+
+                        Column::new()
+                            .width(Length::Fill)
+                            .align_items(Align::Center)
+                            .push(Row::new()
+                                .spacing(DEFAULT_SPACING)
+                                .push(matrix.toggle_view().map(PresetViewMessage::MatrixMessage))
+                                .push(description_button))
+                            .push(Self::subprogrameditor_view(scroll, create_new_button, drag, subprogramsteps, *focus)
+                                .map(move |message| {
+                                    PresetViewMessage::SubprogramConfigMessage(
+                                        SubprogramConfigMessage::SubprogramMessage(id, message)
+                                    )
+                                }))
+                            .into()
                     },
                     SubprogramConfigStetes::SubprogramStepConditonsPick => {
                         let subrogramconfig = unsafe {
@@ -349,7 +1537,7 @@ impl <'a> PresetViews {
 
                         Self::subprogramconditons_view(
                             scroll, create_new_button, conditions,
-                            *conditions_type
+                            *conditions_type, *focus
                         )
                         .map(move |message| {
                             SubprogramConfigMessage::SubprogramMessage(
@@ -357,6 +1545,7 @@ impl <'a> PresetViews {
                                 SubprogramMessage::SubprogramStepMessage(step_id, message)
                             )
                         })
+                        .map(PresetViewMessage::SubprogramConfigMessage)
                     },
                     SubprogramConfigStetes::SubprogramEditDescription => {
                         let subrogramconfig = unsafe {
@@ -389,6 +1578,7 @@ impl <'a> PresetViews {
                             .map(move |message| {
                                 SubprogramConfigMessage::SubprogramMessage(subprogram_id, message)
                             })
+                            .map(PresetViewMessage::SubprogramConfigMessage)
                     },
 
                     _ => Column::new().into()
@@ -401,11 +1591,14 @@ impl <'a> PresetViews {
     fn subrogramconfig_view(
         scroll: &'a mut scrollable::State,
         create_new_button: &'a mut button::State,
-        elements: &'a mut Vec<SubprogramView>
+        drag: &'a mut draglist::State,
+        elements: &'a mut Vec<SubprogramView>,
+        order: Vec<usize>,
+        focus: usize
     ) -> Element<'a, SubprogramConfigMessage> {
         let config = unsafe {
             &GLOBAL_CONFIG
-        }.as_ref().unwrap();
+        }.as_ref().unwrap().borrow();
 
         let add_new = Column::new()
                   .align_items(Align::Center)
@@ -418,15 +1611,15 @@ impl <'a> PresetViews {
                         .on_press(SubprogramConfigMessage::AddNewSubprogram));
 
         let elements_view: Element<_> = if elements.len() > 0 {
-                elements
-                    .iter_mut()
-                    .enumerate()
-                    .fold(Column::new().spacing(20), |column, (i, element)| {
-                        column.push(element.view().map(move |message| {
-                            SubprogramConfigMessage::SubprogramMessage(i, message)
-                        }))
+                draggable_rows(drag, elements, order, focus, |i, element| {
+                    element.view().map(move |message| {
+                        SubprogramConfigMessage::SubprogramMessage(i, message)
                     })
-                .into()
+                }, |from, to| {
+                    SubprogramConfigMessage::SubprogramMessage(
+                        from, SubprogramMessage::SubprogramMoveTo(to)
+                    )
+                })
         } else {
             Container::new(
                 Text::new(config.get_field(SUBPROGRAM_CONFIG_EMPTY).to_string().as_str())
@@ -465,6 +1658,17 @@ impl <'a> PresetViews {
         message: PresetViewMessage,
     ) {
         match message {
+            PresetViewMessage::MatrixMessage(matrix_message) => {
+                if let PresetViews::SubprogramConfigView {matrix, subprogramsteps, ..} = view {
+                    matrix.update(matrix_message);
+                    subprogramsteps.clear();
+                }
+            },
+            PresetViewMessage::EditSubprogramDescription => {
+                if let PresetViews::SubprogramConfigView {state, ..} = view {
+                    *state = SubprogramConfigStetes::SubprogramEditDescription;
+                }
+            },
             PresetViewMessage::SubprogramConfigMessage(subprogramconfig_message) => {
                 let subrogramconfig = unsafe {
                     &SUBPROGRAMS_CONFIG
@@ -524,6 +1728,18 @@ impl <'a> PresetViews {
                     SubprogramMessage::SubprogramDelete => {
                         elements.remove(i);
                     },
+                    SubprogramMessage::SubprogramMove(direction) => {
+                        move_element(elements, i, direction);
+                    },
+                    SubprogramMessage::SubprogramMoveTo(target) => {
+                        move_element_to(elements, i, target);
+                    },
+                    SubprogramMessage::SubprogramDuplicate => {
+                        elements.insert(i + 1, SubprogramView::new(
+                                unsafe{&SUBPROGRAMS_CONFIG}.as_ref().unwrap()
+                                .borrow().get_subprogram(i + 1)
+                            ))
+                    },
                     SubprogramMessage::SubprogramEdit => {
                         *state = SubprogramConfigStetes::SubprogramEditState;
                     },
@@ -544,11 +1760,13 @@ impl <'a> PresetViews {
     fn subprogrameditor_view(
         scroll: &'a mut scrollable::State,
         create_new_button: &'a mut button::State,
-        elements: &'a mut Vec<SubprogramStepView>
+        drag: &'a mut draglist::State,
+        elements: &'a mut Vec<SubprogramStepView>,
+        focus: usize
     ) -> Element<'a, SubprogramMessage> {
         let config = unsafe {
             &GLOBAL_CONFIG
-        }.as_ref().unwrap();
+        }.as_ref().unwrap().borrow();
 
 
         let add_new = Column::new()
@@ -562,15 +1780,17 @@ impl <'a> PresetViews {
                         .on_press(SubprogramMessage::AddNewSubprogramStep));
 
         let elements_view: Element<_> = if elements.len() > 0 {
-                elements
-                    .iter_mut()
-                    .enumerate()
-                    .fold(Column::new().spacing(20), |column, (i, element)| {
-                        column.push(element.view().map(move |message| {
-                            SubprogramMessage::SubprogramStepMessage(i, message)
-                        }))
+                let order = (0..elements.len()).collect();
+
+                draggable_rows(drag, elements, order, focus, |i, element| {
+                    element.view().map(move |message| {
+                        SubprogramMessage::SubprogramStepMessage(i, message)
                     })
-                .into()
+                }, |from, to| {
+                    SubprogramMessage::SubprogramStepMessage(
+                        from, SubprogramStepMessage::MoveStepTo(to)
+                    )
+                })
         } else {
             Container::new(
                 Text::new(config.get_field(SUBPROGRAM_STEPS_EMPTY).to_string().as_str())
@@ -616,6 +1836,18 @@ impl <'a> PresetViews {
                     SubprogramStepMessage::DeleteStep => {
                         elements.remove(i);
                     },
+                    SubprogramStepMessage::MoveStep(direction) => {
+                        move_element(elements, i, direction);
+                    },
+                    SubprogramStepMessage::MoveStepTo(target) => {
+                        move_element_to(elements, i, target);
+                    },
+                    SubprogramStepMessage::DuplicateStep => {
+                        elements.insert(i + 1, SubprogramStepView::new(
+                                unsafe{&SUBPROGRAMS_CONFIG}.as_ref().unwrap()
+                                .borrow().get_current_editable_subprogram().borrow().get_step(i + 1)
+                            ))
+                    },
                     SubprogramStepMessage::PickConditions(frame_type) => {
                         *state = SubprogramConfigStetes::SubprogramStepConditonsPick;
                         *conditions_type = frame_type;
@@ -643,10 +1875,11 @@ impl <'a> PresetViews {
         create_new_button: &'a mut button::State,
         elements: &'a mut Vec<SubprogramIOConditionsView>,
         conditions_type: FrameTypes,
+        focus: usize
     ) -> Element<'a, SubprogramStepMessage> {
         let config = unsafe {
             &GLOBAL_CONFIG
-        }.as_ref().unwrap();
+        }.as_ref().unwrap().borrow();
 
 
         let add_new = Column::new()
@@ -664,9 +1897,9 @@ impl <'a> PresetViews {
                     .iter_mut()
                     .enumerate()
                     .fold(Column::new().spacing(20), |column, (i, element)| {
-                        column.push(element.view().map(move |message| {
+                        column.push(focusable_row(element.view().map(move |message| {
                             SubprogramStepMessage::IOElementCoditionsMessage(i, message)
-                        }))
+                        }), i == focus))
                     })
                 .into()
         } else {
@@ -742,10 +1975,11 @@ impl <'a> PresetViews {
         create_new_button: &'a mut button::State,
         elements: &'a mut Vec<SubprogramIOConditionsView>,
         conditions_type: FrameTypes,
+        focus: usize
     ) -> Element<'a, ConditionsConfigElementMessage> {
         let config = unsafe {
             &GLOBAL_CONFIG
-        }.as_ref().unwrap();
+        }.as_ref().unwrap().borrow();
 
 
         let add_new = Column::new()
@@ -763,9 +1997,9 @@ impl <'a> PresetViews {
                     .iter_mut()
                     .enumerate()
                     .fold(Column::new().spacing(20), |column, (i, element)| {
-                        column.push(element.view().map(move |message| {
+                        column.push(focusable_row(element.view().map(move |message| {
                             ConditionsConfigElementMessage::IOElementCoditionsMessage(i, message)
-                        }))
+                        }), i == focus))
                     })
                 .into()
         } else {
@@ -804,11 +2038,14 @@ impl <'a> PresetViews {
     fn conditions_config_view(
         create_new_button: &'a mut button::State,
         scroll: &'a mut scrollable::State,
+        drag: &'a mut draglist::State,
         condions: &'a mut Vec<ConditonsElementView>,
+        order: Vec<usize>,
+        focus: usize
     ) -> Column<'a, CondtionsConfigMessage> {
         let config = unsafe {
             &GLOBAL_CONFIG
-        }.as_ref().unwrap();
+        }.as_ref().unwrap().borrow();
 
         let add_new = Column::new()
                   .align_items(Align::Center)
@@ -821,15 +2058,15 @@ impl <'a> PresetViews {
                         .on_press(CondtionsConfigMessage::AddNewConditons));
 
         let condions: Element<_> = if condions.len() > 0 {
-                condions
-                    .iter_mut()
-                    .enumerate()
-                    .fold(Column::new().spacing(20), |column, (i, element)| {
-                        column.push(element.view().map(move |message| {
-                            CondtionsConfigMessage::ConditionsConfigElementMessage(i, message)
-                        }))
+                draggable_rows(drag, condions, order, focus, |i, element| {
+                    element.view().map(move |message| {
+                        CondtionsConfigMessage::ConditionsConfigElementMessage(i, message)
                     })
-                .into()
+                }, |from, to| {
+                    CondtionsConfigMessage::ConditionsConfigElementMessage(
+                        from, ConditionsConfigElementMessage::MoveConditionTo(to)
+                    )
+                })
         } else {
             Container::new(
                 Text::new(config.get_field(CONDITIONS_CONFIG_EMPTY).to_string().as_str())
@@ -899,6 +2136,18 @@ impl <'a> PresetViews {
                     ConditionsConfigElementMessage::DeleteCondition => {
                         elements.remove(i);
                     },
+                    ConditionsConfigElementMessage::MoveCondition(direction) => {
+                        move_element(elements, i, direction);
+                    },
+                    ConditionsConfigElementMessage::MoveConditionTo(target) => {
+                        move_element_to(elements, i, target);
+                    },
+                    ConditionsConfigElementMessage::DuplicateCondition => {
+                        elements.insert(i + 1, ConditonsElementView::new(
+                                unsafe{&CONDTIONS_CONFIG}.as_ref().unwrap()
+                                .borrow().get_conditon(i + 1)
+                            ))
+                    },
                     ConditionsConfigElementMessage::PickConditions(frame_type) => {
                         *state = CondtionsConfigStetes::IOConditonsPick;
                         *conditions_type = frame_type;
@@ -925,6 +2174,11 @@ impl <'a> PresetViews {
         message: PresetViewMessage,
     ) {
         match message {
+            PresetViewMessage::MatrixMessage(matrix_message) => {
+                if let PresetViews::ConditionsConfigView {matrix, ..} = view {
+                    matrix.update(matrix_message);
+                }
+            },
             PresetViewMessage::CondtionsConfigMessage(message) => {
                 let config = unsafe {
                     &CONDTIONS_CONFIG
@@ -964,7 +2218,7 @@ impl <'a> PresetViews {
 
     fn conditions_view(
         view: &'a mut PresetViews
-    ) -> Element<'a, CondtionsConfigMessage> {
+    ) -> Element<'a, PresetViewMessage> {
         match view {
             PresetViews::ConditionsConfigView {
                 scroll,
@@ -972,13 +2226,46 @@ impl <'a> PresetViews {
                 state,
                 ioconditionsview,
                 frame_type,
-                conditionsview
+                conditionsview,
+                matrix,
+                filter,
+                focus,
+                drag
             } => {
                 match state {
                     CondtionsConfigStetes::CondtionsConfigState => {
                         ioconditionsview.clear();
 
-                        Self::conditions_config_view(create_new_button, scroll, conditionsview).into()
+                        if matrix.is_active() {
+                            let conditionsconfig = unsafe {
+                                &CONDTIONS_CONFIG
+                            }.as_ref().unwrap();
+
+                            let rows = conditionsconfig.borrow().get_conditions().iter()
+                                .map(|condition| {
+                                    let (description, states, controls, ..) =
+                                        condition.borrow().get_data();
+
+                                    (description, states, controls)
+                                }).collect();
+
+                            return matrix.view(rows).map(conditions_matrix_message)
+                        }
+
+                        let order = filter.get_filter().conditions_order(
+                            &unsafe {
+                                &CONDTIONS_CONFIG
+                            }.as_ref().unwrap().borrow().get_conditions()
+                        );
+
+                        Column::new()
+                            .width(Length::Fill)
+                            .align_items(Align::Center)
+                            .push(matrix.toggle_view().map(PresetViewMessage::MatrixMessage))
+                            .push(filter.view().map(PresetViewMessage::FilterMessage))
+                            .push(Element::from(Self::conditions_config_view(create_new_button, scroll, drag, conditionsview, order, *focus))
+                                .map(PresetViewMessage::CondtionsConfigMessage))
+                            .into()
                     },
                     CondtionsConfigStetes::IOConditonsPick => {
                         let conditionsconfig= unsafe {
@@ -999,7 +2286,7 @@ impl <'a> PresetViews {
 
                         Self::conditions_ioconfig_view(
                             scroll, create_new_button, ioconditionsview,
-                            *frame_type
+                            *frame_type, *focus
                         )
                         .map(move |message| {
                             CondtionsConfigMessage::ConditionsConfigElementMessage(
@@ -1007,6 +2294,7 @@ impl <'a> PresetViews {
                                 message
                             )
                         })
+                        .map(PresetViewMessage::CondtionsConfigMessage)
                     },
                     _ => Column::new().into()
                 }